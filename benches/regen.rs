@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use leafwing_abilities::plugin::PoolPlugin;
+use leafwing_abilities::pool::Pool;
+use leafwing_abilities::premade_pools::life::{Life, LifePool};
+
+const N_POOLS: usize = 5000;
+
+fn regenerate_life_pools(c: &mut Criterion) {
+    let mut world = World::new();
+    // A large max keeps the pools regenerating for every iteration
+    world.spawn_batch((0..N_POOLS).map(|_| LifePool::new(Life(0.), Life(f32::MAX), Life(1.))));
+
+    c.bench_function("regenerate 5000 life pools", |b| {
+        b.iter(|| {
+            PoolPlugin::<LifePool>::regenerate_world(
+                &mut world,
+                black_box(Duration::from_millis(16)),
+            )
+        })
+    });
+}
+
+criterion_group!(benches, regenerate_life_pools);
+criterion_main!(benches);
@@ -30,29 +30,24 @@ fn main() {
         .run();
 }
 
-#[derive(Actionlike, Abilitylike, Clone, Copy, PartialEq, Debug, Default)]
+#[derive(Actionlike, Clone, Copy, PartialEq, Debug, Default)]
 enum CookieAbility {
     #[default]
     AddOne,
     DoubleCookies,
 }
 
-impl CookieAbility {
-    fn cooldown(&self) -> Cooldown {
+// Implementing the trait by hand lets us define the cooldown of each ability in one place
+impl Abilitylike for CookieAbility {
+    fn base_cooldown(&self) -> Option<Cooldown> {
         match self {
-            CookieAbility::AddOne => Cooldown::from_secs(0.1),
-            CookieAbility::DoubleCookies => Cooldown::from_secs(5.0),
-        }
-    }
-
-    fn cooldowns() -> CooldownState<CookieAbility> {
-        let mut cooldowns = CooldownState::default();
-        for ability in CookieAbility::variants() {
-            cooldowns.set(ability, ability.cooldown());
+            CookieAbility::AddOne => Some(Cooldown::from_secs(0.1)),
+            CookieAbility::DoubleCookies => Some(Cooldown::from_secs(5.0)),
         }
-        cooldowns
     }
+}
 
+impl CookieAbility {
     fn key_bindings() -> InputMap<CookieAbility> {
         // CookieAbility::AddOne is pressed manually when the cookie is clicked on
         InputMap::default()
@@ -95,7 +90,7 @@ impl CookieBundle {
                 ..default()
             },
             abilities_bundle: AbilitiesBundle {
-                cooldowns: CookieAbility::cooldowns(),
+                cooldowns: CooldownState::from_abilitylike(),
                 ..default()
             },
             input_manager_bundle: InputManagerBundle {
@@ -2,15 +2,28 @@
 #![allow(missing_docs)]
 
 use crate::{
-    charges::ChargeState,
-    cooldown::CooldownState,
-    pool::{AbilityCosts, MaxPoolLessThanZero, Pool},
+    cast_time::CastTimeState,
+    channel::ChannelState,
+    charges::{ChargeDisplayInfo, ChargeState, Charges, CooldownStrategy},
+    cooldown::{ConcurrencyLimit, Cooldown, CooldownState, ExhaustionState},
+    disabled::DisabledAbilities,
+    pool::{AbilityCosts, Pool, PoolError, ResourceCost, UndamagedRequirements},
+    recast::{CastKind, Recast, RecastState},
+    triggers::{LastUsed, TriggeredAbilities},
     Abilitylike, CannotUseAbility,
 };
 // Required due to poor macro hygiene in `WorldQuery` macro
 // Tracked in https://github.com/bevyengine/bevy/issues/6593
-use bevy::{ecs::query::WorldQuery, prelude::Component};
-use leafwing_input_manager::action_state::ActionState;
+use bevy::{
+    ecs::{change_detection::DetectChangesMut, query::WorldQuery},
+    prelude::{Component, Entity, Resource, World},
+    utils::{Duration, HashMap},
+};
+use core::fmt::Debug;
+use core::hash::Hash;
+use leafwing_input_manager::{
+    action_state::ActionState, input_map::InputMap, user_input::UserInput,
+};
 
 /// A custom [`WorldQuery`](bevy::ecs::query::WorldQuery) type that fetches all ability relevant data for you.
 ///
@@ -23,6 +36,9 @@ use leafwing_input_manager::action_state::ActionState;
 /// Once you have a [`AbilityStateItem`] by calling `.iter_mut()` or `.single_mut` on your query
 /// (or a [`AbilityStateReadOnlyItem`] by calling `.iter()` or `.single`),
 /// you can use the methods defined there to perform common tasks quickly and reliably.
+///
+/// To resolve abilities outside of a query, such as in unit tests, call the methods of [`Abilitylike`]
+/// (like [`Abilitylike::trigger_and_pay_cost`]) directly on the components instead.
 #[derive(WorldQuery)]
 #[world_query(mutable)]
 pub struct AbilityState<A: Abilitylike, P: Pool + Component = NullPool> {
@@ -36,23 +52,92 @@ pub struct AbilityState<A: Abilitylike, P: Pool + Component = NullPool> {
     pub pool: Option<&'static mut P>,
     /// The [`AbilityCosts] of each ability, in terms of [`P::Quantity`](Pool::Quantity)
     pub ability_costs: Option<&'static mut AbilityCosts<A, P>>,
+    /// The [`UndamagedRequirements`] of each ability, checked against the [`Pool`] of type `P`
+    pub undamaged_requirements: Option<&'static UndamagedRequirements<A>>,
+    /// The [`RecastState`] associated with each action of type `A` for this entity
+    pub recasts: Option<&'static mut RecastState<A>>,
+    /// The [`ChannelState`] associated with each action of type `A` for this entity
+    pub channels: Option<&'static mut ChannelState<A>>,
+    /// The [`CastTimeState`] associated with each action of type `A` for this entity
+    pub cast_times: Option<&'static mut CastTimeState<A>>,
+    /// The log of successfully [`TriggeredAbilities`] for this entity, if it is recording them
+    pub triggered: Option<&'static mut TriggeredAbilities<A>>,
+    /// The time since each ability was [`LastUsed`] by this entity, if it is tracking this
+    pub last_used: Option<&'static mut LastUsed<A>>,
+    /// The [`ConcurrencyLimit`] on how many abilities of type `A` can be active at once for this entity
+    pub concurrency_limit: Option<&'static ConcurrencyLimit<A>>,
+    /// The [`ExhaustionState`] of each ability of type `A` for this entity, if it is tracking this
+    pub exhaustion: Option<&'static mut ExhaustionState<A>>,
+    /// The [`InputMap`] that binds inputs to the abilities of this entity, if any
+    pub input_map: Option<&'static InputMap<A>>,
+    /// The [`DisabledAbilities`] of this entity, which cannot be used at all
+    pub disabled: Option<&'static DisabledAbilities<A>>,
 }
 
 impl<A: Abilitylike, P: Pool + Component> AbilityStateItem<'_, A, P> {
     /// Is this ability ready?
     ///
     /// Calls [`Abilitylike::ready`] on the specified action.
+    /// Abilities whose recast window is open are always ready, unless they are [disabled](DisabledAbilities).
+    /// While a [`ChannelState`] is channeling, its [`ChannelInterruptPolicy`](crate::channel::ChannelInterruptPolicy) is checked first,
+    /// and abilities whose channel would be cancelled are always ready.
+    /// Costs that are [deferred](crate::cast_time::CostTiming::OnCompletion) until the end of a cast are not checked.
     #[inline]
     pub fn ready(&self, action: A) -> Result<(), CannotUseAbility> {
+        self.ready_with_costs(action, true)
+    }
+
+    /// Is this ability [ready](Self::ready), checking whether its cost could be paid only if `check_costs` is `true`?
+    fn ready_with_costs(&self, action: A, check_costs: bool) -> Result<(), CannotUseAbility> {
         let maybe_pool = self.pool.as_deref();
-        let maybe_ability_costs = self.ability_costs.as_deref();
+        let maybe_ability_costs = match check_costs && !self.defers_cost(action.clone()) {
+            true => self.ability_costs.as_deref(),
+            false => None,
+        };
+
+        if let Some(disabled) = self.disabled {
+            disabled.check(action.clone())?;
+        }
+
+        if let Some(channels) = self.channels.as_deref() {
+            channels.check(action.clone())?;
+            if channels.is_channeling(action.clone()) {
+                return Ok(());
+            }
+        }
+
+        if let Some(recasts) = self.recasts.as_deref() {
+            if recasts.in_window(action.clone()) {
+                return Ok(());
+            }
+        }
+
+        if let Some(requirements) = self.undamaged_requirements {
+            requirements.check(action.clone(), maybe_pool)?;
+        }
 
         action.ready(
             &*self.charges,
             &*self.cooldowns,
             maybe_pool,
             maybe_ability_costs,
-        )
+        )?;
+
+        if let Some(limit) = self.concurrency_limit {
+            limit.check(action, &self.cooldowns)?;
+        }
+
+        Ok(())
+    }
+
+    /// Would [`trigger`](Self::trigger) succeed for this ability?
+    ///
+    /// This performs exactly the same checks as [`trigger`](Self::trigger), including cooldowns, charges and costs,
+    /// but nothing is modified and no resources are spent.
+    /// Unlike [`ready`](Self::ready), the cost is always checked, even for abilities with a cooldown or charges.
+    #[inline]
+    pub fn can_trigger(&self, action: A) -> Result<(), CannotUseAbility> {
+        self.ready_with_cost(action)
     }
 
     /// Is this ability both ready and pressed?
@@ -81,37 +166,829 @@ impl<A: Abilitylike, P: Pool + Component> AbilityStateItem<'_, A, P> {
         }
     }
 
+    /// Collects a [`ChargeDisplayInfo`] for each action, in [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) order.
+    ///
+    /// Calls [`ChargeState::display_info`].
+    #[inline]
+    #[must_use]
+    pub fn charge_display_info(&self) -> Vec<Option<ChargeDisplayInfo>> {
+        self.charges.display_info(&self.cooldowns)
+    }
+
+    /// Returns how long it will be until `action` can next be used, ignoring any resource costs.
+    ///
+    /// Calls [`ChargeState::next_usable_in`].
+    #[inline]
+    #[must_use]
+    pub fn next_usable_in(&self, action: A) -> Option<Duration> {
+        self.charges.next_usable_in(action, &self.cooldowns)
+    }
+
+    /// Returns how much of the [`Pool`] is needed to refill `action` to its max charges.
+    ///
+    /// Each missing charge costs the [`AbilityCosts`] of `action`, as computed by [`ChargeState::pool_cost_to_full`].
+    /// If `action` has no cost or no [`Charges`](crate::charges::Charges), this is [`Pool::ZERO`].
+    #[inline]
+    #[must_use]
+    pub fn pool_cost_to_full(&self, action: A) -> P::Quantity {
+        match self
+            .ability_costs
+            .as_deref()
+            .and_then(|costs| *costs.get(action.clone()))
+        {
+            Some(cost_per_charge) => self.charges.pool_cost_to_full(action, cost_per_charge),
+            None => P::ZERO,
+        }
+    }
+
+    /// Is the cooldown of `action` itself ready, ignoring its charges, costs, group cooldown and the global cooldown?
+    ///
+    /// Calls [`CooldownState::is_ready`]. Together with [`charges_available`](Self::charges_available),
+    /// this lets a UI draw the cooldown sweep and the charge pips of an ability independently.
+    #[inline]
+    #[must_use]
+    pub fn cooldown_ready(&self, action: A) -> bool {
+        self.cooldowns.is_ready(action)
+    }
+
+    /// Is at least one charge available for `action`, ignoring its cooldowns and costs?
+    ///
+    /// Calls [`ChargeState::available`], so this is `true` for abilities without charges.
+    #[inline]
+    #[must_use]
+    pub fn charges_available(&self, action: A) -> bool {
+        self.charges.available(action)
+    }
+
+    /// Returns how long ago `action` was last used, including recasts.
+    ///
+    /// Returns [`None`] if the action has never been used, or if this entity has no [`LastUsed`] component.
+    #[inline]
+    #[must_use]
+    pub fn time_since_last_use(&self, action: A) -> Option<Duration> {
+        self.last_used
+            .as_deref()
+            .and_then(|last_used| last_used.time_since_last_use(action))
+    }
+
+    /// Collects every ability that is currently [ready](Self::ready) and affordable, in [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) order.
+    ///
+    /// This uses the same checks as [`trigger`](AbilityStateItem::trigger), including cooldowns, charges and costs,
+    /// so an AI controller can choose freely between the abilities returned.
+    #[must_use]
+    pub fn ready_abilities(&self) -> Vec<A> {
+        A::variants()
+            .filter(|action| self.ready_with_cost(action.clone()).is_ok())
+            .collect()
+    }
+
+    /// Is at least one ability currently [ready](Self::ready) and affordable?
+    ///
+    /// This is equivalent to `!self.ready_abilities().is_empty()`, but stops at the first ready ability and does not allocate.
+    /// It is useful for skipping the turn of an AI that cannot act, or disabling a whole action bar.
+    #[must_use]
+    pub fn any_ready(&self) -> bool {
+        A::variants().any(|action| self.ready_with_cost(action).is_ok())
+    }
+
+    /// Summarizes the state of every ability in a human-readable report, with one line per ability.
+    ///
+    /// Each line lists whether the ability is [ready](Self::ready), the time until its cooldown is ready, its charges,
+    /// and its cost along with whether it is affordable. Fields are omitted for abilities without a cooldown, charges or cost.
+    /// This is intended for logging during balance testing.
+    #[must_use]
+    pub fn debug_report(&self) -> String
+    where
+        A: Debug,
+        P::Quantity: Debug,
+    {
+        debug_report(
+            |action| self.ready(action),
+            &self.charges,
+            &self.cooldowns,
+            self.pool.as_deref(),
+            self.ability_costs.as_deref(),
+        )
+    }
+
+    /// Packs the readiness of every action into a bitmask, where bit `i` is set if the `i`th action of [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) is [ready](Self::ready) and affordable.
+    ///
+    /// This is useful for performance-sensitive checks of many abilities at once,
+    /// such as intersecting with a mask of the abilities that an AI wants to use.
+    ///
+    /// Only the first 64 actions can be represented: any further actions are never set in the mask.
+    #[must_use]
+    pub fn ready_bitmask(&self) -> u64 {
+        debug_assert!(
+            A::n_variants() <= 64,
+            "ready_bitmask can only represent the first 64 actions"
+        );
+
+        A::variants()
+            .take(64)
+            .enumerate()
+            .filter(|(_, action)| self.ready_with_cost(action.clone()).is_ok())
+            .fold(0, |mask, (i, _)| mask | (1 << i))
+    }
+
+    /// Returns the inputs that are bound to `action` in this entity's [`InputMap`].
+    ///
+    /// This is useful for contextual prompts, such as "Press Q to cast Fireball".
+    /// Returns an empty iterator if this entity has no [`InputMap`].
+    pub fn inputs_for_action(&self, action: A) -> impl Iterator<Item = &UserInput> + '_ {
+        self.input_map
+            .into_iter()
+            .flat_map(move |input_map| input_map.get(action.clone()).iter())
+    }
+
+    /// Returns the action that `input` is bound to in this entity's [`InputMap`], and whether that action is currently [ready](Self::ready).
+    ///
+    /// If `input` is bound to several actions, the first in [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) order is returned.
+    /// Returns [`None`] if `input` is not bound to any action, or if this entity has no [`InputMap`].
+    #[must_use]
+    pub fn action_for_input(
+        &self,
+        input: impl Into<UserInput>,
+    ) -> Option<(A, Result<(), CannotUseAbility>)> {
+        let input = input.into();
+        let (_, action) = self
+            .input_map?
+            .iter()
+            .find(|(inputs, _)| inputs.contains(&input))?;
+        let ready = self.ready(action.clone());
+
+        Some((action, ready))
+    }
+
+    /// Chooses one of the currently ready abilities at random, with a probability proportional to its entry in `weights`.
+    ///
+    /// Abilities that are not ready, whose cost cannot be paid, or that do not have a positive weight, are never chosen.
+    /// Returns [`None`] if no ability can be chosen.
+    ///
+    /// `rng` must return a uniformly distributed random number in the range `[0, 1)`,
+    /// such as `|| rng.f32()` for a seeded `fastrand::Rng`.
+    /// This is useful for simple AI, and is deterministic for a deterministic `rng`.
+    #[must_use]
+    pub fn choose_ready_weighted(
+        &self,
+        weights: &HashMap<A, f32>,
+        rng: impl FnMut() -> f32,
+    ) -> Option<A>
+    where
+        A: Hash + Eq,
+    {
+        choose_weighted(weights, |action| self.ready_with_cost(action).is_ok(), rng)
+    }
+
+    /// Casts this ability, paying all of its requirements at once. This is the recommended way to use abilities.
+    ///
+    /// Each requirement is checked in the following order, and the error of the first one that fails is returned:
+    /// 1. the ability must not be [disabled](DisabledAbilities): [`CannotUseAbility::Disabled`]
+    /// 2. any active channel must allow the ability to be used: [`CannotUseAbility::Channeling`]
+    /// 3. if the ability is being channeled, its channel is cancelled instead, and no further requirements apply
+    /// 4. if the recast window of the ability is open, it is recast instead, and no further requirements apply
+    /// 5. the global cooldown must be ready, unless the ability [ignores it](CooldownState::ignores_global_cooldown):
+    ///    [`CannotUseAbility::OnGlobalCooldown`]
+    /// 6. the ability's own cooldown must be ready, if it gates use rather than recharging its [`Charges`]:
+    ///    [`CannotUseAbility::OnCooldown`]
+    /// 7. the cooldown of the ability's [group](CooldownState::set_group) must be ready: [`CannotUseAbility::OnCooldown`]
+    /// 8. at least one charge must be available: [`CannotUseAbility::NoCharges`]
+    /// 9. the cost of the ability must be affordable: [`CannotUseAbility::PoolInsufficient`]
+    /// 10. any other requirements of [`ready`](Self::ready), such as [`UndamagedRequirements`] and the [`ConcurrencyLimit`]
+    ///
+    /// On success, charges, cooldowns and costs are all spent together, exactly as by [`trigger_and_pay_cost`](Self::trigger_and_pay_cost).
+    /// On failure, this call has no effect.
+    /// Lower-level methods such as [`trigger`](Self::trigger) remain available for custom rules.
+    ///
+    /// Returns which kind of cast was performed.
+    pub fn cast(&mut self, action: A) -> Result<CastKind, CannotUseAbility> {
+        if let Some(disabled) = self.disabled {
+            disabled.check(action.clone())?;
+        }
+
+        let cancels_channel = match self.channels.as_deref() {
+            Some(channels) => {
+                channels.check(action.clone())?;
+                channels.is_channeling(action.clone())
+            }
+            None => false,
+        };
+
+        let is_recast = match self.recasts.as_deref() {
+            Some(recasts) => recasts.in_window(action.clone()),
+            None => false,
+        };
+
+        if !cancels_channel && !is_recast {
+            if !self.cooldowns.ignores_global_cooldown(action.clone()) {
+                self.cooldowns.gcd_ready()?;
+            }
+
+            let charges = self.charges.get(action.clone());
+            let cooldown_gates_use = match charges {
+                Some(charges) => charges.cooldown_strat == CooldownStrategy::RateLimit,
+                None => true,
+            };
+            if cooldown_gates_use {
+                if let Some(cooldown) = self.cooldowns.get(action.clone()) {
+                    cooldown.ready()?;
+                }
+            }
+            self.cooldowns.group_ready(action.clone())?;
+
+            if let Some(charges) = charges {
+                if !charges.available() {
+                    return Err(CannotUseAbility::NoCharges);
+                }
+            }
+
+            self.check_cost(action.clone())?;
+            self.ready(action.clone())?;
+        }
+
+        let cast_kind = self.trigger_or_recast(action.clone())?;
+        if cast_kind == CastKind::Initial && !self.defers_cost(action.clone()) {
+            self.pay_cost(action)?;
+        }
+
+        Ok(cast_kind)
+    }
+
     /// Triggers this ability, depleting a charge if available.
     ///
     /// Calls [`Abilitylike::trigger`] on the specified action.
+    /// If the recast window of this ability is open, the ability is recast instead: see [`Self::trigger_or_recast`].
+    /// This does not pay the cost of the ability: prefer [`cast`](Self::cast), which does.
     #[inline]
     pub fn trigger(&mut self, action: A) -> Result<(), CannotUseAbility> {
-        let maybe_pool = self.pool.as_deref_mut();
-        let maybe_ability_costs = self.ability_costs.as_deref();
+        self.trigger_or_recast(action).map(|_| ())
+    }
+
+    /// Triggers this ability, or recasts it if its recast window is open.
+    ///
+    /// Recasting closes the recast window, and does not deplete charges, trigger cooldowns or pay costs.
+    /// Otherwise, this calls [`Abilitylike::trigger`] on the specified action,
+    /// and opens the recast window of the ability (if it has one) on success.
+    /// If this entity has a [`ConcurrencyLimit`], its overflow policy is applied before the ability is triggered.
+    /// [Disabled](DisabledAbilities) abilities can neither be cast nor recast.
+    ///
+    /// If this entity has a [`ChannelState`], its [`ChannelInterruptPolicy`](crate::channel::ChannelInterruptPolicy) is consulted first.
+    /// Triggering an ability that is being channeled may cancel its channel instead, returning [`CastKind::ChannelCancelled`].
+    /// On a successful cast, any other active channel is cancelled, and the channel of this ability (if it has one) is started.
+    ///
+    /// Returns which kind of cast was performed.
+    #[inline]
+    pub fn trigger_or_recast(&mut self, action: A) -> Result<CastKind, CannotUseAbility> {
+        self.trigger_or_recast_with_costs(action, true)
+    }
 
-        action.trigger(
+    /// Triggers this ability like [`trigger_or_recast`](Self::trigger_or_recast),
+    /// checking whether its cost could be paid only if `check_costs` is `true`.
+    fn trigger_or_recast_with_costs(
+        &mut self,
+        action: A,
+        check_costs: bool,
+    ) -> Result<CastKind, CannotUseAbility> {
+        if let Some(disabled) = self.disabled {
+            disabled.check(action.clone())?;
+        }
+
+        if let Some(channels) = self.channels.as_deref_mut() {
+            channels.check(action.clone())?;
+            if channels.cancel(action.clone()) {
+                return Ok(CastKind::ChannelCancelled);
+            }
+        }
+
+        if let Some(recasts) = self.recasts.as_deref_mut() {
+            if recasts.recast(action.clone()) {
+                if let Some(last_used) = self.last_used.as_deref_mut() {
+                    last_used.record(action.clone());
+                }
+
+                if let Some(triggered) = self.triggered.as_deref_mut() {
+                    triggered.record(action, CastKind::Recast);
+                }
+
+                return Ok(CastKind::Recast);
+            }
+        }
+
+        if let Some(requirements) = self.undamaged_requirements {
+            requirements.check(action.clone(), self.pool.as_deref())?;
+        }
+
+        let check_costs = check_costs && !self.defers_cost(action.clone());
+        let (maybe_pool, maybe_ability_costs) = match check_costs {
+            true => (self.pool.as_deref_mut(), self.ability_costs.as_deref()),
+            false => (None, None),
+        };
+
+        // Only make room for this ability once we know that it can actually be used
+        if let Some(limit) = self.concurrency_limit {
+            action.ready(
+                &*self.charges,
+                &*self.cooldowns,
+                maybe_pool.as_deref(),
+                maybe_ability_costs,
+            )?;
+            limit.make_room(action.clone(), &mut self.cooldowns)?;
+        }
+
+        action.clone().trigger(
             &mut *self.charges,
             &mut *self.cooldowns,
             maybe_pool,
             maybe_ability_costs,
+        )?;
+
+        if let Some(recasts) = self.recasts.as_deref_mut() {
+            recasts.open(action.clone());
+        }
+
+        if let Some(channels) = self.channels.as_deref_mut() {
+            channels.cancel_all();
+            channels.start(action.clone());
+        }
+
+        if let Some(cast_times) = self.cast_times.as_deref_mut() {
+            cast_times.start(action.clone());
+        }
+
+        if let Some(exhaustion) = self.exhaustion.as_deref_mut() {
+            exhaustion.record_use(action.clone());
+        }
+
+        if let Some(last_used) = self.last_used.as_deref_mut() {
+            last_used.record(action.clone());
+        }
+
+        if let Some(triggered) = self.triggered.as_deref_mut() {
+            triggered.record(action, CastKind::Initial);
+        }
+
+        Ok(CastKind::Initial)
+    }
+
+    /// Triggers every ability in `actions` together, paying their costs, if and only if all of them can be used.
+    ///
+    /// Every ability must be [`ready`](Self::ready) before any of them are triggered.
+    /// They are then triggered in order, so that charges, cooldowns, channels and [`ConcurrencyLimit`]s
+    /// used up by earlier abilities in `actions` are accounted for, and their combined cost is paid from the [`Pool`] `P`.
+    /// If any ability cannot be used, its error is returned and every change is rolled back, so that nothing is triggered or spent.
+    ///
+    /// The abilities do not block each other through the global cooldown:
+    /// it is started once, after all of them have been triggered (unless they all ignore it).
+    /// Abilities whose recast window is open are recast instead, without paying their cost.
+    pub fn trigger_all(&mut self, actions: &[A]) -> Result<(), CannotUseAbility> {
+        for action in actions {
+            self.ready(action.clone())?;
+        }
+
+        let charges = (*self.charges).clone();
+        let cooldowns = (*self.cooldowns).clone();
+        let recasts = self.recasts.as_deref().cloned();
+        let channels = self.channels.as_deref().cloned();
+        let cast_times = self.cast_times.as_deref().cloned();
+        let exhaustion = self.exhaustion.as_deref().cloned();
+        let last_used = self.last_used.as_deref().cloned();
+        let triggered = self.triggered.as_deref().cloned();
+
+        // The pool is only touched once every ability has been triggered, so it never needs to be rolled back
+        let result = self.trigger_all_unpaid(actions).and_then(|total_cost| {
+            match self.pool.as_deref_mut() {
+                Some(pool) => pool.expend(total_cost),
+                None if total_cost > P::ZERO => Err(CannotUseAbility::PoolInsufficient {
+                    deficit: total_cost.into(),
+                }),
+                None => Ok(()),
+            }
+        });
+
+        if result.is_err() {
+            *self.charges = charges;
+            *self.cooldowns = cooldowns;
+            restore(self.recasts.as_deref_mut(), recasts);
+            restore(self.channels.as_deref_mut(), channels);
+            restore(self.cast_times.as_deref_mut(), cast_times);
+            restore(self.exhaustion.as_deref_mut(), exhaustion);
+            restore(self.last_used.as_deref_mut(), last_used);
+            restore(self.triggered.as_deref_mut(), triggered);
+        }
+
+        result
+    }
+
+    /// Triggers each ability in `actions` in order, without paying their costs.
+    ///
+    /// Returns the total cost that is owed, or the first error encountered.
+    /// Abilities before the one that failed are left triggered.
+    fn trigger_all_unpaid(&mut self, actions: &[A]) -> Result<P::Quantity, CannotUseAbility> {
+        // Otherwise, each ability after the first would be blocked by the global cooldown
+        let global_cooldown = self.cooldowns.global_cooldown.take();
+        let mut result = Ok(());
+        let mut total_cost = P::ZERO;
+        let mut global_cooldown_action = None;
+
+        for action in actions {
+            match self.trigger_or_recast_with_costs(action.clone(), false) {
+                Ok(CastKind::Initial) => {
+                    if global_cooldown_action.is_none()
+                        && !self.cooldowns.ignores_global_cooldown(action.clone())
+                    {
+                        global_cooldown_action = Some(action.clone());
+                    }
+
+                    if !self.defers_cost(action.clone()) {
+                        if let Some(ability_costs) = self.ability_costs.as_deref() {
+                            if let Some(cost) = ability_costs.get(action.clone()) {
+                                total_cost += *cost;
+                            }
+                        }
+                    }
+                }
+                Ok(CastKind::Recast | CastKind::ChannelCancelled) => (),
+                Err(error) => {
+                    result = Err(error);
+                    break;
+                }
+            }
+        }
+
+        self.cooldowns.global_cooldown = global_cooldown;
+        result?;
+
+        if let Some(action) = global_cooldown_action {
+            self.cooldowns.trigger_global_cooldown(action)?;
+        }
+
+        Ok(total_cost)
+    }
+
+    /// Is this ability [ready](Self::ready), and can its cost be paid?
+    ///
+    /// Unlike [`ready`](Self::ready), the cost is always checked, even for abilities with a cooldown or charges.
+    /// Recasts and cancelled channels are free, so their cost is not checked.
+    fn ready_with_cost(&self, action: A) -> Result<(), CannotUseAbility> {
+        self.ready(action.clone())?;
+
+        match casts_for_free(
+            action.clone(),
+            self.recasts.as_deref(),
+            self.channels.as_deref(),
+        ) {
+            true => Ok(()),
+            false => check_cost(
+                action,
+                self.pool.as_deref(),
+                self.ability_costs.as_deref(),
+                self.cast_times.as_deref(),
+            ),
+        }
+    }
+
+    /// Is the cost of this ability paid when its [`CastTime`](crate::cast_time::CastTime) finishes, rather than when it is triggered?
+    fn defers_cost(&self, action: A) -> bool {
+        match self.cast_times.as_deref() {
+            Some(cast_times) => cast_times.defers_cost(action),
+            None => false,
+        }
+    }
+
+    /// Triggers this ability, but only if the fraction of the `target_pool` remaining is below the `threshold`.
+    ///
+    /// This is used for "execute" abilities, which can only be used on targets with (for example) less than 20% of their life remaining.
+    /// The `target_pool` is typically read from another entity, and its [`Pool::fraction`] is compared to the `threshold`.
+    ///
+    /// If the target is at or above the threshold, [`CannotUseAbility::TargetAboveThreshold`] is returned and this call has no effect.
+    /// Otherwise, this calls [`Self::trigger`].
+    #[inline]
+    pub fn trigger_if_target_below<T: Pool>(
+        &mut self,
+        action: A,
+        target_pool: &T,
+        threshold: f32,
+    ) -> Result<(), CannotUseAbility> {
+        if target_pool.fraction() < threshold {
+            self.trigger(action)
+        } else {
+            Err(CannotUseAbility::TargetAboveThreshold)
+        }
+    }
+
+    /// Pays the cost of this ability from the [`Pool`], without triggering it.
+    ///
+    /// The [`CooldownState`] and [`ChargeState`] of this ability are not affected.
+    /// This is useful for upkeep costs and other resource transactions that should not use the ability.
+    ///
+    /// If the cost cannot be paid, [`CannotUseAbility::PoolInsufficient`] is returned and this call has no effect.
+    #[inline]
+    pub fn pay_cost(&mut self, action: A) -> Result<(), CannotUseAbility> {
+        match (self.pool.as_deref_mut(), self.ability_costs.as_deref_mut()) {
+            (Some(pool), Some(ability_costs)) => ability_costs.pay_cost(action, pool),
+            // The pool does not exist, but the cost might
+            (None, Some(ability_costs)) => match ability_costs.get(action) {
+                Some(cost) if *cost > P::ZERO => Err(CannotUseAbility::PoolInsufficient {
+                    deficit: (*cost).into(),
+                }),
+                _ => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Triggers this ability and pays its cost from the [`Pool`].
+    ///
+    /// If the cost cannot be paid, [`CannotUseAbility::PoolInsufficient`] is returned and the ability is not triggered.
+    /// Recasts and cancelled channels do not pay costs: see [`Self::trigger_or_recast`].
+    pub fn trigger_and_pay_cost(&mut self, action: A) -> Result<(), CannotUseAbility> {
+        if let Some(disabled) = self.disabled {
+            disabled.check(action.clone())?;
+        }
+
+        if !casts_for_free(
+            action.clone(),
+            self.recasts.as_deref(),
+            self.channels.as_deref(),
+        ) {
+            self.check_cost(action.clone())?;
+        }
+
+        match self.trigger_or_recast(action.clone())? {
+            CastKind::Initial if !self.defers_cost(action.clone()) => self.pay_cost(action),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns [`CannotUseAbility::PoolInsufficient`] if the cost of this ability cannot currently be paid.
+    fn check_cost(&self, action: A) -> Result<(), CannotUseAbility> {
+        check_cost(
+            action,
+            self.pool.as_deref(),
+            self.ability_costs.as_deref(),
+            self.cast_times.as_deref(),
         )
     }
 
+    /// Triggers this ability without checking or paying its cost, such as for a "next spell is free" buff.
+    ///
+    /// Cooldowns, charges and every other requirement of [`trigger`](Self::trigger) are still respected,
+    /// and the [`Pool`] is left untouched.
+    /// If the ability cannot be used, its error is returned and this call has no effect, not even on change detection.
+    pub fn trigger_ignoring_cost(&mut self, action: A) -> Result<(), CannotUseAbility> {
+        // Check first, so that failures do not mark any component as changed
+        self.ready_with_costs(action.clone(), false)?;
+        self.trigger_or_recast_with_costs(action, false).map(|_| ())
+    }
+
+    /// Triggers this ability and pays its cost like [`trigger_and_pay_cost`](Self::trigger_and_pay_cost),
+    /// even if its cooldown is not ready, such as for an "instant reset" buff.
+    ///
+    /// The cooldown of the ability, its [group](CooldownState::set_group) cooldown and the global cooldown are all ignored,
+    /// and then restarted as usual. Charges and costs are still required:
+    /// abilities with charges are only blocked by their cooldown if it [rate limits](CooldownStrategy::RateLimit) them.
+    /// If the ability cannot be used for any other reason, its error is returned and this call has no effect,
+    /// not even on change detection.
+    pub fn trigger_ignoring_cooldown(&mut self, action: A) -> Result<(), CannotUseAbility> {
+        let previous_cooldowns = (*self.cooldowns).clone();
+
+        let cooldown_gates_use = match self.charges.get(action.clone()) {
+            Some(charges) => charges.cooldown_strat == CooldownStrategy::RateLimit,
+            None => true,
+        };
+        // Cooldowns are only marked as changed once the ability is known to be usable
+        let cooldowns = self.cooldowns.bypass_change_detection();
+        if cooldown_gates_use {
+            cooldowns.reset(action.clone());
+        }
+        if let Some(group) = cooldowns.group(action.clone()) {
+            cooldowns.group_cooldown_mut(group).refresh();
+        }
+        if let Some(global_cooldown) = cooldowns.global_cooldown.as_mut() {
+            global_cooldown.refresh();
+        }
+
+        let result = self
+            .ready_with_cost(action.clone())
+            .and_then(|_| self.trigger_and_pay_cost(action));
+        if result.is_err() {
+            *self.cooldowns.bypass_change_detection() = previous_cooldowns;
+        }
+        result
+    }
+
+    /// Triggers this ability and pays its cost, like [`trigger_and_pay_cost`](Self::trigger_and_pay_cost),
+    /// returning a [`RefundToken`] that records exactly what was spent.
+    ///
+    /// Pass the token to [`refund`](Self::refund) to undo the trigger, such as when a cast is interrupted.
+    pub fn trigger_with_refund_token(
+        &mut self,
+        action: A,
+    ) -> Result<RefundToken<A, P>, CannotUseAbility> {
+        let charges = self.charges.get(action.clone()).clone();
+        let cooldown = *self.cooldowns.get(action.clone());
+        let group_cooldown = self
+            .cooldowns
+            .group(action.clone())
+            .map(|group| *self.cooldowns.group_cooldown(group));
+        let global_cooldown = self.cooldowns.global_cooldown;
+        let recast = self
+            .recasts
+            .as_deref()
+            .and_then(|recasts| recasts.get(action.clone()).clone());
+        // Triggering can cancel the channels and casts of other abilities too, so the whole state is recorded
+        let channels = self.channels.as_deref().cloned();
+        let cast_times = self.cast_times.as_deref().cloned();
+        let pool_before = self.pool.as_deref().map(Pool::current);
+
+        self.trigger_and_pay_cost(action.clone())?;
+
+        let cost = match (pool_before, self.pool.as_deref()) {
+            (Some(before), Some(pool)) => before - pool.current(),
+            _ => P::ZERO,
+        };
+
+        Ok(RefundToken {
+            action,
+            cost,
+            charges,
+            cooldown,
+            group_cooldown,
+            global_cooldown,
+            recast,
+            channels,
+            cast_times,
+        })
+    }
+
+    /// Undoes a trigger performed by [`trigger_with_refund_token`](Self::trigger_with_refund_token).
+    ///
+    /// The cost is returned to the [`Pool`], and the charges, cooldown, global cooldown and recast window
+    /// of the ability are restored to exactly their state before it was triggered, along with the [`ChannelState`] and [`CastTimeState`].
+    /// As a result, a refunded cast with a [deferred cost](crate::cast_time::CostTiming::OnCompletion) never finishes, and its cost is never paid.
+    /// This should be called on the same frame as the trigger: any ticking since then is also undone.
+    ///
+    /// The [`LastUsed`], [`TriggeredAbilities`] and [`ExhaustionState`] records of the trigger are kept,
+    /// and abilities deactivated by a [`ConcurrencyLimit`] are not reactivated.
+    pub fn refund(&mut self, token: RefundToken<A, P>) {
+        *self.charges.get_mut(token.action.clone()) = token.charges;
+        *self.cooldowns.get_mut(token.action.clone()) = token.cooldown;
+        if let (Some(group), Some(group_cooldown)) = (
+            self.cooldowns.group(token.action.clone()),
+            token.group_cooldown,
+        ) {
+            *self.cooldowns.group_cooldown_mut(group) = group_cooldown;
+        }
+        self.cooldowns.global_cooldown = token.global_cooldown;
+
+        if let Some(recasts) = self.recasts.as_deref_mut() {
+            *recasts.get_mut(token.action) = token.recast;
+        }
+        restore(self.channels.as_deref_mut(), token.channels);
+        restore(self.cast_times.as_deref_mut(), token.cast_times);
+
+        if let Some(pool) = self.pool.as_deref_mut() {
+            pool.replenish(token.cost);
+        }
+    }
+
+    /// Triggers the ability, paying both its cost in the [`Pool`] `P` and the `extra_costs`, which may be spent from other pools.
+    ///
+    /// Every cost is checked before anything is spent: if any of them cannot be paid,
+    /// [`CannotUseAbility::PoolInsufficient`] is returned, the ability is not triggered and no resources are spent.
+    /// Recasts and cancelled channels are free: neither cost is checked or paid.
+    ///
+    /// The extra costs cannot be deferred until a [`CastTime`](crate::cast_time::CastTime) finishes.
+    /// If the cost of the ability is [paid on completion](crate::cast_time::CostTiming::OnCompletion),
+    /// [`CannotUseAbility::CostDeferred`] is returned and this call has no effect.
+    ///
+    /// To pay costs from a second pool type, add it (and its [`AbilityCosts`]) to the same query:
+    ///
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use leafwing_abilities::pool::PoolCost;
+    /// use leafwing_abilities::prelude::*;
+    /// use leafwing_abilities::premade_pools::life::LifePool;
+    /// use leafwing_abilities::premade_pools::mana::ManaPool;
+    /// use leafwing_input_manager::Actionlike;
+    ///
+    /// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+    /// enum Action {
+    ///     BloodMagic,
+    /// }
+    ///
+    /// fn cast_blood_magic(
+    ///     mut query: Query<(
+    ///         AbilityState<Action, ManaPool>,
+    ///         &mut LifePool,
+    ///         &AbilityCosts<Action, LifePool>,
+    ///     )>,
+    /// ) {
+    ///     for (mut ability_state, mut life, life_costs) in query.iter_mut() {
+    ///         let mut life_cost = PoolCost::new(&mut *life, life_costs);
+    ///         let _result = ability_state.trigger_and_pay_costs(Action::BloodMagic, &mut life_cost);
+    ///     }
+    /// }
+    /// # bevy::ecs::system::assert_is_system(cast_blood_magic);
+    /// ```
+    pub fn trigger_and_pay_costs(
+        &mut self,
+        action: A,
+        extra_costs: &mut impl ResourceCost<A>,
+    ) -> Result<(), CannotUseAbility> {
+        if casts_for_free(
+            action.clone(),
+            self.recasts.as_deref(),
+            self.channels.as_deref(),
+        ) {
+            self.trigger_or_recast(action)?;
+            return Ok(());
+        }
+
+        // Check affordability before triggering, so that failures have no effect
+        self.ready(action.clone())?;
+        if self.defers_cost(action.clone()) {
+            return Err(CannotUseAbility::CostDeferred);
+        }
+        extra_costs.can_afford(action.clone())?;
+
+        match self.trigger_or_recast(action.clone())? {
+            CastKind::Initial => {
+                self.pay_cost(action.clone())?;
+                extra_costs.pay(action)
+            }
+            CastKind::Recast | CastKind::ChannelCancelled => Ok(()),
+        }
+    }
+
+    /// Triggers every ability that was just pressed, paying its cost, and reports the outcome for each of them.
+    ///
+    /// Abilities are processed in [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) order,
+    /// so earlier abilities can spend resources from the shared [`Pool`] that later abilities needed.
+    /// Calls [`Self::trigger_and_pay_cost`] on each ability.
+    pub fn trigger_all_just_pressed(&mut self) -> Vec<(A, Result<(), CannotUseAbility>)> {
+        let just_pressed: Vec<A> = A::variants()
+            .filter(|action| self.action_state.just_pressed(action.clone()))
+            .collect();
+
+        just_pressed
+            .into_iter()
+            .map(|action| {
+                let result = self.trigger_and_pay_cost(action.clone());
+                (action, result)
+            })
+            .collect()
+    }
+
+    /// Spends `n` charges of `charge_action` to reduce the cooldown of `target_action` by `n * per_charge_reduction`.
+    ///
+    /// The cooldown cannot be reduced below zero: excess reduction is lost.
+    /// If `target_action` has no [`Cooldown`](crate::cooldown::Cooldown), the charges are still spent.
+    /// The charges are spent one at a time with [`Charges::expend`], so they recharge as if `charge_action` had been used:
+    /// if they are recovered by the cooldown of `charge_action`, that cooldown is started.
+    ///
+    /// If `charge_action` has fewer than `n` charges available (or has no [`Charges`](crate::charges::Charges) at all),
+    /// [`CannotUseAbility::NoCharges`] is returned and this call has no effect.
+    pub fn spend_charges_to_reduce_cooldown(
+        &mut self,
+        charge_action: A,
+        n: u8,
+        target_action: A,
+        per_charge_reduction: Duration,
+    ) -> Result<(), CannotUseAbility> {
+        let charges = match self.charges.get_mut(charge_action.clone()) {
+            Some(charges) if charges.charges() >= n => charges,
+            _ => return Err(CannotUseAbility::NoCharges),
+        };
+
+        for _ in 0..n {
+            charges.expend()?;
+        }
+
+        // Start recovering the spent charges, unless the cooldown is already doing so
+        if charges.recharging() {
+            if let Some(cooldown) = self.cooldowns.get_mut(charge_action) {
+                if cooldown.ready().is_ok() {
+                    cooldown.trigger()?;
+                }
+            }
+        }
+
+        if let Some(cooldown) = self.cooldowns.get_mut(target_action) {
+            let reduction = per_charge_reduction.saturating_mul(n.into());
+            cooldown.set_remaining(cooldown.remaining().saturating_sub(reduction));
+        }
+
+        Ok(())
+    }
+
     /// Triggers this ability (and depletes available charges), if action is pressed.
     ///
     /// Calls [`Abilitylike::trigger`] on the specified action.
     #[inline]
     pub fn trigger_if_pressed(&mut self, action: A) -> Result<(), CannotUseAbility> {
         if self.action_state.just_pressed(action.clone()) {
-            let maybe_pool = self.pool.as_deref_mut();
-            let maybe_ability_costs = self.ability_costs.as_deref();
-
-            action.trigger(
-                &mut *self.charges,
-                &mut *self.cooldowns,
-                maybe_pool,
-                maybe_ability_costs,
-            )
+            self.trigger(action)
         } else {
             Err(CannotUseAbility::NotPressed)
         }
@@ -123,15 +1000,7 @@ impl<A: Abilitylike, P: Pool + Component> AbilityStateItem<'_, A, P> {
     #[inline]
     pub fn trigger_if_just_pressed(&mut self, action: A) -> Result<(), CannotUseAbility> {
         if self.action_state.just_pressed(action.clone()) {
-            let maybe_pool = self.pool.as_deref_mut();
-            let maybe_ability_costs = self.ability_costs.as_deref();
-
-            action.trigger(
-                &mut *self.charges,
-                &mut *self.cooldowns,
-                maybe_pool,
-                maybe_ability_costs,
-            )
+            self.trigger(action)
         } else {
             Err(CannotUseAbility::NotPressed)
         }
@@ -142,36 +1011,482 @@ impl<A: Abilitylike, P: Pool + Component> AbilityStateReadOnlyItem<'_, A, P> {
     /// Is this ability ready?
     ///
     /// Calls [`Abilitylike::ready`] on the specified action.
+    /// Abilities whose recast window is open are always ready, unless they are [disabled](DisabledAbilities).
+    /// While a [`ChannelState`] is channeling, its [`ChannelInterruptPolicy`](crate::channel::ChannelInterruptPolicy) is checked first,
+    /// and abilities whose channel would be cancelled are always ready.
+    /// Costs that are [deferred](crate::cast_time::CostTiming::OnCompletion) until the end of a cast are not checked.
     #[inline]
     pub fn ready(&self, action: A) -> Result<(), CannotUseAbility> {
-        action.ready(self.charges, self.cooldowns, self.pool, self.ability_costs)
-    }
+        let maybe_ability_costs = match self.cast_times {
+            Some(cast_times) if cast_times.defers_cost(action.clone()) => None,
+            _ => self.ability_costs,
+        };
 
-    /// Is this ability both ready and pressed?
-    ///
-    /// The error value for "this ability is not pressed" will be prioritized over "this ability is not ready".
-    #[inline]
-    pub fn ready_and_pressed(&self, action: A) -> Result<(), CannotUseAbility> {
-        if self.action_state.pressed(action.clone()) {
-            self.ready(action)?;
-            Ok(())
-        } else {
-            Err(CannotUseAbility::NotPressed)
+        if let Some(disabled) = self.disabled {
+            disabled.check(action.clone())?;
         }
-    }
 
-    /// Is this ability both ready and just pressed?
-    ///
-    /// The error value for "this ability is not pressed" will be prioritized over "this ability is not ready".
-    #[inline]
-    pub fn ready_and_just_pressed(&self, action: A) -> Result<(), CannotUseAbility> {
-        if self.action_state.just_pressed(action.clone()) {
-            self.ready(action)?;
-            Ok(())
+        if let Some(channels) = self.channels {
+            channels.check(action.clone())?;
+            if channels.is_channeling(action.clone()) {
+                return Ok(());
+            }
+        }
+
+        if let Some(recasts) = self.recasts {
+            if recasts.in_window(action.clone()) {
+                return Ok(());
+            }
+        }
+
+        if let Some(requirements) = self.undamaged_requirements {
+            requirements.check(action.clone(), self.pool)?;
+        }
+
+        action.ready(self.charges, self.cooldowns, self.pool, maybe_ability_costs)?;
+
+        if let Some(limit) = self.concurrency_limit {
+            limit.check(action, self.cooldowns)?;
+        }
+
+        Ok(())
+    }
+
+    /// Is this ability [ready](Self::ready), and can its cost be paid?
+    ///
+    /// Unlike [`ready`](Self::ready), the cost is always checked, even for abilities with a cooldown or charges.
+    /// Recasts and cancelled channels are free, so their cost is not checked.
+    fn ready_with_cost(&self, action: A) -> Result<(), CannotUseAbility> {
+        self.ready(action.clone())?;
+
+        match casts_for_free(action.clone(), self.recasts, self.channels) {
+            true => Ok(()),
+            false => check_cost(action, self.pool, self.ability_costs, self.cast_times),
+        }
+    }
+
+    /// Would [`trigger`](AbilityStateItem::trigger) succeed for this ability?
+    ///
+    /// This performs exactly the same checks as [`trigger`](AbilityStateItem::trigger), including cooldowns, charges and costs,
+    /// but nothing is modified and no resources are spent.
+    /// Unlike [`ready`](Self::ready), the cost is always checked, even for abilities with a cooldown or charges.
+    ///
+    /// This is useful for greying out buttons or showing tooltips, from a query that is only iterated immutably:
+    ///
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use leafwing_abilities::prelude::*;
+    /// use leafwing_input_manager::Actionlike;
+    ///
+    /// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+    /// enum Action {
+    ///     Fireball,
+    /// }
+    ///
+    /// fn grey_out_buttons(query: Query<AbilityState<Action>>) {
+    ///     for ability_state in query.iter() {
+    ///         let _usable = ability_state.can_trigger(Action::Fireball).is_ok();
+    ///     }
+    /// }
+    /// # bevy::ecs::system::assert_is_system(grey_out_buttons);
+    /// ```
+    #[inline]
+    pub fn can_trigger(&self, action: A) -> Result<(), CannotUseAbility> {
+        self.ready_with_cost(action)
+    }
+
+    /// Is this ability both ready and pressed?
+    ///
+    /// The error value for "this ability is not pressed" will be prioritized over "this ability is not ready".
+    #[inline]
+    pub fn ready_and_pressed(&self, action: A) -> Result<(), CannotUseAbility> {
+        if self.action_state.pressed(action.clone()) {
+            self.ready(action)?;
+            Ok(())
         } else {
             Err(CannotUseAbility::NotPressed)
         }
     }
+
+    /// Is this ability both ready and just pressed?
+    ///
+    /// The error value for "this ability is not pressed" will be prioritized over "this ability is not ready".
+    #[inline]
+    pub fn ready_and_just_pressed(&self, action: A) -> Result<(), CannotUseAbility> {
+        if self.action_state.just_pressed(action.clone()) {
+            self.ready(action)?;
+            Ok(())
+        } else {
+            Err(CannotUseAbility::NotPressed)
+        }
+    }
+
+    /// Collects a [`ChargeDisplayInfo`] for each action, in [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) order.
+    ///
+    /// Calls [`ChargeState::display_info`].
+    #[inline]
+    #[must_use]
+    pub fn charge_display_info(&self) -> Vec<Option<ChargeDisplayInfo>> {
+        self.charges.display_info(self.cooldowns)
+    }
+
+    /// Returns how long it will be until `action` can next be used, ignoring any resource costs.
+    ///
+    /// Calls [`ChargeState::next_usable_in`].
+    #[inline]
+    #[must_use]
+    pub fn next_usable_in(&self, action: A) -> Option<Duration> {
+        self.charges.next_usable_in(action, self.cooldowns)
+    }
+
+    /// Returns how much of the [`Pool`] is needed to refill `action` to its max charges.
+    ///
+    /// Each missing charge costs the [`AbilityCosts`] of `action`, as computed by [`ChargeState::pool_cost_to_full`].
+    /// If `action` has no cost or no [`Charges`](crate::charges::Charges), this is [`Pool::ZERO`].
+    #[inline]
+    #[must_use]
+    pub fn pool_cost_to_full(&self, action: A) -> P::Quantity {
+        match self
+            .ability_costs
+            .and_then(|costs| *costs.get(action.clone()))
+        {
+            Some(cost_per_charge) => self.charges.pool_cost_to_full(action, cost_per_charge),
+            None => P::ZERO,
+        }
+    }
+
+    /// Is the cooldown of `action` itself ready, ignoring its charges, costs, group cooldown and the global cooldown?
+    ///
+    /// Calls [`CooldownState::is_ready`]. Together with [`charges_available`](Self::charges_available),
+    /// this lets a UI draw the cooldown sweep and the charge pips of an ability independently.
+    #[inline]
+    #[must_use]
+    pub fn cooldown_ready(&self, action: A) -> bool {
+        self.cooldowns.is_ready(action)
+    }
+
+    /// Is at least one charge available for `action`, ignoring its cooldowns and costs?
+    ///
+    /// Calls [`ChargeState::available`], so this is `true` for abilities without charges.
+    #[inline]
+    #[must_use]
+    pub fn charges_available(&self, action: A) -> bool {
+        self.charges.available(action)
+    }
+
+    /// Returns how long ago `action` was last used, including recasts.
+    ///
+    /// Returns [`None`] if the action has never been used, or if this entity has no [`LastUsed`] component.
+    #[inline]
+    #[must_use]
+    pub fn time_since_last_use(&self, action: A) -> Option<Duration> {
+        self.last_used
+            .and_then(|last_used| last_used.time_since_last_use(action))
+    }
+
+    /// Collects every ability that is currently [ready](Self::ready) and affordable, in [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) order.
+    ///
+    /// This uses the same checks as [`trigger`](AbilityStateItem::trigger), including cooldowns, charges and costs,
+    /// so an AI controller can choose freely between the abilities returned.
+    #[must_use]
+    pub fn ready_abilities(&self) -> Vec<A> {
+        A::variants()
+            .filter(|action| self.ready_with_cost(action.clone()).is_ok())
+            .collect()
+    }
+
+    /// Is at least one ability currently [ready](Self::ready) and affordable?
+    ///
+    /// This is equivalent to `!self.ready_abilities().is_empty()`, but stops at the first ready ability and does not allocate.
+    /// It is useful for skipping the turn of an AI that cannot act, or disabling a whole action bar.
+    #[must_use]
+    pub fn any_ready(&self) -> bool {
+        A::variants().any(|action| self.ready_with_cost(action).is_ok())
+    }
+
+    /// Summarizes the state of every ability in a human-readable report, with one line per ability.
+    ///
+    /// Each line lists whether the ability is [ready](Self::ready), the time until its cooldown is ready, its charges,
+    /// and its cost along with whether it is affordable. Fields are omitted for abilities without a cooldown, charges or cost.
+    /// This is intended for logging during balance testing.
+    #[must_use]
+    pub fn debug_report(&self) -> String
+    where
+        A: Debug,
+        P::Quantity: Debug,
+    {
+        debug_report(
+            |action| self.ready(action),
+            self.charges,
+            self.cooldowns,
+            self.pool,
+            self.ability_costs,
+        )
+    }
+
+    /// Packs the readiness of every action into a bitmask, where bit `i` is set if the `i`th action of [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) is [ready](Self::ready) and affordable.
+    ///
+    /// This is useful for performance-sensitive checks of many abilities at once,
+    /// such as intersecting with a mask of the abilities that an AI wants to use.
+    ///
+    /// Only the first 64 actions can be represented: any further actions are never set in the mask.
+    #[must_use]
+    pub fn ready_bitmask(&self) -> u64 {
+        debug_assert!(
+            A::n_variants() <= 64,
+            "ready_bitmask can only represent the first 64 actions"
+        );
+
+        A::variants()
+            .take(64)
+            .enumerate()
+            .filter(|(_, action)| self.ready_with_cost(action.clone()).is_ok())
+            .fold(0, |mask, (i, _)| mask | (1 << i))
+    }
+
+    /// Returns the inputs that are bound to `action` in this entity's [`InputMap`].
+    ///
+    /// This is useful for contextual prompts, such as "Press Q to cast Fireball".
+    /// Returns an empty iterator if this entity has no [`InputMap`].
+    pub fn inputs_for_action(&self, action: A) -> impl Iterator<Item = &UserInput> + '_ {
+        self.input_map
+            .into_iter()
+            .flat_map(move |input_map| input_map.get(action.clone()).iter())
+    }
+
+    /// Returns the action that `input` is bound to in this entity's [`InputMap`], and whether that action is currently [ready](Self::ready).
+    ///
+    /// If `input` is bound to several actions, the first in [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) order is returned.
+    /// Returns [`None`] if `input` is not bound to any action, or if this entity has no [`InputMap`].
+    #[must_use]
+    pub fn action_for_input(
+        &self,
+        input: impl Into<UserInput>,
+    ) -> Option<(A, Result<(), CannotUseAbility>)> {
+        let input = input.into();
+        let (_, action) = self
+            .input_map?
+            .iter()
+            .find(|(inputs, _)| inputs.contains(&input))?;
+        let ready = self.ready(action.clone());
+
+        Some((action, ready))
+    }
+
+    /// Chooses one of the currently ready abilities at random, with a probability proportional to its entry in `weights`.
+    ///
+    /// Abilities that are not ready, whose cost cannot be paid, or that do not have a positive weight, are never chosen.
+    /// Returns [`None`] if no ability can be chosen.
+    ///
+    /// `rng` must return a uniformly distributed random number in the range `[0, 1)`,
+    /// such as `|| rng.f32()` for a seeded `fastrand::Rng`.
+    /// This is useful for simple AI, and is deterministic for a deterministic `rng`.
+    #[must_use]
+    pub fn choose_ready_weighted(
+        &self,
+        weights: &HashMap<A, f32>,
+        rng: impl FnMut() -> f32,
+    ) -> Option<A>
+    where
+        A: Hash + Eq,
+    {
+        choose_weighted(weights, |action| self.ready_with_cost(action).is_ok(), rng)
+    }
+}
+
+/// Builds the report for [`AbilityStateItem::debug_report`], using `ready` to check whether each ability can be used.
+fn debug_report<A: Abilitylike + Debug, P: Pool>(
+    ready: impl Fn(A) -> Result<(), CannotUseAbility>,
+    charges: &ChargeState<A>,
+    cooldowns: &CooldownState<A>,
+    pool: Option<&P>,
+    ability_costs: Option<&AbilityCosts<A, P>>,
+) -> String
+where
+    P::Quantity: Debug,
+{
+    A::variants()
+        .map(|action| {
+            let mut fields = vec![match ready(action.clone()) {
+                Ok(()) => "ready".to_string(),
+                Err(error) => format!("not ready ({error})"),
+            }];
+
+            if let Some(cooldown) = cooldowns.get(action.clone()) {
+                fields.push(format!(
+                    "cooldown {:.2}s",
+                    cooldown.time_until_ready().as_secs_f32()
+                ));
+            }
+
+            if let Some(charges) = charges.get(action.clone()) {
+                fields.push(format!(
+                    "charges {}/{}",
+                    charges.charges(),
+                    charges.max_charges()
+                ));
+            }
+
+            if let Some(&cost) = ability_costs.and_then(|costs| costs.get(action.clone()).as_ref())
+            {
+                let affordable = match pool {
+                    Some(pool) => pool.available(cost).is_ok(),
+                    None => cost <= P::ZERO,
+                };
+                let affordability = if affordable {
+                    "affordable"
+                } else {
+                    "unaffordable"
+                };
+                fields.push(format!("cost {cost:?} ({affordability})"));
+            }
+
+            format!("{action:?}: {}", fields.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Samples one of the `weights` whose action `is_ready`, using the uniform random numbers produced by `rng`.
+///
+/// Actions are considered in [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) order,
+/// rather than the order of the [`HashMap`], so that the result is deterministic.
+fn choose_weighted<A: Abilitylike + Hash + Eq>(
+    weights: &HashMap<A, f32>,
+    is_ready: impl Fn(A) -> bool,
+    mut rng: impl FnMut() -> f32,
+) -> Option<A> {
+    let candidates: Vec<(A, f32)> = A::variants()
+        .filter_map(|action| {
+            let &weight = weights.get(&action)?;
+            (weight.is_finite() && weight > 0.0 && is_ready(action.clone()))
+                .then_some((action, weight))
+        })
+        .collect();
+
+    let total_weight: f32 = candidates.iter().map(|(_, weight)| weight).sum();
+    let mut remaining = rng().clamp(0.0, 1.0) * total_weight;
+
+    for (action, weight) in candidates.iter() {
+        if remaining < *weight {
+            return Some(action.clone());
+        }
+        remaining -= weight;
+    }
+
+    // Floating point error can leave a tiny remainder: fall back to the last candidate
+    candidates.last().map(|(action, _)| action.clone())
+}
+
+/// Would triggering `action` recast it or cancel its channel, rather than casting it anew and paying its cost?
+fn casts_for_free<A: Abilitylike>(
+    action: A,
+    recasts: Option<&RecastState<A>>,
+    channels: Option<&ChannelState<A>>,
+) -> bool {
+    matches!(channels, Some(channels) if channels.is_channeling(action.clone()))
+        || matches!(recasts, Some(recasts) if recasts.in_window(action))
+}
+
+/// Returns [`CannotUseAbility::PoolInsufficient`] if the cost of `action` cannot currently be paid from the `pool`.
+///
+/// Costs that are [deferred](crate::cast_time::CostTiming::OnCompletion) until the end of a cast are not checked.
+fn check_cost<A: Abilitylike, P: Pool>(
+    action: A,
+    pool: Option<&P>,
+    ability_costs: Option<&AbilityCosts<A, P>>,
+    cast_times: Option<&CastTimeState<A>>,
+) -> Result<(), CannotUseAbility> {
+    if matches!(cast_times, Some(cast_times) if cast_times.defers_cost(action.clone())) {
+        return Ok(());
+    }
+
+    let cost = match ability_costs {
+        Some(ability_costs) => *ability_costs.get(action),
+        None => None,
+    };
+
+    match (pool, cost) {
+        (Some(pool), Some(cost)) => pool.available(cost),
+        // The pool does not exist, but the cost does
+        (None, Some(cost)) if cost > P::ZERO => Err(CannotUseAbility::PoolInsufficient {
+            deficit: cost.into(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Overwrites the optional `component` with its `snapshot`, taken before it was modified.
+fn restore<T>(component: Option<&mut T>, snapshot: Option<T>) {
+    if let (Some(component), Some(snapshot)) = (component, snapshot) {
+        *component = snapshot;
+    }
+}
+
+/// Calls `f` on the [`CooldownState`] of every entity with abilities of type `A`.
+///
+/// This is useful for global effects, such as a "recharge all abilities for everyone" game event.
+/// Only [`CooldownState`] components are affected: if you are storing a [`CooldownState`] as a resource,
+/// modify it directly instead.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_abilities::prelude::*;
+/// use leafwing_abilities::for_each_ability_state;
+/// use leafwing_input_manager::Actionlike;
+///
+/// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+/// enum Action {
+///     Fireball,
+/// }
+///
+/// let mut world = World::new();
+/// world.spawn(CooldownState::new([(Action::Fireball, Cooldown::from_secs(1.))]));
+///
+/// for_each_ability_state::<Action>(&mut world, |_entity, cooldowns| cooldowns.reset_all());
+/// ```
+pub fn for_each_ability_state<A: Abilitylike>(
+    world: &mut World,
+    mut f: impl FnMut(Entity, &mut CooldownState<A>),
+) {
+    let mut query_state = world.query::<(Entity, &mut CooldownState<A>)>();
+    for (entity, mut cooldowns) in query_state.iter_mut(world) {
+        f(entity, &mut cooldowns);
+    }
+}
+
+/// A record of exactly what was spent when an ability was triggered by [`AbilityStateItem::trigger_with_refund_token`].
+///
+/// Pass this to [`AbilityStateItem::refund`] to undo the trigger, such as when a cast is interrupted on the same frame.
+#[must_use]
+pub struct RefundToken<A: Abilitylike, P: Pool> {
+    action: A,
+    cost: P::Quantity,
+    charges: Option<Charges>,
+    cooldown: Option<Cooldown>,
+    group_cooldown: Option<Cooldown>,
+    global_cooldown: Option<Cooldown>,
+    recast: Option<Recast>,
+    channels: Option<ChannelState<A>>,
+    cast_times: Option<CastTimeState<A>>,
+}
+
+impl<A: Abilitylike, P: Pool> RefundToken<A, P> {
+    /// The ability that was triggered.
+    #[inline]
+    #[must_use]
+    pub fn action(&self) -> A {
+        self.action.clone()
+    }
+
+    /// The quantity that was spent from the [`Pool`].
+    ///
+    /// This is [`Pool::ZERO`] for recasts, and for abilities without a cost.
+    #[inline]
+    #[must_use]
+    pub fn cost(&self) -> P::Quantity {
+        self.cost
+    }
 }
 
 #[cfg(test)]
@@ -179,6 +1494,7 @@ mod tests {
     use crate as leafwing_abilities;
     use crate::{AbilitiesBundle, AbilityState, Abilitylike};
     use bevy::prelude::*;
+    use bevy::utils::Duration;
     use leafwing_input_manager::{action_state::ActionState, Actionlike};
 
     #[derive(Actionlike, Abilitylike, Clone, Debug)]
@@ -208,6 +1524,1421 @@ mod tests {
         let mut query_state = world.query::<AbilityState<TestAction>>();
         assert_eq!(query_state.iter(&world).len(), 1);
     }
+
+    #[test]
+    fn for_each_ability_state_resets_all_entities() {
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::for_each_ability_state;
+
+        let mut world = World::new();
+        let mut cooldowns = CooldownState::new([(TestAction::Duck, Cooldown::from_secs(1.))]);
+        cooldowns.trigger(TestAction::Duck).unwrap();
+
+        for _ in 0..3 {
+            world.spawn(cooldowns.clone());
+        }
+
+        let mut n_visited = 0;
+        for_each_ability_state::<TestAction>(&mut world, |_entity, cooldowns| {
+            cooldowns.reset_all();
+            n_visited += 1;
+        });
+        assert_eq!(n_visited, 3);
+
+        let mut query_state = world.query::<&CooldownState<TestAction>>();
+        for cooldowns in query_state.iter(&world) {
+            assert!(cooldowns.ready(TestAction::Duck).is_ok());
+        }
+    }
+
+    #[test]
+    fn execute_only_triggers_below_threshold() {
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::pool::Pool;
+        use crate::premade_pools::life::{Life, LifePool};
+        use crate::CannotUseAbility;
+
+        let mut world = World::new();
+        world
+            .spawn(AbilitiesBundle::<TestAction> {
+                cooldowns: CooldownState::new([(TestAction::Duck, Cooldown::from_secs(1.))]),
+                ..Default::default()
+            })
+            .insert(ActionState::<TestAction>::default());
+        let mut target_pool = LifePool::new_full(Life(100.), Life(0.));
+
+        let mut query_state = world.query::<AbilityState<TestAction>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+
+        assert_eq!(
+            ability_state.trigger_if_target_below(TestAction::Duck, &target_pool, 0.2),
+            Err(CannotUseAbility::TargetAboveThreshold)
+        );
+        assert!(ability_state.cooldowns.ready(TestAction::Duck).is_ok());
+
+        target_pool.set_current(Life(15.));
+        assert!(ability_state
+            .trigger_if_target_below(TestAction::Duck, &target_pool, 0.2)
+            .is_ok());
+        assert_eq!(
+            ability_state.cooldowns.ready(TestAction::Duck),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(1)
+            })
+        );
+    }
+
+    #[test]
+    fn concurrency_limit_applies_overflow_policy() {
+        use crate::cooldown::{ConcurrencyLimit, ConcurrencyOverflow, Cooldown, CooldownState};
+        use crate::CannotUseAbility;
+        use bevy::utils::Duration;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy, Debug)]
+        enum Aura {
+            Fire,
+            Frost,
+            Storm,
+        }
+
+        let aura = Cooldown::from_secs(1.).with_active_duration(Duration::from_secs(10));
+        let cooldowns =
+            CooldownState::new([(Aura::Fire, aura), (Aura::Frost, aura), (Aura::Storm, aura)]);
+
+        let mut world = World::new();
+        let rejecting = world
+            .spawn((
+                AbilitiesBundle::<Aura> {
+                    cooldowns: cooldowns.clone(),
+                    ..Default::default()
+                },
+                ActionState::<Aura>::default(),
+                ConcurrencyLimit::<Aura>::new(2, ConcurrencyOverflow::Reject),
+            ))
+            .id();
+        let replacing = world
+            .spawn((
+                AbilitiesBundle::<Aura> {
+                    cooldowns,
+                    ..Default::default()
+                },
+                ActionState::<Aura>::default(),
+                ConcurrencyLimit::<Aura>::new(2, ConcurrencyOverflow::DeactivateOldest),
+            ))
+            .id();
+
+        let mut query_state = world.query::<AbilityState<Aura>>();
+        for entity in [rejecting, replacing] {
+            let mut ability_state = query_state.get_mut(&mut world, entity).unwrap();
+            ability_state.trigger(Aura::Fire).unwrap();
+            ability_state.cooldowns.tick(Duration::from_secs(1), None);
+            ability_state.trigger(Aura::Frost).unwrap();
+        }
+
+        let mut ability_state = query_state.get_mut(&mut world, rejecting).unwrap();
+        assert_eq!(
+            ability_state.ready(Aura::Storm),
+            Err(CannotUseAbility::TooManyActive)
+        );
+        assert_eq!(
+            ability_state.trigger(Aura::Storm),
+            Err(CannotUseAbility::TooManyActive)
+        );
+        assert!(ability_state.cooldowns.get(Aura::Fire).unwrap().is_active());
+        assert!(ability_state
+            .cooldowns
+            .get(Aura::Frost)
+            .unwrap()
+            .is_active());
+        assert!(ability_state.cooldowns.ready(Aura::Storm).is_ok());
+
+        let mut ability_state = query_state.get_mut(&mut world, replacing).unwrap();
+        assert!(ability_state.ready(Aura::Storm).is_ok());
+        ability_state.trigger(Aura::Storm).unwrap();
+        assert!(!ability_state.cooldowns.get(Aura::Fire).unwrap().is_active());
+        assert!(ability_state
+            .cooldowns
+            .get(Aura::Frost)
+            .unwrap()
+            .is_active());
+        assert!(ability_state
+            .cooldowns
+            .get(Aura::Storm)
+            .unwrap()
+            .is_active());
+    }
+
+    #[test]
+    fn choose_ready_weighted_matches_weights() {
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::pool::{AbilityCosts, Pool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+        use bevy::utils::HashMap;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        enum Attack {
+            Jab,
+            Kick,
+            Slam,
+            Taunt,
+            Meteor,
+        }
+
+        let mut cooldowns = CooldownState::new([
+            (Attack::Slam, Cooldown::from_secs(5.)),
+            (Attack::Meteor, Cooldown::from_secs(5.)),
+        ]);
+        cooldowns.trigger(Attack::Slam).unwrap();
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<Attack> {
+                cooldowns,
+                ..Default::default()
+            },
+            ActionState::<Attack>::default(),
+            ManaPool::new_full(Mana(10.), Mana(0.)),
+            AbilityCosts::<Attack, ManaPool>::new([(Attack::Meteor, Mana(50.))]),
+        ));
+
+        // Slam is on cooldown, Meteor is off cooldown but unaffordable, and Taunt has no weight
+        let weights = HashMap::from_iter([
+            (Attack::Jab, 1.0),
+            (Attack::Kick, 3.0),
+            (Attack::Slam, 6.0),
+            (Attack::Meteor, 6.0),
+        ]);
+
+        let mut query_state = world.query::<AbilityState<Attack, ManaPool>>();
+        let ability_state = query_state.single(&world);
+        let rng = fastrand::Rng::with_seed(42);
+
+        let n_samples = 10_000;
+        let mut counts: HashMap<Attack, usize> = HashMap::default();
+        for _ in 0..n_samples {
+            let choice = ability_state
+                .choose_ready_weighted(&weights, || rng.f32())
+                .unwrap();
+            *counts.entry(choice).or_default() += 1;
+        }
+
+        assert_eq!(counts.get(&Attack::Slam), None);
+        assert_eq!(counts.get(&Attack::Taunt), None);
+        assert_eq!(counts.get(&Attack::Meteor), None);
+        let jab_fraction = counts[&Attack::Jab] as f32 / n_samples as f32;
+        assert!((jab_fraction - 0.25).abs() < 0.02, "{jab_fraction}");
+
+        // No ready ability has any weight
+        let weights = HashMap::from_iter([(Attack::Slam, 1.0)]);
+        assert_eq!(
+            ability_state.choose_ready_weighted(&weights, || rng.f32()),
+            None
+        );
+    }
+
+    #[test]
+    fn ready_bitmask_matches_ready() {
+        use crate::cooldown::{Cooldown, CooldownState};
+
+        let mut cooldowns = CooldownState::new([(TestAction::Cover, Cooldown::from_secs(1.))]);
+        cooldowns.trigger(TestAction::Cover).unwrap();
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<TestAction> {
+                cooldowns,
+                ..Default::default()
+            },
+            ActionState::<TestAction>::default(),
+        ));
+
+        let mut query_state = world.query::<AbilityState<TestAction>>();
+        let ability_state = query_state.single(&world);
+        let mask = ability_state.ready_bitmask();
+        assert_eq!(mask, 0b01);
+
+        for (i, action) in TestAction::variants().enumerate() {
+            assert_eq!(mask & (1 << i) != 0, ability_state.ready(action).is_ok());
+        }
+    }
+
+    #[test]
+    fn input_lookups_reflect_bindings_and_readiness() {
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::CannotUseAbility;
+        use leafwing_input_manager::input_map::InputMap;
+        use leafwing_input_manager::user_input::UserInput;
+
+        let mut cooldowns = CooldownState::new([(TestAction::Cover, Cooldown::from_secs(1.))]);
+        cooldowns.trigger(TestAction::Cover).unwrap();
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<TestAction> {
+                cooldowns,
+                ..Default::default()
+            },
+            ActionState::<TestAction>::default(),
+            InputMap::new([
+                (KeyCode::Q, TestAction::Duck),
+                (KeyCode::E, TestAction::Cover),
+                (KeyCode::R, TestAction::Cover),
+            ]),
+        ));
+
+        let mut query_state = world.query::<AbilityState<TestAction>>();
+        let ability_state = query_state.single(&world);
+
+        let cover_inputs: Vec<&UserInput> =
+            ability_state.inputs_for_action(TestAction::Cover).collect();
+        assert_eq!(
+            cover_inputs,
+            vec![&UserInput::from(KeyCode::E), &UserInput::from(KeyCode::R)]
+        );
+
+        let (action, ready) = ability_state.action_for_input(KeyCode::Q).unwrap();
+        assert!(matches!(action, TestAction::Duck));
+        assert!(ready.is_ok());
+
+        let (action, ready) = ability_state.action_for_input(KeyCode::R).unwrap();
+        assert!(matches!(action, TestAction::Cover));
+        assert_eq!(
+            ready,
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(1)
+            })
+        );
+
+        assert!(ability_state.action_for_input(KeyCode::Space).is_none());
+    }
+
+    #[test]
+    fn pay_cost_does_not_trigger() {
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::pool::{AbilityCosts, Pool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+        use crate::CannotUseAbility;
+
+        let mut world = World::new();
+        world
+            .spawn(AbilitiesBundle::<TestAction> {
+                cooldowns: CooldownState::new([(TestAction::Duck, Cooldown::from_secs(1.))]),
+                ..Default::default()
+            })
+            .insert(ActionState::<TestAction>::default())
+            .insert(ManaPool::new_full(Mana(15.), Mana(0.)))
+            .insert(AbilityCosts::<TestAction, ManaPool>::new([(
+                TestAction::Duck,
+                Mana(10.),
+            )]));
+
+        let mut query_state = world.query::<AbilityState<TestAction, ManaPool>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+
+        assert!(ability_state.pay_cost(TestAction::Duck).is_ok());
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(5.));
+        assert!(ability_state.cooldowns.ready(TestAction::Duck).is_ok());
+
+        assert_eq!(
+            ability_state.pay_cost(TestAction::Duck),
+            Err(CannotUseAbility::PoolInsufficient { deficit: 5. })
+        );
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(5.));
+    }
+
+    #[test]
+    fn trigger_all_just_pressed_shares_pool() {
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::pool::{AbilityCosts, Pool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+        use crate::CannotUseAbility;
+
+        let mut action_state = ActionState::<TestAction>::default();
+        action_state.press(TestAction::Duck);
+        action_state.press(TestAction::Cover);
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<TestAction> {
+                cooldowns: CooldownState::new([
+                    (TestAction::Duck, Cooldown::from_secs(1.)),
+                    (TestAction::Cover, Cooldown::from_secs(1.)),
+                ]),
+                ..Default::default()
+            },
+            action_state,
+            ManaPool::new_full(Mana(10.), Mana(0.)),
+            AbilityCosts::<TestAction, ManaPool>::new([
+                (TestAction::Duck, Mana(6.)),
+                (TestAction::Cover, Mana(6.)),
+            ]),
+        ));
+
+        let mut query_state = world.query::<AbilityState<TestAction, ManaPool>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+
+        let results = ability_state.trigger_all_just_pressed();
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[0], (TestAction::Duck, Ok(()))));
+        assert!(matches!(
+            results[1],
+            (
+                TestAction::Cover,
+                Err(CannotUseAbility::PoolInsufficient { .. })
+            )
+        ));
+
+        // Only the first ability was paid for and triggered
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(4.));
+        assert!(ability_state.cooldowns.ready(TestAction::Duck).is_err());
+        assert!(ability_state.cooldowns.ready(TestAction::Cover).is_ok());
+    }
+
+    #[test]
+    fn recast_skips_cooldown() {
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::recast::{CastKind, Recast, RecastExpiry, RecastState};
+        use crate::CannotUseAbility;
+        use bevy::utils::Duration;
+
+        let mut world = World::new();
+        world
+            .spawn(AbilitiesBundle::<TestAction> {
+                cooldowns: CooldownState::new([(TestAction::Duck, Cooldown::from_secs(1.))]),
+                ..Default::default()
+            })
+            .insert(ActionState::<TestAction>::default())
+            .insert(RecastState::new([(
+                TestAction::Duck,
+                Recast::new(Duration::from_secs(1), RecastExpiry::Cancel),
+            )]));
+
+        let mut query_state = world.query::<AbilityState<TestAction>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+
+        assert_eq!(
+            ability_state.trigger_or_recast(TestAction::Duck),
+            Ok(CastKind::Initial)
+        );
+        assert!(ability_state.ready(TestAction::Duck).is_ok());
+        assert_eq!(
+            ability_state.trigger_or_recast(TestAction::Duck),
+            Ok(CastKind::Recast)
+        );
+        assert_eq!(
+            ability_state.trigger_or_recast(TestAction::Duck),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(1)
+            })
+        );
+    }
+
+    #[test]
+    fn repressing_cancels_channel() {
+        use crate::channel::{Channel, ChannelInterruptPolicy, ChannelState};
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::recast::CastKind;
+        use crate::CannotUseAbility;
+        use bevy::utils::Duration;
+
+        let mut world = World::new();
+        world
+            .spawn(AbilitiesBundle::<TestAction> {
+                cooldowns: CooldownState::new([(TestAction::Duck, Cooldown::from_secs(1.))]),
+                ..Default::default()
+            })
+            .insert(ActionState::<TestAction>::default())
+            .insert(
+                ChannelState::new([(TestAction::Duck, Channel::new(Duration::from_secs(3)))])
+                    .with_interrupt_policy(ChannelInterruptPolicy::CancelOnSamePress),
+            );
+
+        let mut query_state = world.query::<AbilityState<TestAction>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+
+        assert_eq!(
+            ability_state.trigger_or_recast(TestAction::Duck),
+            Ok(CastKind::Initial)
+        );
+        assert!(ability_state
+            .channels
+            .as_ref()
+            .unwrap()
+            .is_channeling(TestAction::Duck));
+        assert_eq!(
+            ability_state.trigger(TestAction::Cover),
+            Err(CannotUseAbility::Channeling)
+        );
+
+        // Re-pressing ends the channel without starting a new one, or restarting the cooldown
+        ability_state
+            .cooldowns
+            .tick(Duration::from_millis(400), None);
+        assert_eq!(
+            ability_state.trigger_or_recast(TestAction::Duck),
+            Ok(CastKind::ChannelCancelled)
+        );
+        assert!(ability_state
+            .channels
+            .as_ref()
+            .unwrap()
+            .channeling()
+            .is_none());
+        assert_eq!(
+            ability_state
+                .cooldowns
+                .get(TestAction::Duck)
+                .as_ref()
+                .unwrap()
+                .remaining(),
+            Duration::from_millis(600)
+        );
+        assert!(ability_state.trigger(TestAction::Cover).is_ok());
+    }
+
+    #[test]
+    fn pool_cost_to_full_reads_ability_costs() {
+        use crate::charges::{ChargeState, Charges};
+        use crate::pool::{AbilityCosts, Pool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+
+        let mut charges = ChargeState::new([(TestAction::Duck, Charges::simple(4))]);
+        charges
+            .get_mut(TestAction::Duck)
+            .as_mut()
+            .unwrap()
+            .set_charges(1);
+
+        let mut world = World::new();
+        world
+            .spawn(AbilitiesBundle::<TestAction> {
+                charges,
+                ..Default::default()
+            })
+            .insert(ActionState::<TestAction>::default())
+            .insert(ManaPool::new_full(Mana(100.), Mana(0.)))
+            .insert(AbilityCosts::<TestAction, ManaPool>::new([(
+                TestAction::Duck,
+                Mana(25.),
+            )]));
+
+        let mut query_state = world.query::<AbilityState<TestAction, ManaPool>>();
+        let ability_state = query_state.single(&world);
+
+        assert_eq!(ability_state.pool_cost_to_full(TestAction::Duck), Mana(75.));
+        // Abilities without a cost never need any resources
+        assert_eq!(ability_state.pool_cost_to_full(TestAction::Cover), Mana(0.));
+    }
+
+    #[test]
+    fn recent_damage_blocks_trigger() {
+        use crate::pool::{Pool, UndamagedRequirements};
+        use crate::premade_pools::life::{Life, LifePool};
+        use crate::CannotUseAbility;
+        use bevy::utils::Duration;
+
+        let mut world = World::new();
+        let mut life_pool = LifePool::new_full(Life(10.), Life(0.));
+        life_pool.expend(Life(1.)).unwrap();
+
+        world
+            .spawn(AbilitiesBundle::<TestAction>::default())
+            .insert(ActionState::<TestAction>::default())
+            .insert(life_pool)
+            .insert(UndamagedRequirements::new([(
+                TestAction::Cover,
+                Duration::from_secs(1),
+            )]));
+
+        let mut query_state = world.query::<AbilityState<TestAction, LifePool>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+        assert_eq!(
+            ability_state.trigger(TestAction::Cover),
+            Err(CannotUseAbility::RecentlyDamaged)
+        );
+        assert!(ability_state.trigger(TestAction::Duck).is_ok());
+
+        ability_state
+            .pool
+            .as_mut()
+            .unwrap()
+            .regenerate(Duration::from_secs(1));
+        assert!(ability_state.trigger(TestAction::Cover).is_ok());
+    }
+
+    #[test]
+    fn multiple_costs_are_paid_atomically() {
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::pool::{AbilityCosts, Pool, PoolCost};
+        use crate::premade_pools::life::{Life, LifePool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+        use crate::CannotUseAbility;
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<TestAction> {
+                cooldowns: CooldownState::new([(TestAction::Duck, Cooldown::from_secs(1.))]),
+                ..Default::default()
+            },
+            ActionState::<TestAction>::default(),
+            ManaPool::new_full(Mana(10.), Mana(0.)),
+            AbilityCosts::<TestAction, ManaPool>::new([(TestAction::Duck, Mana(4.))]),
+            LifePool::new_full(Life(5.), Life(0.)),
+        ));
+        let life_costs = AbilityCosts::<TestAction, LifePool>::new([(TestAction::Duck, Life(8.))]);
+
+        let mut query_state = world.query::<(AbilityState<TestAction, ManaPool>, &mut LifePool)>();
+        let (mut ability_state, mut life_pool) = query_state.single_mut(&mut world);
+
+        // The life cost cannot be paid, so nothing happens
+        let mut life_cost = PoolCost::new(&mut *life_pool, &life_costs);
+        assert_eq!(
+            ability_state.trigger_and_pay_costs(TestAction::Duck, &mut life_cost),
+            Err(CannotUseAbility::PoolInsufficient { deficit: 3. })
+        );
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(10.));
+        assert_eq!(life_cost.pool.current(), Life(5.));
+        assert!(ability_state.ready(TestAction::Duck).is_ok());
+
+        life_cost.pool.set_max(Life(10.)).unwrap();
+        life_cost.pool.set_current(Life(10.));
+        assert!(ability_state
+            .trigger_and_pay_costs(TestAction::Duck, &mut life_cost)
+            .is_ok());
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(6.));
+        assert_eq!(life_cost.pool.current(), Life(2.));
+        assert!(ability_state.ready(TestAction::Duck).is_err());
+    }
+
+    #[test]
+    fn cancelling_a_channel_pays_no_extra_costs() {
+        use crate::channel::{Channel, ChannelInterruptPolicy, ChannelState};
+        use crate::pool::{AbilityCosts, Pool, PoolCost};
+        use crate::premade_pools::life::{Life, LifePool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+        use bevy::utils::Duration;
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<TestAction>::default(),
+            ActionState::<TestAction>::default(),
+            ManaPool::new_full(Mana(10.), Mana(0.)),
+            AbilityCosts::<TestAction, ManaPool>::new([(TestAction::Duck, Mana(4.))]),
+            LifePool::new_full(Life(10.), Life(0.)),
+            ChannelState::new([(TestAction::Duck, Channel::new(Duration::from_secs(3)))])
+                .with_interrupt_policy(ChannelInterruptPolicy::CancelOnSamePress),
+        ));
+        let life_costs = AbilityCosts::<TestAction, LifePool>::new([(TestAction::Duck, Life(4.))]);
+
+        let mut query_state = world.query::<(AbilityState<TestAction, ManaPool>, &mut LifePool)>();
+        let (mut ability_state, mut life_pool) = query_state.single_mut(&mut world);
+        let mut life_cost = PoolCost::new(&mut *life_pool, &life_costs);
+
+        assert!(ability_state
+            .trigger_and_pay_costs(TestAction::Duck, &mut life_cost)
+            .is_ok());
+        assert_eq!(life_cost.pool.current(), Life(6.));
+
+        // Pressing again cancels the channel for free, even though the life cost can no longer be afforded
+        life_cost.pool.set_current(Life(2.));
+        assert!(ability_state
+            .trigger_and_pay_costs(TestAction::Duck, &mut life_cost)
+            .is_ok());
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(6.));
+        assert_eq!(life_cost.pool.current(), Life(2.));
+        assert!(ability_state
+            .channels
+            .as_ref()
+            .unwrap()
+            .channeling()
+            .is_none());
+    }
+
+    #[test]
+    fn extra_costs_cannot_be_deferred() {
+        use crate::cast_time::{CastTime, CastTimeState, CostTiming};
+        use crate::pool::{AbilityCosts, Pool, PoolCost};
+        use crate::premade_pools::life::{Life, LifePool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+        use crate::CannotUseAbility;
+        use bevy::utils::Duration;
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<TestAction>::default(),
+            ActionState::<TestAction>::default(),
+            ManaPool::new_full(Mana(10.), Mana(0.)),
+            AbilityCosts::<TestAction, ManaPool>::new([(TestAction::Duck, Mana(4.))]),
+            LifePool::new_full(Life(10.), Life(0.)),
+            CastTimeState::new([(
+                TestAction::Duck,
+                CastTime::new(Duration::from_secs(1)).with_cost_timing(CostTiming::OnCompletion),
+            )]),
+        ));
+        let life_costs = AbilityCosts::<TestAction, LifePool>::new([(TestAction::Duck, Life(4.))]);
+
+        let mut query_state = world.query::<(AbilityState<TestAction, ManaPool>, &mut LifePool)>();
+        let (mut ability_state, mut life_pool) = query_state.single_mut(&mut world);
+        let mut life_cost = PoolCost::new(&mut *life_pool, &life_costs);
+
+        assert_eq!(
+            ability_state.trigger_and_pay_costs(TestAction::Duck, &mut life_cost),
+            Err(CannotUseAbility::CostDeferred)
+        );
+        assert!(!ability_state
+            .cast_times
+            .as_ref()
+            .unwrap()
+            .is_casting(TestAction::Duck));
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(10.));
+        assert_eq!(life_cost.pool.current(), Life(10.));
+    }
+
+    #[test]
+    fn ready_abilities_are_usable() {
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::pool::{AbilityCosts, Pool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+
+        // Cover has both a cooldown and a cost
+        let mut cooldowns = CooldownState::new([
+            (TestAction::Duck, Cooldown::from_secs(1.)),
+            (TestAction::Cover, Cooldown::from_secs(1.)),
+        ]);
+        cooldowns.trigger(TestAction::Duck).unwrap();
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<TestAction> {
+                cooldowns,
+                ..Default::default()
+            },
+            ActionState::<TestAction>::default(),
+            ManaPool::new_full(Mana(10.), Mana(0.)),
+            AbilityCosts::<TestAction, ManaPool>::new([(TestAction::Cover, Mana(4.))]),
+        ));
+
+        let mut query_state = world.query::<AbilityState<TestAction, ManaPool>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+        let ready = ability_state.ready_abilities();
+        assert!(matches!(ready[..], [TestAction::Cover]));
+        assert!(ability_state.any_ready());
+        assert_eq!(ability_state.ready_bitmask(), 0b10);
+
+        // Abilities that cannot be afforded are excluded, even though their cooldown is ready
+        ability_state.pool.as_mut().unwrap().set_current(Mana(2.));
+        assert!(ability_state.ready_abilities().is_empty());
+        assert!(!ability_state.any_ready());
+        assert_eq!(ability_state.ready_bitmask(), 0);
+
+        let ability_state = query_state.single(&world);
+        assert!(ability_state.ready_abilities().is_empty());
+        assert!(!ability_state.any_ready());
+        assert_eq!(ability_state.ready_bitmask(), 0);
+    }
+
+    #[test]
+    fn can_trigger_checks_costs_of_abilities_with_cooldowns() {
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::pool::{AbilityCosts, Pool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+        use crate::CannotUseAbility;
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<TestAction> {
+                cooldowns: CooldownState::new([(TestAction::Duck, Cooldown::from_secs(1.))]),
+                ..Default::default()
+            },
+            ActionState::<TestAction>::default(),
+            ManaPool::new_full(Mana(3.), Mana(0.)),
+            AbilityCosts::<TestAction, ManaPool>::new([(TestAction::Duck, Mana(4.))]),
+        ));
+
+        let mut query_state = world.query::<AbilityState<TestAction, ManaPool>>();
+        let ability_state = query_state.single_mut(&mut world);
+
+        // The cooldown is ready, but the cost cannot be paid
+        assert!(ability_state.ready(TestAction::Duck).is_ok());
+        assert_eq!(
+            ability_state.can_trigger(TestAction::Duck),
+            Err(CannotUseAbility::PoolInsufficient { deficit: 1. })
+        );
+
+        let ability_state = query_state.single(&world);
+        assert_eq!(
+            ability_state.can_trigger(TestAction::Duck),
+            Err(CannotUseAbility::PoolInsufficient { deficit: 1. })
+        );
+    }
+
+    #[test]
+    fn cooldowns_and_charges_can_be_checked_separately() {
+        use crate::charges::{ChargeState, Charges, CooldownStrategy, ReplenishStrategy};
+        use crate::cooldown::{Cooldown, CooldownState};
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<TestAction> {
+                cooldowns: CooldownState::new([(TestAction::Duck, Cooldown::from_secs(1.))]),
+                charges: ChargeState::new([(
+                    TestAction::Duck,
+                    Charges::new(
+                        2,
+                        ReplenishStrategy::OneAtATime,
+                        CooldownStrategy::RateLimit,
+                    ),
+                )]),
+            },
+            ActionState::<TestAction>::default(),
+        ));
+
+        let mut query_state = world.query::<AbilityState<TestAction>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+        ability_state.trigger(TestAction::Duck).unwrap();
+
+        // A charge remains, but the cooldown is still running
+        assert!(!ability_state.cooldown_ready(TestAction::Duck));
+        assert!(ability_state.charges_available(TestAction::Duck));
+        assert!(ability_state.ready(TestAction::Duck).is_err());
+
+        // Abilities without cooldowns or charges pass both checks
+        assert!(ability_state.cooldown_ready(TestAction::Cover));
+        assert!(ability_state.charges_available(TestAction::Cover));
+    }
+
+    #[test]
+    fn debug_report_summarizes_each_ability() {
+        use crate::charges::{ChargeState, Charges};
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::pool::{AbilityCosts, Pool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+
+        let mut cooldowns = CooldownState::new([(TestAction::Duck, Cooldown::from_secs(1.))]);
+        cooldowns.trigger(TestAction::Duck).unwrap();
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<TestAction> {
+                cooldowns,
+                charges: ChargeState::new([(TestAction::Duck, Charges::simple(2))]),
+            },
+            ActionState::<TestAction>::default(),
+            ManaPool::new_full(Mana(10.), Mana(0.)),
+            AbilityCosts::<TestAction, ManaPool>::new([(TestAction::Cover, Mana(4.))]),
+        ));
+
+        let mut query_state = world.query::<AbilityState<TestAction, ManaPool>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+        ability_state.pool.as_mut().unwrap().set_current(Mana(2.));
+
+        assert_eq!(
+            ability_state.debug_report(),
+            "Duck: ready, cooldown 1.00s, charges 2/2\n\
+             Cover: not ready (Not enough resources: 2 more needed.), cost Mana(4.0) (unaffordable)"
+        );
+    }
+
+    #[test]
+    fn refunds_restore_what_was_spent() {
+        use crate::charges::{ChargeState, Charges};
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::pool::{AbilityCosts, Pool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+
+        let mut cooldowns = CooldownState::new([(TestAction::Duck, Cooldown::from_secs(1.))]);
+        cooldowns.set_global_cooldown(Cooldown::from_secs(0.5));
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<TestAction> {
+                cooldowns,
+                charges: ChargeState::new([(TestAction::Duck, Charges::replenish_one(2))]),
+            },
+            ActionState::<TestAction>::default(),
+            ManaPool::new_full(Mana(10.), Mana(0.)),
+            AbilityCosts::<TestAction, ManaPool>::new([(TestAction::Duck, Mana(4.))]),
+        ));
+
+        let mut query_state = world.query::<AbilityState<TestAction, ManaPool>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+        let duck_state = |ability_state: &crate::AbilityStateItem<TestAction, ManaPool>| {
+            (
+                ability_state.cooldowns.get(TestAction::Duck).unwrap(),
+                ability_state.cooldowns.global_cooldown.unwrap(),
+                ability_state.charges.get(TestAction::Duck).clone().unwrap(),
+            )
+        };
+        let before = duck_state(&ability_state);
+
+        let token = ability_state
+            .trigger_with_refund_token(TestAction::Duck)
+            .unwrap();
+        assert_eq!(token.cost(), Mana(4.));
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(6.));
+        assert_ne!(duck_state(&ability_state), before);
+
+        ability_state.refund(token);
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(10.));
+        assert_eq!(duck_state(&ability_state), before);
+    }
+
+    #[test]
+    fn refunded_deferred_casts_never_pay_their_cost() {
+        use crate::cast_time::{CastFinished, CastTime, CastTimeState, CostTiming};
+        use crate::plugin::AbilityPlugin;
+        use crate::pool::{AbilityCosts, Pool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+
+        let mut world = World::new();
+        let entity = world
+            .spawn((
+                AbilitiesBundle::<TestAction>::default(),
+                ActionState::<TestAction>::default(),
+                ManaPool::new_full(Mana(10.), Mana(0.)),
+                AbilityCosts::<TestAction, ManaPool>::new([(TestAction::Duck, Mana(4.))]),
+                CastTimeState::new([(
+                    TestAction::Duck,
+                    CastTime::new(Duration::from_secs(1))
+                        .with_cost_timing(CostTiming::OnCompletion),
+                )]),
+            ))
+            .id();
+
+        let mut query_state = world.query::<AbilityState<TestAction, ManaPool>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+        let token = ability_state
+            .trigger_with_refund_token(TestAction::Duck)
+            .unwrap();
+        assert_eq!(token.cost(), Mana(0.));
+        assert!(ability_state
+            .cast_times
+            .as_ref()
+            .unwrap()
+            .is_casting(TestAction::Duck));
+
+        ability_state.refund(token);
+        AbilityPlugin::<TestAction>::tick_world::<ManaPool>(&mut world, Duration::from_secs(2));
+
+        assert_eq!(world.get::<ManaPool>(entity).unwrap().current(), Mana(10.));
+        assert!(world
+            .resource::<Events<CastFinished<TestAction>>>()
+            .is_empty());
+    }
+
+    #[test]
+    fn trigger_all_is_all_or_nothing() {
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::pool::{AbilityCosts, Pool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+        use crate::CannotUseAbility;
+
+        let mut cooldowns = CooldownState::new([
+            (TestAction::Duck, Cooldown::from_secs(1.)),
+            (TestAction::Cover, Cooldown::from_secs(1.)),
+        ]);
+        cooldowns.set_global_cooldown(Cooldown::from_secs(0.5));
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<TestAction> {
+                cooldowns,
+                ..Default::default()
+            },
+            ActionState::<TestAction>::default(),
+            ManaPool::new_full(Mana(10.), Mana(0.)),
+            AbilityCosts::<TestAction, ManaPool>::new([
+                (TestAction::Duck, Mana(4.)),
+                (TestAction::Cover, Mana(4.)),
+            ]),
+        ));
+
+        let mut query_state = world.query::<AbilityState<TestAction, ManaPool>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+
+        // Using the same ability twice would need it to be ready twice
+        assert_eq!(
+            ability_state.trigger_all(&[TestAction::Duck, TestAction::Cover, TestAction::Duck]),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(1)
+            })
+        );
+        assert!(ability_state.ready(TestAction::Duck).is_ok());
+        assert!(ability_state.ready(TestAction::Cover).is_ok());
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(10.));
+
+        // The global cooldown does not block the rest of the combo
+        assert!(ability_state
+            .trigger_all(&[TestAction::Duck, TestAction::Cover])
+            .is_ok());
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(2.));
+        assert!(ability_state.cooldowns.gcd_ready().is_err());
+
+        // Costs are checked in total
+        ability_state.cooldowns.reset_all();
+        assert_eq!(
+            ability_state.trigger_all(&[TestAction::Duck, TestAction::Cover]),
+            Err(CannotUseAbility::PoolInsufficient { deficit: 6. })
+        );
+        assert!(ability_state.ready(TestAction::Duck).is_ok());
+    }
+
+    #[test]
+    fn trigger_all_rolls_back_failures_part_way_through() {
+        use crate::channel::{Channel, ChannelState};
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::pool::{AbilityCosts, Pool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+        use crate::triggers::TriggeredAbilities;
+        use crate::CannotUseAbility;
+        use bevy::utils::Duration;
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<TestAction> {
+                cooldowns: CooldownState::new([(TestAction::Duck, Cooldown::from_secs(1.))]),
+                ..Default::default()
+            },
+            ActionState::<TestAction>::default(),
+            ManaPool::new_full(Mana(10.), Mana(0.)),
+            AbilityCosts::<TestAction, ManaPool>::new([(TestAction::Duck, Mana(4.))]),
+            ChannelState::new([(TestAction::Duck, Channel::new(Duration::from_secs(3)))]),
+            TriggeredAbilities::<TestAction>::default(),
+        ));
+
+        let mut query_state = world.query::<AbilityState<TestAction, ManaPool>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+
+        // Both abilities are ready on their own, but channeling the first blocks the second
+        assert!(ability_state.ready(TestAction::Cover).is_ok());
+        assert_eq!(
+            ability_state.trigger_all(&[TestAction::Duck, TestAction::Cover]),
+            Err(CannotUseAbility::Channeling)
+        );
+
+        assert!(ability_state.cooldowns.ready(TestAction::Duck).is_ok());
+        assert!(!ability_state
+            .channels
+            .as_ref()
+            .unwrap()
+            .is_channeling(TestAction::Duck));
+        assert!(ability_state.triggered.as_ref().unwrap().is_empty());
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(10.));
+    }
+
+    #[test]
+    fn next_usable_in_combines_charges_and_cooldowns() {
+        use crate::charges::{ChargeState, Charges};
+        use crate::cooldown::{Cooldown, CooldownState};
+        use bevy::utils::Duration;
+
+        let mut cooldowns = CooldownState::new([
+            (TestAction::Duck, Cooldown::from_secs(4.)),
+            (TestAction::Cover, Cooldown::from_secs(3.)),
+        ]);
+        cooldowns.global_cooldown = Some(Cooldown::from_secs(1.));
+
+        let mut world = World::new();
+        world
+            .spawn(AbilitiesBundle::<TestAction> {
+                cooldowns,
+                charges: ChargeState::new([(TestAction::Duck, Charges::simple(2))]),
+            })
+            .insert(ActionState::<TestAction>::default());
+
+        let mut query_state = world.query::<AbilityState<TestAction>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+
+        // Ready
+        assert_eq!(
+            ability_state.next_usable_in(TestAction::Duck),
+            Some(Duration::ZERO)
+        );
+
+        // Charge available: only gated by the global cooldown
+        ability_state.charges.expend(TestAction::Duck).unwrap();
+        ability_state.cooldowns.trigger(TestAction::Duck).unwrap();
+        assert_eq!(
+            ability_state.next_usable_in(TestAction::Duck),
+            Some(Duration::from_secs(1))
+        );
+
+        // No charges: waits for the next charge
+        ability_state.charges.expend(TestAction::Duck).unwrap();
+        assert_eq!(
+            ability_state.next_usable_in(TestAction::Duck),
+            Some(Duration::from_secs(4))
+        );
+
+        // Without charges, the action's own cooldown applies
+        assert_eq!(
+            ability_state.next_usable_in(TestAction::Cover),
+            Some(Duration::from_secs(1))
+        );
+        ability_state.cooldowns.tick(Duration::from_secs(1), None);
+        ability_state.cooldowns.trigger(TestAction::Cover).unwrap();
+        assert_eq!(
+            ability_state.next_usable_in(TestAction::Cover),
+            Some(Duration::from_secs(3))
+        );
+
+        // The active phase of the global cooldown delays every action
+        let global_cooldown = ability_state.cooldowns.global_cooldown.as_mut().unwrap();
+        *global_cooldown = Cooldown::from_secs(1.).with_active_duration(Duration::from_secs(5));
+        global_cooldown.trigger().unwrap();
+        assert_eq!(
+            ability_state.next_usable_in(TestAction::Cover),
+            Some(Duration::from_secs(6))
+        );
+    }
+
+    #[test]
+    fn spending_charges_reduces_cooldown() {
+        use crate::charges::{ChargeState, Charges};
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::CannotUseAbility;
+        use bevy::utils::Duration;
+
+        let mut world = World::new();
+        world
+            .spawn(AbilitiesBundle::<TestAction> {
+                cooldowns: CooldownState::new([(TestAction::Cover, Cooldown::from_secs(10.))]),
+                charges: ChargeState::new([(TestAction::Duck, Charges::simple(3))]),
+            })
+            .insert(ActionState::<TestAction>::default());
+
+        let mut query_state = world.query::<AbilityState<TestAction>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+        ability_state.cooldowns.trigger(TestAction::Cover).unwrap();
+
+        ability_state
+            .spend_charges_to_reduce_cooldown(
+                TestAction::Duck,
+                2,
+                TestAction::Cover,
+                Duration::from_secs(3),
+            )
+            .unwrap();
+        let cooldown = ability_state.cooldowns.get(TestAction::Cover).unwrap();
+        assert_eq!(cooldown.remaining(), Duration::from_secs(4));
+        let charges = ability_state
+            .charges
+            .get(TestAction::Duck)
+            .as_ref()
+            .unwrap();
+        assert_eq!(charges.charges(), 1);
+
+        // Not enough charges: nothing happens
+        assert_eq!(
+            ability_state.spend_charges_to_reduce_cooldown(
+                TestAction::Duck,
+                2,
+                TestAction::Cover,
+                Duration::from_secs(3),
+            ),
+            Err(CannotUseAbility::NoCharges)
+        );
+        let cooldown = ability_state.cooldowns.get(TestAction::Cover).unwrap();
+        assert_eq!(cooldown.remaining(), Duration::from_secs(4));
+        let charges = ability_state
+            .charges
+            .get(TestAction::Duck)
+            .as_ref()
+            .unwrap();
+        assert_eq!(charges.charges(), 1);
+    }
+
+    #[test]
+    fn charges_spent_to_reduce_cooldown_recharge() {
+        use crate::charges::{ChargeState, Charges};
+        use crate::cooldown::{Cooldown, CooldownState};
+        use bevy::utils::Duration;
+
+        let mut world = World::new();
+        world
+            .spawn(AbilitiesBundle::<TestAction> {
+                cooldowns: CooldownState::new([
+                    (TestAction::Duck, Cooldown::from_secs(1.)),
+                    (TestAction::Cover, Cooldown::from_secs(10.)),
+                ]),
+                charges: ChargeState::new([(TestAction::Duck, Charges::replenish_one(3))]),
+            })
+            .insert(ActionState::<TestAction>::default());
+
+        let mut query_state = world.query::<AbilityState<TestAction>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+        ability_state.cooldowns.trigger(TestAction::Cover).unwrap();
+
+        ability_state
+            .spend_charges_to_reduce_cooldown(
+                TestAction::Duck,
+                2,
+                TestAction::Cover,
+                Duration::from_secs(3),
+            )
+            .unwrap();
+        assert!(ability_state.cooldowns.ready(TestAction::Duck).is_err());
+
+        // The spent charges come back, one per cooldown of Duck
+        for expected in [1, 2, 3] {
+            let charges = &mut *ability_state.charges;
+            assert_eq!(
+                charges.get(TestAction::Duck).as_ref().unwrap().charges(),
+                expected
+            );
+            ability_state
+                .cooldowns
+                .tick(Duration::from_secs(1), Some(charges));
+        }
+    }
+
+    #[test]
+    fn triggers_can_ignore_costs_or_cooldowns() {
+        use crate::charges::ChargeState;
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::pool::{AbilityCosts, Pool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+        use crate::CannotUseAbility;
+
+        let mut cooldowns = CooldownState::new([(TestAction::Duck, Cooldown::from_secs(1.))]);
+        cooldowns.set_global_cooldown(Cooldown::from_secs(0.5));
+
+        let mut world = World::new();
+        world.spawn((
+            AbilitiesBundle::<TestAction> {
+                cooldowns,
+                ..Default::default()
+            },
+            ActionState::<TestAction>::default(),
+            ManaPool::new_full(Mana(6.), Mana(0.)),
+            AbilityCosts::<TestAction, ManaPool>::new([(TestAction::Duck, Mana(4.))]),
+        ));
+
+        let mut query_state = world.query::<AbilityState<TestAction, ManaPool>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+
+        // Free casts still trigger the cooldown
+        ability_state
+            .trigger_ignoring_cost(TestAction::Duck)
+            .unwrap();
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(6.));
+        assert!(ability_state
+            .trigger_ignoring_cost(TestAction::Duck)
+            .is_err());
+
+        // Instant resets still pay the cost
+        ability_state
+            .trigger_ignoring_cooldown(TestAction::Duck)
+            .unwrap();
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(2.));
+        assert!(ability_state.cooldowns.ready(TestAction::Duck).is_err());
+
+        // Failures leave the cooldowns untouched
+        let remaining = |ability_state: &crate::AbilityStateItem<TestAction, ManaPool>| {
+            ability_state.cooldowns.remaining(TestAction::Duck)
+        };
+        let remaining_before = remaining(&ability_state);
+        assert_eq!(
+            ability_state.trigger_ignoring_cooldown(TestAction::Duck),
+            Err(CannotUseAbility::PoolInsufficient { deficit: 2. })
+        );
+        assert_eq!(remaining(&ability_state), remaining_before);
+        assert!(ability_state.cooldowns.gcd_ready().is_err());
+
+        // Nor do they trip change detection
+        world.clear_trackers();
+        let mut ability_state = query_state.single_mut(&mut world);
+        assert!(ability_state
+            .trigger_ignoring_cooldown(TestAction::Duck)
+            .is_err());
+        assert!(ability_state
+            .trigger_ignoring_cost(TestAction::Duck)
+            .is_err());
+        let mut changed = world.query_filtered::<Entity, Or<(
+            Changed<CooldownState<TestAction>>,
+            Changed<ChargeState<TestAction>>,
+            Changed<ManaPool>,
+        )>>();
+        assert_eq!(changed.iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn cast_checks_requirements_in_order() {
+        use crate::charges::{ChargeState, Charges, CooldownStrategy, ReplenishStrategy};
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::disabled::DisabledAbilities;
+        use crate::pool::{AbilityCosts, Pool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+        use crate::recast::CastKind;
+        use crate::CannotUseAbility;
+
+        let mut cooldowns = CooldownState::new([(TestAction::Duck, Cooldown::from_secs(1.))]);
+        cooldowns.set_global_cooldown(Cooldown::from_secs(1.));
+        let group = cooldowns.set_group([TestAction::Duck], Cooldown::from_secs(1.));
+        let mut charges = Charges::new(
+            1,
+            ReplenishStrategy::OneAtATime,
+            CooldownStrategy::RateLimit,
+        );
+        charges.set_charges(0);
+
+        let mut world = World::new();
+        let entity = world
+            .spawn((
+                AbilitiesBundle::<TestAction> {
+                    cooldowns,
+                    charges: ChargeState::new([(TestAction::Duck, charges)]),
+                },
+                ActionState::<TestAction>::default(),
+                ManaPool::new_empty(Mana(10.), Mana(0.)),
+                AbilityCosts::<TestAction, ManaPool>::new([(TestAction::Duck, Mana(4.))]),
+                DisabledAbilities::new([TestAction::Duck]),
+            ))
+            .id();
+
+        let mut query_state = world.query::<AbilityState<TestAction, ManaPool>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+        let cooldowns = &mut ability_state.cooldowns;
+        cooldowns
+            .global_cooldown
+            .as_mut()
+            .unwrap()
+            .trigger()
+            .unwrap();
+        cooldowns
+            .get_mut(TestAction::Duck)
+            .as_mut()
+            .unwrap()
+            .trigger()
+            .unwrap();
+        cooldowns.group_cooldown_mut(group).trigger().unwrap();
+        assert_eq!(
+            ability_state.cast(TestAction::Duck),
+            Err(CannotUseAbility::Disabled)
+        );
+
+        // Each requirement is lifted in turn, revealing the next one
+        world
+            .get_mut::<DisabledAbilities<TestAction>>(entity)
+            .unwrap()
+            .enable_all();
+        let mut ability_state = query_state.single_mut(&mut world);
+        assert_eq!(
+            ability_state.cast(TestAction::Duck),
+            Err(CannotUseAbility::OnGlobalCooldown {
+                remaining: Duration::from_secs(1)
+            })
+        );
+
+        ability_state
+            .cooldowns
+            .global_cooldown
+            .as_mut()
+            .unwrap()
+            .refresh();
+        assert_eq!(
+            ability_state.cast(TestAction::Duck),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(1)
+            })
+        );
+
+        ability_state.cooldowns.reset(TestAction::Duck);
+        assert_eq!(
+            ability_state.cast(TestAction::Duck),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(1)
+            })
+        );
+
+        ability_state.cooldowns.group_cooldown_mut(group).refresh();
+        assert_eq!(
+            ability_state.cast(TestAction::Duck),
+            Err(CannotUseAbility::NoCharges)
+        );
+
+        ability_state.charges.replenish(TestAction::Duck);
+        assert_eq!(
+            ability_state.cast(TestAction::Duck),
+            Err(CannotUseAbility::PoolInsufficient { deficit: 4. })
+        );
+
+        // Failed casts have no effect
+        assert_eq!(
+            ability_state
+                .charges
+                .get(TestAction::Duck)
+                .as_ref()
+                .unwrap()
+                .charges(),
+            1
+        );
+        assert!(ability_state.cooldowns.ready(TestAction::Duck).is_ok());
+
+        // Once every requirement is met, everything is spent together
+        ability_state.pool.as_mut().unwrap().set_current(Mana(10.));
+        assert_eq!(ability_state.cast(TestAction::Duck), Ok(CastKind::Initial));
+        assert_eq!(ability_state.pool.as_ref().unwrap().current(), Mana(6.));
+        assert_eq!(
+            ability_state
+                .charges
+                .get(TestAction::Duck)
+                .as_ref()
+                .unwrap()
+                .charges(),
+            0
+        );
+        assert!(ability_state
+            .cooldowns
+            .get(TestAction::Duck)
+            .unwrap()
+            .ready()
+            .is_err());
+        assert!(ability_state.cooldowns.gcd_ready().is_err());
+        assert!(ability_state
+            .cooldowns
+            .group_ready(TestAction::Duck)
+            .is_err());
+    }
+
+    #[test]
+    fn disabled_abilities_cannot_be_used() {
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::disabled::DisabledAbilities;
+        use crate::CannotUseAbility;
+
+        let mut world = World::new();
+        let entity = world
+            .spawn((
+                AbilitiesBundle::<TestAction> {
+                    cooldowns: CooldownState::new([(TestAction::Duck, Cooldown::from_secs(1.))]),
+                    ..Default::default()
+                },
+                ActionState::<TestAction>::default(),
+                DisabledAbilities::new([TestAction::Duck]),
+            ))
+            .id();
+
+        let mut query_state = world.query::<AbilityState<TestAction>>();
+        let mut ability_state = query_state.single_mut(&mut world);
+        assert_eq!(
+            ability_state.ready(TestAction::Duck),
+            Err(CannotUseAbility::Disabled)
+        );
+        assert_eq!(
+            ability_state.trigger_and_pay_cost(TestAction::Duck),
+            Err(CannotUseAbility::Disabled)
+        );
+        assert!(ability_state.trigger(TestAction::Cover).is_ok());
+        assert!(ability_state.cooldowns.ready(TestAction::Duck).is_ok());
+
+        world
+            .get_mut::<DisabledAbilities<TestAction>>(entity)
+            .unwrap()
+            .enable(TestAction::Duck);
+        let mut ability_state = query_state.single_mut(&mut world);
+        assert!(ability_state.trigger(TestAction::Duck).is_ok());
+    }
 }
 
 /// A no-op type that implements [`Pool`] and [`Component`].
@@ -215,7 +2946,7 @@ mod tests {
 /// Used in [`AbilityState`] to get the type system to play nice when no resource pool type is needed.
 ///
 /// Values of this type should never be constructed.
-#[derive(Component)]
+#[derive(Component, Resource)]
 pub struct NullPool;
 
 impl Pool for NullPool {
@@ -243,7 +2974,7 @@ impl Pool for NullPool {
         Self::ZERO
     }
 
-    fn set_max(&mut self, _new_max: Self::Quantity) -> Result<(), MaxPoolLessThanZero> {
+    fn set_max(&mut self, _new_max: Self::Quantity) -> Result<(), PoolError> {
         Ok(())
     }
 
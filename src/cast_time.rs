@@ -0,0 +1,339 @@
+//! Cast times delay the effect of an ability until some time after it is triggered.
+//!
+//! Spells with a cast bar, or heavy attacks with a wind-up, are common examples.
+//! Each [`CastTime`] chooses when the cost of its ability is paid with its [`CostTiming`].
+//! Deferring payment to the end of the cast lets the [`Pool`] regenerate while the ability is being cast,
+//! so abilities that are not yet affordable can be started early.
+//!
+//! Casts are advanced and finished by the [`tick_cast_times`](crate::systems::tick_cast_times) system,
+//! which must be added to your schedule for each pair of ability and [`Pool`] types.
+
+use bevy::ecs::prelude::{Component, Entity, Resource};
+use bevy::utils::Duration;
+use std::marker::PhantomData;
+
+use crate::pool::{AbilityCosts, Pool};
+use crate::{Abilitylike, CannotUseAbility};
+
+/// A component / resource that stores the [`CastTime`] for each [`Abilitylike`] action of type `A`.
+///
+/// Actions without a [`CastTime`] take effect as soon as they are triggered.
+///
+/// ```rust
+/// use bevy::utils::Duration;
+/// use leafwing_abilities::prelude::*;
+/// use leafwing_abilities::cast_time::{CastTime, CastTimeState, CostTiming};
+/// use leafwing_input_manager::Actionlike;
+///
+/// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+/// enum Action {
+///     Pyroblast,
+/// }
+///
+/// let mut cast_times = CastTimeState::new([(
+///     Action::Pyroblast,
+///     CastTime::new(Duration::from_secs(3)).with_cost_timing(CostTiming::OnCompletion),
+/// )]);
+///
+/// // The cost is checked and paid once the cast finishes
+/// assert!(cast_times.defers_cost(Action::Pyroblast));
+///
+/// cast_times.start(Action::Pyroblast);
+/// assert!(cast_times.is_casting(Action::Pyroblast));
+/// ```
+#[derive(Resource, Component, Clone, PartialEq, Eq, Debug)]
+pub struct CastTimeState<A: Abilitylike> {
+    /// The underlying [`CastTime`], stored in [`Actionlike::variants`] order.
+    cast_time_vec: Vec<Option<CastTime>>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Abilitylike> Default for CastTimeState<A> {
+    fn default() -> Self {
+        CastTimeState {
+            cast_time_vec: A::variants().map(|_| None).collect(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Abilitylike> CastTimeState<A> {
+    /// Creates a new [`CastTimeState`] from an iterator of `(action, cast_time)` pairs
+    ///
+    /// If a [`CastTime`] is not provided for an action, that action takes effect immediately.
+    ///
+    /// To create an empty [`CastTimeState`] struct, use the [`Default::default`] method instead.
+    #[must_use]
+    pub fn new(action_cast_time_pairs: impl IntoIterator<Item = (A, CastTime)>) -> Self {
+        let mut cast_time_state = CastTimeState::default();
+        for (action, cast_time) in action_cast_time_pairs.into_iter() {
+            cast_time_state.set(action, cast_time);
+        }
+        cast_time_state
+    }
+
+    /// Is `action` currently being cast?
+    ///
+    /// Returns `false` if the underlying [`CastTime`] is [`None`].
+    #[inline]
+    #[must_use]
+    pub fn is_casting(&self, action: A) -> bool {
+        if let Some(cast_time) = self.get(action) {
+            cast_time.is_casting()
+        } else {
+            false
+        }
+    }
+
+    /// Is the cost of `action` paid when its cast finishes, rather than when it is triggered?
+    #[inline]
+    #[must_use]
+    pub fn defers_cost(&self, action: A) -> bool {
+        if let Some(cast_time) = self.get(action) {
+            cast_time.cost_timing == CostTiming::OnCompletion
+        } else {
+            false
+        }
+    }
+
+    /// Starts casting `action`, if it has a cast time.
+    ///
+    /// This should be called when the ability is triggered.
+    #[inline]
+    pub fn start(&mut self, action: A) {
+        if let Some(cast_time) = self.get_mut(action) {
+            cast_time.start();
+        }
+    }
+
+    /// Cancels the cast of `action`.
+    ///
+    /// Returns `true` if `action` was being cast.
+    /// Any deferred cost is never paid.
+    #[inline]
+    pub fn cancel(&mut self, action: A) -> bool {
+        if let Some(cast_time) = self.get_mut(action) {
+            cast_time.cancel()
+        } else {
+            false
+        }
+    }
+
+    /// Advances each underlying [`CastTime`] according to the elapsed `delta_time`.
+    ///
+    /// Calls `on_finish` once for each action whose cast finished as a result.
+    pub fn tick(&mut self, delta_time: Duration, mut on_finish: impl FnMut(A)) {
+        for action in A::variants() {
+            if let Some(cast_time) = self.get_mut(action.clone()) {
+                if cast_time.tick(delta_time) {
+                    on_finish(action);
+                }
+            }
+        }
+    }
+
+    /// Advances each cast like [`tick`](Self::tick), and pays the deferred costs of every cast that finishes.
+    ///
+    /// Casts whose cost is deferred with [`CostTiming::OnCompletion`] are checked against the `maybe_pool` when they finish.
+    /// If the cost cannot be paid, the cast fails with [`CannotUseAbility::PoolInsufficient`] and nothing is spent.
+    /// Calls `on_finish` once for each finished cast, with the result of paying its cost.
+    pub fn tick_and_pay<P: Pool>(
+        &mut self,
+        delta_time: Duration,
+        mut maybe_pool: Option<&mut P>,
+        maybe_costs: Option<&AbilityCosts<A, P>>,
+        mut on_finish: impl FnMut(A, Result<(), CannotUseAbility>),
+    ) {
+        let mut finished = Vec::new();
+        self.tick(delta_time, |action| finished.push(action));
+
+        for action in finished {
+            let result = match self.defers_cost(action.clone()) {
+                true => pay_deferred_cost(action.clone(), maybe_pool.as_deref_mut(), maybe_costs),
+                false => Ok(()),
+            };
+            on_finish(action, result);
+        }
+    }
+
+    /// Returns a reference to the underlying [`CastTime`] for `action`, if set.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, action: A) -> &Option<CastTime> {
+        &self.cast_time_vec[action.index()]
+    }
+
+    /// Returns a mutable reference to the underlying [`CastTime`] for `action`, if set.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, action: A) -> &mut Option<CastTime> {
+        &mut self.cast_time_vec[action.index()]
+    }
+
+    /// Sets the underlying [`CastTime`] for `action` to the provided value.
+    ///
+    /// Unless you're building a new [`CastTimeState`] struct, you likely want to use [`Self::get_mut`].
+    #[inline]
+    pub fn set(&mut self, action: A, cast_time: CastTime) -> &mut Self {
+        *self.get_mut(action) = Some(cast_time);
+        self
+    }
+
+    /// Returns an iterator of references to the underlying non-[`None`] [`CastTime`]s
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &CastTime> {
+        self.cast_time_vec.iter().flatten()
+    }
+
+    /// Returns an iterator of mutable references to the underlying non-[`None`] [`CastTime`]s
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut CastTime> {
+        self.cast_time_vec.iter_mut().flatten()
+    }
+}
+
+/// Pays the cost of `action` from `maybe_pool`, failing if the pool is missing but the cost is not zero.
+fn pay_deferred_cost<A: Abilitylike, P: Pool>(
+    action: A,
+    maybe_pool: Option<&mut P>,
+    maybe_costs: Option<&AbilityCosts<A, P>>,
+) -> Result<(), CannotUseAbility> {
+    let cost = match maybe_costs.and_then(|costs| *costs.get(action)) {
+        Some(cost) => cost,
+        None => return Ok(()),
+    };
+
+    match maybe_pool {
+        Some(pool) => pool.expend(cost),
+        None if cost > P::ZERO => Err(CannotUseAbility::PoolInsufficient {
+            deficit: cost.into(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// How long it takes to cast an ability, and when its cost is paid.
+///
+/// ```rust
+/// use bevy::utils::Duration;
+/// use leafwing_abilities::cast_time::CastTime;
+///
+/// let mut cast_time = CastTime::new(Duration::from_secs(2));
+/// assert!(!cast_time.is_casting());
+///
+/// cast_time.start();
+/// assert_eq!(cast_time.remaining(), Some(Duration::from_secs(2)));
+///
+/// assert!(!cast_time.tick(Duration::from_secs(1)));
+/// assert!(cast_time.tick(Duration::from_secs(1)));
+/// assert!(!cast_time.is_casting());
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CastTime {
+    duration: Duration,
+    /// The time remaining in the current cast, if one is in progress.
+    remaining: Option<Duration>,
+    /// When the cost of the ability is checked and paid.
+    pub cost_timing: CostTiming,
+}
+
+impl CastTime {
+    /// Creates a new [`CastTime`], which finishes `duration` after the ability is triggered.
+    ///
+    /// The cost is paid when the cast starts: use [`Self::with_cost_timing`] to defer it.
+    #[inline]
+    #[must_use]
+    pub fn new(duration: Duration) -> CastTime {
+        CastTime {
+            duration,
+            remaining: None,
+            cost_timing: CostTiming::default(),
+        }
+    }
+
+    /// Sets when the cost of the ability is checked and paid.
+    #[inline]
+    #[must_use]
+    pub fn with_cost_timing(mut self, cost_timing: CostTiming) -> CastTime {
+        self.cost_timing = cost_timing;
+        self
+    }
+
+    /// The full length of the cast.
+    #[inline]
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The time remaining until the cast finishes, or [`None`] if it is not being cast.
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> Option<Duration> {
+        self.remaining
+    }
+
+    /// Is the ability currently being cast?
+    #[inline]
+    #[must_use]
+    pub fn is_casting(&self) -> bool {
+        self.remaining.is_some()
+    }
+
+    /// Starts the cast, resetting the time remaining to the full duration.
+    #[inline]
+    pub fn start(&mut self) {
+        self.remaining = Some(self.duration);
+    }
+
+    /// Cancels the cast.
+    ///
+    /// Returns `true` if the ability was being cast.
+    #[inline]
+    pub fn cancel(&mut self) -> bool {
+        self.remaining.take().is_some()
+    }
+
+    /// Advances the cast by `delta_time`.
+    ///
+    /// Returns `true` if this caused the cast to finish.
+    pub fn tick(&mut self, delta_time: Duration) -> bool {
+        let remaining = match self.remaining {
+            Some(remaining) => remaining.saturating_sub(delta_time),
+            None => return false,
+        };
+
+        if remaining == Duration::ZERO {
+            self.remaining = None;
+            true
+        } else {
+            self.remaining = Some(remaining);
+            false
+        }
+    }
+}
+
+/// When is the cost of an ability with a [`CastTime`] checked and paid?
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CostTiming {
+    /// The cost must be affordable when the ability is triggered, and is paid immediately.
+    #[default]
+    OnStart,
+    /// The cost is ignored when the ability is triggered, and is checked and paid when the cast finishes.
+    ///
+    /// If the cost cannot be paid by then, the cast fails and nothing is spent.
+    OnCompletion,
+}
+
+/// An event sent when the [`CastTime`] of an ability of type `A` finishes.
+///
+/// Sent by the [`tick_cast_times`](crate::systems::tick_cast_times) system.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastFinished<A: Abilitylike> {
+    /// The entity whose cast finished, or [`None`] if the [`CastTimeState`] is stored as a resource.
+    pub entity: Option<Entity>,
+    /// The ability whose cast finished.
+    pub action: A,
+    /// Whether the ability takes effect, or [`CannotUseAbility::PoolInsufficient`] if its deferred cost could not be paid.
+    pub result: Result<(), CannotUseAbility>,
+}
@@ -0,0 +1,379 @@
+//! Channeled abilities keep taking effect for a period of time after they are triggered.
+//!
+//! Beams that deal damage for as long as they are held, or heals that tick while the caster stands still
+//! are common examples.
+//! While an ability is being channeled, the [`ChannelInterruptPolicy`] of its [`ChannelState`]
+//! decides what happens when that ability, or any other, is triggered again.
+//!
+//! The [`Cooldown`](crate::cooldown::Cooldown) of a channeled ability starts when the channel begins.
+//! Cancelling the channel early does not reset or refund it.
+
+use bevy::ecs::prelude::{Component, Entity, Resource};
+use bevy::utils::Duration;
+use std::marker::PhantomData;
+
+use crate::{Abilitylike, CannotUseAbility};
+
+/// A component / resource that stores the [`Channel`] for each [`Abilitylike`] action of type `A`.
+///
+/// Actions without a [`Channel`] are never channeled.
+///
+/// ```rust
+/// use bevy::utils::Duration;
+/// use leafwing_abilities::prelude::*;
+/// use leafwing_abilities::channel::ChannelInterruptPolicy;
+/// use leafwing_input_manager::Actionlike;
+///
+/// #[derive(Actionlike, Abilitylike, Clone, Copy, PartialEq, Debug)]
+/// enum Action {
+///     Beam,
+///     Dash,
+/// }
+///
+/// let mut channels = ChannelState::new([(Action::Beam, Channel::new(Duration::from_secs(3)))])
+///     .with_interrupt_policy(ChannelInterruptPolicy::CancelOnSamePress);
+///
+/// channels.start(Action::Beam);
+/// assert_eq!(channels.channeling(), Some(Action::Beam));
+///
+/// // Other abilities cannot be used while channeling
+/// assert!(channels.check(Action::Dash).is_err());
+/// // But pressing the channeled ability again cancels the channel
+/// assert!(channels.check(Action::Beam).is_ok());
+/// assert!(channels.cancel(Action::Beam));
+/// assert_eq!(channels.channeling(), None);
+/// ```
+#[derive(Resource, Component, Clone, PartialEq, Eq, Debug)]
+pub struct ChannelState<A: Abilitylike> {
+    /// The underlying [`Channel`], stored in [`Actionlike::variants`] order.
+    channel_vec: Vec<Option<Channel>>,
+    /// What happens when an ability is triggered while a channel is active.
+    pub interrupt_policy: ChannelInterruptPolicy,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Abilitylike> Default for ChannelState<A> {
+    fn default() -> Self {
+        ChannelState {
+            channel_vec: A::variants().map(|_| None).collect(),
+            interrupt_policy: ChannelInterruptPolicy::default(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Abilitylike> ChannelState<A> {
+    /// Creates a new [`ChannelState`] from an iterator of `(action, channel)` pairs
+    ///
+    /// If a [`Channel`] is not provided for an action, that action is never channeled.
+    /// The [`ChannelInterruptPolicy::Ignore`] policy is used: set another with [`Self::with_interrupt_policy`].
+    ///
+    /// To create an empty [`ChannelState`] struct, use the [`Default::default`] method instead.
+    #[must_use]
+    pub fn new(action_channel_pairs: impl IntoIterator<Item = (A, Channel)>) -> Self {
+        let mut channel_state = ChannelState::default();
+        for (action, channel) in action_channel_pairs.into_iter() {
+            channel_state.set(action, channel);
+        }
+        channel_state
+    }
+
+    /// Sets the [`ChannelInterruptPolicy`] used while a channel is active.
+    #[inline]
+    #[must_use]
+    pub fn with_interrupt_policy(mut self, interrupt_policy: ChannelInterruptPolicy) -> Self {
+        self.interrupt_policy = interrupt_policy;
+        self
+    }
+
+    /// Is `action` currently being channeled?
+    ///
+    /// Returns `false` if the underlying [`Channel`] is [`None`].
+    #[inline]
+    #[must_use]
+    pub fn is_channeling(&self, action: A) -> bool {
+        if let Some(channel) = self.get(action) {
+            channel.is_active()
+        } else {
+            false
+        }
+    }
+
+    /// The first action that is currently being channeled, if any.
+    #[must_use]
+    pub fn channeling(&self) -> Option<A> {
+        A::variants().find(|action| self.is_channeling(action.clone()))
+    }
+
+    /// Can `action` be triggered right now, according to the [`ChannelInterruptPolicy`]?
+    ///
+    /// Returns [`CannotUseAbility::Channeling`] if a channel is active and the policy ignores this press.
+    /// If this returns `Ok` while `action` is itself being channeled,
+    /// triggering it cancels the channel rather than casting it again.
+    #[inline]
+    pub fn check(&self, action: A) -> Result<(), CannotUseAbility> {
+        if self.channeling().is_none() {
+            return Ok(());
+        }
+
+        match self.interrupt_policy {
+            ChannelInterruptPolicy::Ignore => Err(CannotUseAbility::Channeling),
+            ChannelInterruptPolicy::CancelOnSamePress => match self.is_channeling(action) {
+                true => Ok(()),
+                false => Err(CannotUseAbility::Channeling),
+            },
+            ChannelInterruptPolicy::CancelOnAnyAbility => Ok(()),
+        }
+    }
+
+    /// Starts channeling `action`, if it can be channeled.
+    ///
+    /// This should be called when the ability is cast.
+    /// If `action` was already being channeled, its channel restarts from the beginning.
+    #[inline]
+    pub fn start(&mut self, action: A) {
+        if let Some(channel) = self.get_mut(action) {
+            channel.start();
+        }
+    }
+
+    /// Cancels the channel of `action`.
+    ///
+    /// Returns `true` if `action` was being channeled.
+    #[inline]
+    pub fn cancel(&mut self, action: A) -> bool {
+        if let Some(channel) = self.get_mut(action) {
+            channel.cancel()
+        } else {
+            false
+        }
+    }
+
+    /// Cancels every active channel.
+    #[inline]
+    pub fn cancel_all(&mut self) {
+        for channel in self.iter_mut() {
+            channel.cancel();
+        }
+    }
+
+    /// Advances each underlying [`Channel`] according to the elapsed `delta_time`.
+    ///
+    /// Calls `on_complete` once for each action whose channel finished as a result.
+    pub fn tick(&mut self, delta_time: Duration, mut on_complete: impl FnMut(A)) {
+        for action in A::variants() {
+            if let Some(channel) = self.get_mut(action.clone()) {
+                if channel.tick(delta_time) {
+                    on_complete(action);
+                }
+            }
+        }
+    }
+
+    /// Returns a reference to the underlying [`Channel`] for `action`, if set.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, action: A) -> &Option<Channel> {
+        &self.channel_vec[action.index()]
+    }
+
+    /// Returns a mutable reference to the underlying [`Channel`] for `action`, if set.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, action: A) -> &mut Option<Channel> {
+        &mut self.channel_vec[action.index()]
+    }
+
+    /// Sets the underlying [`Channel`] for `action` to the provided value.
+    ///
+    /// Unless you're building a new [`ChannelState`] struct, you likely want to use [`Self::get_mut`].
+    #[inline]
+    pub fn set(&mut self, action: A, channel: Channel) -> &mut Self {
+        *self.get_mut(action) = Some(channel);
+        self
+    }
+
+    /// Returns an iterator of references to the underlying non-[`None`] [`Channel`]s
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Channel> {
+        self.channel_vec.iter().flatten()
+    }
+
+    /// Returns an iterator of mutable references to the underlying non-[`None`] [`Channel`]s
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Channel> {
+        self.channel_vec.iter_mut().flatten()
+    }
+}
+
+/// How long an ability keeps taking effect after it is cast.
+///
+/// ```rust
+/// use bevy::utils::Duration;
+/// use leafwing_abilities::channel::Channel;
+///
+/// let mut channel = Channel::new(Duration::from_secs(2));
+/// assert!(!channel.is_active());
+///
+/// channel.start();
+/// assert_eq!(channel.remaining(), Some(Duration::from_secs(2)));
+///
+/// assert!(!channel.tick(Duration::from_secs(1)));
+/// assert!(channel.tick(Duration::from_secs(1)));
+/// assert!(!channel.is_active());
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Channel {
+    duration: Duration,
+    /// The time remaining in the current channel, if it is active.
+    remaining: Option<Duration>,
+}
+
+impl Channel {
+    /// Creates a new [`Channel`], which lasts for `duration` after the ability is cast.
+    ///
+    /// The channel starts inactive.
+    #[inline]
+    #[must_use]
+    pub fn new(duration: Duration) -> Channel {
+        Channel {
+            duration,
+            remaining: None,
+        }
+    }
+
+    /// The full length of the channel.
+    #[inline]
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The time remaining until the channel completes, or [`None`] if it is not active.
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> Option<Duration> {
+        self.remaining
+    }
+
+    /// Is the ability currently being channeled?
+    #[inline]
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.remaining.is_some()
+    }
+
+    /// Starts the channel, resetting the time remaining to the full duration.
+    #[inline]
+    pub fn start(&mut self) {
+        self.remaining = Some(self.duration);
+    }
+
+    /// Ends the channel early.
+    ///
+    /// Returns `true` if the channel was active.
+    #[inline]
+    pub fn cancel(&mut self) -> bool {
+        self.remaining.take().is_some()
+    }
+
+    /// Advances the channel by `delta_time`.
+    ///
+    /// Returns `true` if this caused the channel to complete.
+    pub fn tick(&mut self, delta_time: Duration) -> bool {
+        let Some(remaining) = self.remaining else {
+            return false;
+        };
+        let remaining = remaining.saturating_sub(delta_time);
+
+        if remaining == Duration::ZERO {
+            self.remaining = None;
+            true
+        } else {
+            self.remaining = Some(remaining);
+            false
+        }
+    }
+}
+
+/// What happens when an ability is triggered while a [`Channel`] is active?
+///
+/// Stored in [`ChannelState::interrupt_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ChannelInterruptPolicy {
+    /// Every ability, including the channeled one, fails with [`CannotUseAbility::Channeling`] until the channel completes.
+    #[default]
+    Ignore,
+    /// Triggering the channeled ability again cancels its channel, without casting it again.
+    ///
+    /// Other abilities fail with [`CannotUseAbility::Channeling`] until the channel ends.
+    CancelOnSamePress,
+    /// Triggering the channeled ability again cancels its channel, without casting it again.
+    ///
+    /// Triggering any other ability cancels the channel once that ability has been successfully cast.
+    CancelOnAnyAbility,
+}
+
+/// An event sent when the [`Channel`] of an ability of type `A` completes without being cancelled.
+///
+/// Sent by the [`tick_channels`](crate::systems::tick_channels) system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelCompleted<A: Abilitylike> {
+    /// The entity whose channel completed, or [`None`] if the [`ChannelState`] is stored as a resource.
+    pub entity: Option<Entity>,
+    /// The ability whose channel completed.
+    pub action: A,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_abilities;
+    use leafwing_input_manager::Actionlike;
+
+    #[derive(Actionlike, Abilitylike, Clone, Copy, Debug, PartialEq)]
+    enum Action {
+        Beam,
+        Dash,
+    }
+
+    fn channels(interrupt_policy: ChannelInterruptPolicy) -> ChannelState<Action> {
+        ChannelState::new([(Action::Beam, Channel::new(Duration::from_secs(2)))])
+            .with_interrupt_policy(interrupt_policy)
+    }
+
+    #[test]
+    fn policy_decides_which_presses_are_allowed() {
+        for (interrupt_policy, beam_allowed, dash_allowed) in [
+            (ChannelInterruptPolicy::Ignore, false, false),
+            (ChannelInterruptPolicy::CancelOnSamePress, true, false),
+            (ChannelInterruptPolicy::CancelOnAnyAbility, true, true),
+        ] {
+            let mut channels = channels(interrupt_policy);
+            assert!(channels.check(Action::Beam).is_ok());
+            assert!(channels.check(Action::Dash).is_ok());
+
+            channels.start(Action::Beam);
+            assert_eq!(channels.check(Action::Beam).is_ok(), beam_allowed);
+            assert_eq!(channels.check(Action::Dash).is_ok(), dash_allowed);
+        }
+    }
+
+    #[test]
+    fn channel_completes() {
+        let mut channels = channels(ChannelInterruptPolicy::Ignore);
+        channels.start(Action::Beam);
+        // Actions without a channel are unaffected
+        channels.start(Action::Dash);
+        assert_eq!(channels.channeling(), Some(Action::Beam));
+
+        let mut completed = Vec::new();
+        channels.tick(Duration::from_secs(1), |action| completed.push(action));
+        assert!(completed.is_empty());
+        channels.tick(Duration::from_secs(1), |action| completed.push(action));
+
+        assert_eq!(completed, vec![Action::Beam]);
+        assert_eq!(channels.channeling(), None);
+        assert!(!channels.cancel(Action::Beam));
+    }
+}
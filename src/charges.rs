@@ -2,10 +2,18 @@
 //! Actions may only be used if at least one charge is available.
 //! Unlike pools, charges are not shared across abilities.
 
-use bevy::ecs::prelude::{Component, Resource};
+use bevy::ecs::prelude::{Component, ReflectComponent, ReflectResource, Resource};
+use bevy::reflect::{FromReflect, Reflect};
+use bevy::utils::Duration;
+use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
+use std::ops::Mul;
 
-use crate::{Abilitylike, CannotUseAbility};
+use crate::{
+    cooldown::{Cooldown, CooldownState},
+    pool::RegenGate,
+    Abilitylike, CannotUseAbility,
+};
 
 /// A component / resource that stores the [`Charges`] for each [`Abilitylike`] action of type `A`.
 ///
@@ -78,10 +86,14 @@ use crate::{Abilitylike, CannotUseAbility};
 ///     Action::Spell.trigger(&mut abilities_bundle.charges, &mut abilities_bundle.cooldowns, Some(&mut mana_bundle.pool), Some(&mut mana_bundle.ability_costs));
 /// }
 /// ```
-#[derive(Resource, Component, Clone, PartialEq, Eq, Debug)]
+#[derive(Resource, Component, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, Reflect)]
+#[serde(bound = "")]
+#[reflect(Component, Resource)]
 pub struct ChargeState<A: Abilitylike> {
     /// The underlying [`Charges`], stored in [`Actionlike::variants`] order.
     charges_vec: Vec<Option<Charges>>,
+    #[serde(skip)]
+    #[reflect(ignore)]
     _phantom: PhantomData<A>,
 }
 
@@ -96,9 +108,19 @@ impl<A: Abilitylike> Default for ChargeState<A> {
 
 /// Stores how many times an action can be used.
 ///
-/// Charges refresh when [`Charges::refresh`] is called manually,
-/// or when the corresponding cooldown expires (if the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) is added).
-#[derive(Clone, PartialEq, Eq, Debug)]
+/// Charges refresh when [`Charges::replenish`] is called manually,
+/// or when the corresponding cooldown expires (if the [`AbilityPlugin`](crate::plugin::AbilityPlugin) is added).
+///
+/// When an action has both charges and a [`Cooldown`](crate::cooldown::Cooldown), the cooldown acts as the recharge timer.
+/// Triggering the action spends a charge, and starts the cooldown if the charges are now [recharging](Charges::recharging)
+/// and the cooldown is not already running.
+/// Each time the cooldown completes, the charges are replenished according to their [`ReplenishStrategy`]:
+/// either one charge or all of them are recovered.
+/// The cooldown then restarts if the charges are still recharging, as determined by their [`CooldownStrategy`].
+///
+/// Setting the maximum number of charges to zero disables the action:
+/// it will never be available, and neither replenishing nor ticking its cooldown will grant it charges.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize, Reflect, FromReflect)]
 pub struct Charges {
     current: u8,
     max: u8,
@@ -106,10 +128,36 @@ pub struct Charges {
     pub replenish_strat: ReplenishStrategy,
     /// How should the corresponding [`Cooldown`](crate::cooldown::Cooldown) interact with these charges?
     pub cooldown_strat: CooldownStrategy,
+    /// The temporary increase to the maximum number of charges, included in `max`.
+    bonus: u8,
+    /// The time remaining until the temporary bonus expires.
+    bonus_remaining: Duration,
+    /// The time remaining until each spent charge is recovered, with [`CooldownStrategy::IndependentTimers`].
+    #[serde(default)]
+    recharge_timers: Vec<Duration>,
+}
+
+/// A snapshot of the state of the [`Charges`] of a single action, intended for charge-pip UIs.
+///
+/// Created by [`ChargeState::display_info`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChargeDisplayInfo {
+    /// The current number of available charges.
+    pub charges: u8,
+    /// The maximum number of available charges.
+    pub max_charges: u8,
+    /// The progress towards recovering the next charge, between `0.0` and `1.0`.
+    ///
+    /// This is `1.0` when all charges are available, and `0.0` if no charge is being recovered.
+    pub recharge_fraction: f32,
+    /// The time until the next charge is recovered, if one is being recovered.
+    pub time_to_next_charge: Option<Duration>,
+    /// Is a charge currently being recovered by the action's [`Cooldown`](crate::cooldown::Cooldown)?
+    pub reloading: bool,
 }
 
 /// What happens when [`Charges`] are replenished?
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Reflect, FromReflect)]
 pub enum ReplenishStrategy {
     /// A single charge will be recovered.
     ///
@@ -122,7 +170,7 @@ pub enum ReplenishStrategy {
 }
 
 /// How do these charges replenish when cooldowns are refreshed?
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Reflect, FromReflect)]
 pub enum CooldownStrategy {
     /// Cooldowns refresh will have no effect on the charges.
     Ignore,
@@ -134,6 +182,21 @@ pub enum CooldownStrategy {
     ///
     /// Usually paired with [`ReplenishStrategy::AllAtOnce`].
     RefreshWhenEmpty,
+    /// Cooldowns limit how quickly charges can be spent, and never replenish them.
+    ///
+    /// Each use needs both a charge and a ready cooldown, and spends a charge while triggering the cooldown.
+    /// This models a shotgun whose shells are the charges and whose rate of fire is the cooldown:
+    /// charges must be restored separately, such as by calling [`Charges::replenish`] when reloading.
+    RateLimit,
+    /// Cooldowns have no effect on the charges: instead, each spent charge recovers on its own timer.
+    ///
+    /// Spending a charge starts a timer lasting `recharge_time`, and that charge is recovered once the timer elapses,
+    /// so charges spent in quick succession come back staggered.
+    /// The timers are advanced by [`ChargeState::tick`], and are not scaled by cooldown rates or reductions.
+    IndependentTimers {
+        /// The time it takes for each spent charge to be recovered.
+        recharge_time: Duration,
+    },
 }
 
 impl<A: Abilitylike> ChargeState<A> {
@@ -210,6 +273,205 @@ impl<A: Abilitylike> ChargeState<A> {
         }
     }
 
+    /// Grants `charges` additional charges of `action` immediately, up to its max charges.
+    ///
+    /// Calls [`Charges::add_charges`], returning the number of excess charges.
+    /// If the `action` is not associated with a [`Charges`], this has no effect and `0` is returned.
+    #[inline]
+    pub fn add_charges(&mut self, action: A, charges: u8) -> u8 {
+        match self.get_mut(action) {
+            Some(action_charges) => action_charges.add_charges(charges),
+            None => 0,
+        }
+    }
+
+    /// Sets the maximum number of charges of `action`, such as when a talent grants an extra charge.
+    ///
+    /// Calls [`Charges::set_max_charges`]: if more charges are available than the new max, they are removed.
+    /// Raising the max does not grant any charges: use [`ChargeState::add_charges`] to fill the new slots.
+    /// If the `action` is not associated with a [`Charges`], this has no effect.
+    #[inline]
+    pub fn set_max_charges(&mut self, action: A, max_charges: u8) {
+        if let Some(charges) = self.get_mut(action) {
+            charges.set_max_charges(max_charges);
+        }
+    }
+
+    /// Temporarily raises the maximum charges of `action` by `bonus` for `duration`, immediately granting the bonus charges.
+    ///
+    /// Calls [`Charges::add_temporary_max`].
+    /// If the `action` is not associated with a [`Charges`], this has no effect.
+    #[inline]
+    pub fn add_temporary_max(&mut self, action: A, bonus: u8, duration: Duration) {
+        if let Some(charges) = self.get_mut(action) {
+            charges.add_temporary_max(bonus, duration);
+        }
+    }
+
+    /// Advances the temporary bonuses to maximum charges and any [independent recharge timers](CooldownStrategy::IndependentTimers)
+    /// according to the elapsed `delta_time`.
+    ///
+    /// This is called by the [`tick_cooldowns`](crate::systems::tick_cooldowns) system,
+    /// but can also be called manually alongside [`CooldownState::tick`], which recharges the charges themselves.
+    #[inline]
+    pub fn tick(&mut self, delta_time: Duration) {
+        self.tick_gated(delta_time, None);
+    }
+
+    /// Advances these charges like [`tick`](Self::tick), but holds their independent recharge timers in place while the `regen_gate` is closed.
+    ///
+    /// Temporary bonuses to maximum charges still expire on time.
+    #[inline]
+    pub fn tick_gated(&mut self, delta_time: Duration, regen_gate: Option<&RegenGate>) {
+        let gate_open = !matches!(regen_gate, Some(gate) if !gate.is_open());
+
+        for charges in self.iter_mut() {
+            charges.tick_bonus(delta_time);
+            if gate_open {
+                charges.tick_recharge_timers(delta_time);
+            }
+        }
+    }
+
+    /// Is the recovery of the charges of `action` held in place by a closed `regen_gate`?
+    ///
+    /// Only charges that are [recovered over time](Charges::recharges_over_time) can be held.
+    pub(crate) fn recharge_held(&self, action: A, regen_gate: Option<&RegenGate>) -> bool {
+        match (self.get(action), regen_gate) {
+            (Some(charges), Some(gate)) => !gate.is_open() && charges.recharges_over_time(),
+            _ => false,
+        }
+    }
+
+    /// Captures the complete state of these charges, so that it can later be [restored](Self::restore).
+    ///
+    /// See [`CooldownState::snapshot`] for how snapshots are used when re-simulating frames.
+    /// Charges are counted with integers and temporary bonuses are timed with [`Duration`]s,
+    /// so a restored [`ChargeState`] is bit-identical to when it was taken.
+    #[inline]
+    #[must_use]
+    pub fn snapshot(&self) -> Self {
+        self.clone()
+    }
+
+    /// Restores the charges captured by [`snapshot`](Self::snapshot), reusing the existing allocation.
+    #[inline]
+    pub fn restore(&mut self, snapshot: &Self) {
+        self.clone_from(snapshot);
+    }
+
+    /// Computes the total pool resource needed to refill `action` to its max charges,
+    /// when each charge costs `cost_per_charge`.
+    ///
+    /// Any [`Pool::Quantity`](crate::pool::Pool::Quantity) can be used as the cost.
+    /// If the `action` is not associated with a [`Charges`], or is already full, this is zero.
+    #[inline]
+    #[must_use]
+    pub fn pool_cost_to_full<Q: Mul<f32, Output = Q>>(&self, action: A, cost_per_charge: Q) -> Q {
+        let missing_charges = match self.get(action) {
+            Some(charges) => charges.max_charges().saturating_sub(charges.charges()),
+            None => 0,
+        };
+
+        cost_per_charge * missing_charges as f32
+    }
+
+    /// Collects a [`ChargeDisplayInfo`] for each action, in [`Actionlike::variants`] order.
+    ///
+    /// Actions without [`Charges`] are represented by [`None`].
+    /// The progress towards the next charge is read from the corresponding cooldown in `cooldowns`.
+    #[must_use]
+    pub fn display_info(&self, cooldowns: &CooldownState<A>) -> Vec<Option<ChargeDisplayInfo>> {
+        A::variants()
+            .map(|action| {
+                self.get(action.clone()).as_ref().map(|charges| {
+                    let cooldown = cooldowns.get(action).as_ref();
+                    let mut info = ChargeDisplayInfo {
+                        charges: charges.charges(),
+                        max_charges: charges.max_charges(),
+                        recharge_fraction: charges.recharge_fraction(cooldown),
+                        time_to_next_charge: None,
+                        reloading: false,
+                    };
+
+                    if let Some(time_to_next_charge) = charges.next_charge_in() {
+                        info.time_to_next_charge = Some(time_to_next_charge);
+                        info.reloading = true;
+                    } else if charges.charges() < charges.max_charges()
+                        && charges.cooldown_strat != CooldownStrategy::RateLimit
+                    {
+                        if let Some(cooldown) = cooldown {
+                            if cooldown.ready().is_err() {
+                                info.time_to_next_charge = Some(cooldown.remaining());
+                                info.reloading = true;
+                            }
+                        }
+                    }
+
+                    info
+                })
+            })
+            .collect()
+    }
+
+    /// Returns how long it will be until `action` can next be used, ignoring any resource costs.
+    ///
+    /// When paired with [`Charges`], the [`Cooldown`](crate::cooldown::Cooldown) of an action is the timer that recharges it,
+    /// so the wait is computed as follows:
+    /// - if a charge is available (or the action has no [`Charges`]), uses are only gated by the cooldowns in `cooldowns`:
+    ///   the time remaining on the global cooldown, the action's [group cooldown](CooldownState::set_group),
+    ///   and (for actions without charges) the action's own cooldown, each including any active phase.
+    /// - if no charges are available, this is the larger of those shared cooldowns and the time until the next charge.
+    ///
+    /// Charges with [`CooldownStrategy::RateLimit`] are never recharged by the cooldown,
+    /// which instead gates every use while charges remain.
+    /// Charges with [`CooldownStrategy::IndependentTimers`] wait for their [next charge](Charges::next_charge_in) instead.
+    ///
+    /// Returns [`Duration::ZERO`] if the action is ready now,
+    /// and [`None`] if the action will never become usable by waiting (it is out of charges, and nothing recharges them).
+    #[must_use]
+    pub fn next_usable_in(&self, action: A, cooldowns: &CooldownState<A>) -> Option<Duration> {
+        let gcd_remaining = match &cooldowns.global_cooldown {
+            Some(global_cooldown) if !cooldowns.ignores_global_cooldown(action.clone()) => {
+                global_cooldown.time_until_ready()
+            }
+            _ => Duration::ZERO,
+        };
+        let group_remaining = match cooldowns.group(action.clone()) {
+            Some(group) => cooldowns.group_cooldown(group).time_until_ready(),
+            None => Duration::ZERO,
+        };
+
+        // Each of these includes any active phase, during which the cooldown does not advance
+        let cooldown_remaining = cooldowns
+            .get(action.clone())
+            .as_ref()
+            .map(Cooldown::time_until_ready);
+
+        let action_remaining = match self.get(action) {
+            Some(charges) if charges.cooldown_strat == CooldownStrategy::RateLimit => {
+                if charges.available() {
+                    cooldown_remaining.unwrap_or_default()
+                } else {
+                    return None;
+                }
+            }
+            Some(charges) if charges.available() => Duration::ZERO,
+            Some(charges)
+                if matches!(
+                    charges.cooldown_strat,
+                    CooldownStrategy::IndependentTimers { .. }
+                ) =>
+            {
+                charges.next_charge_in()?
+            }
+            Some(_) => cooldown_remaining?,
+            None => cooldown_remaining.unwrap_or_default(),
+        };
+
+        Some(gcd_remaining.max(group_remaining).max(action_remaining))
+    }
+
     /// Returns a reference to the underlying [`Charges`] for `action`, if set.
     #[inline]
     #[must_use]
@@ -273,6 +535,9 @@ impl Charges {
             max: max_charges,
             replenish_strat,
             cooldown_strat,
+            bonus: 0,
+            bonus_remaining: Duration::ZERO,
+            recharge_timers: Vec::new(),
         }
     }
 
@@ -283,6 +548,9 @@ impl Charges {
             max: max_charges,
             replenish_strat: ReplenishStrategy::OneAtATime,
             cooldown_strat: CooldownStrategy::Ignore,
+            bonus: 0,
+            bonus_remaining: Duration::ZERO,
+            recharge_timers: Vec::new(),
         }
     }
 
@@ -293,6 +561,9 @@ impl Charges {
             max: max_charges,
             replenish_strat: ReplenishStrategy::AllAtOnce,
             cooldown_strat: CooldownStrategy::Ignore,
+            bonus: 0,
+            bonus_remaining: Duration::ZERO,
+            recharge_timers: Vec::new(),
         }
     }
 
@@ -303,6 +574,39 @@ impl Charges {
             max: max_charges,
             replenish_strat: ReplenishStrategy::OneAtATime,
             cooldown_strat: CooldownStrategy::ConstantlyRefresh,
+            bonus: 0,
+            bonus_remaining: Duration::ZERO,
+            recharge_timers: Vec::new(),
+        }
+    }
+
+    /// Creates a new [`Charges`] with [`ReplenishStrategy::OneAtATime`] and [`CooldownStrategy::IndependentTimers`],
+    /// where each spent charge is recovered `recharge_time` after it was spent.
+    ///
+    /// ```rust
+    /// use bevy::utils::Duration;
+    /// use leafwing_abilities::charges::Charges;
+    ///
+    /// let mut charges = Charges::independent(3, Duration::from_secs(8));
+    /// charges.expend().unwrap();
+    /// charges.tick_recharge_timers(Duration::from_secs(2));
+    /// charges.expend().unwrap();
+    /// assert_eq!(charges.next_charge_in(), Some(Duration::from_secs(6)));
+    ///
+    /// // The charges come back one at a time, eight seconds after each was spent
+    /// charges.tick_recharge_timers(Duration::from_secs(6));
+    /// assert_eq!(charges.charges(), 2);
+    /// assert_eq!(charges.next_charge_in(), Some(Duration::from_secs(2)));
+    /// ```
+    pub fn independent(max_charges: u8, recharge_time: Duration) -> Charges {
+        Charges {
+            current: max_charges,
+            max: max_charges,
+            replenish_strat: ReplenishStrategy::OneAtATime,
+            cooldown_strat: CooldownStrategy::IndependentTimers { recharge_time },
+            bonus: 0,
+            bonus_remaining: Duration::ZERO,
+            recharge_timers: Vec::new(),
         }
     }
 
@@ -313,6 +617,9 @@ impl Charges {
             max: max_charges,
             replenish_strat: ReplenishStrategy::AllAtOnce,
             cooldown_strat: CooldownStrategy::RefreshWhenEmpty,
+            bonus: 0,
+            bonus_remaining: Duration::ZERO,
+            recharge_timers: Vec::new(),
         }
     }
 
@@ -324,12 +631,122 @@ impl Charges {
     }
 
     /// The maximum number of available charges
+    ///
+    /// This includes any temporary bonus added by [`Charges::add_temporary_max`].
     #[inline]
     #[must_use]
     pub fn max_charges(&self) -> u8 {
         self.max
     }
 
+    /// The maximum number of available charges, excluding any temporary bonus.
+    #[inline]
+    #[must_use]
+    pub fn base_max_charges(&self) -> u8 {
+        self.max - self.bonus
+    }
+
+    /// Temporarily raises the maximum number of charges by `bonus` for `duration`, immediately granting the bonus charges.
+    ///
+    /// Once the duration has elapsed (see [`Charges::tick_bonus`]), the maximum returns to its base value,
+    /// and any charges above it are removed.
+    /// Any existing temporary bonus is replaced.
+    #[inline]
+    pub fn add_temporary_max(&mut self, bonus: u8, duration: Duration) {
+        let base_max = self.base_max_charges();
+        self.max = base_max.saturating_add(bonus);
+        self.bonus = self.max - base_max;
+        self.bonus_remaining = duration;
+
+        let _ = self.add_charges(self.bonus);
+    }
+
+    /// Returns the time remaining until the temporary bonus to the maximum charges expires.
+    ///
+    /// This is [`Duration::ZERO`] if there is no temporary bonus.
+    #[inline]
+    #[must_use]
+    pub fn bonus_remaining(&self) -> Duration {
+        self.bonus_remaining
+    }
+
+    /// Advances the temporary bonus to the maximum charges by `delta_time`, removing it once it expires.
+    #[inline]
+    pub fn tick_bonus(&mut self, delta_time: Duration) {
+        if self.bonus == 0 {
+            return;
+        }
+
+        self.bonus_remaining = self.bonus_remaining.saturating_sub(delta_time);
+        if self.bonus_remaining == Duration::ZERO {
+            self.max -= self.bonus;
+            self.bonus = 0;
+            self.current = self.current.min(self.max);
+            self.sync_recharge_timers();
+        }
+    }
+
+    /// Advances the timers of charges with [`CooldownStrategy::IndependentTimers`] by `delta_time`,
+    /// recovering one charge for each timer that elapses.
+    ///
+    /// Charges with any other [`CooldownStrategy`] are unaffected.
+    #[inline]
+    pub fn tick_recharge_timers(&mut self, delta_time: Duration) {
+        if self.recharge_timers.is_empty() {
+            return;
+        }
+
+        for timer in self.recharge_timers.iter_mut() {
+            *timer = timer.saturating_sub(delta_time);
+        }
+
+        let n_elapsed = self
+            .recharge_timers
+            .iter()
+            .filter(|timer| **timer == Duration::ZERO)
+            .count();
+        self.recharge_timers.retain(|timer| *timer > Duration::ZERO);
+        self.current = self
+            .current
+            .saturating_add(n_elapsed.try_into().unwrap_or(u8::MAX))
+            .min(self.max);
+        self.sync_recharge_timers();
+    }
+
+    /// Returns the time until the next charge is recovered by its [independent timer](CooldownStrategy::IndependentTimers).
+    ///
+    /// Returns [`None`] if no charge is being recovered, including for any other [`CooldownStrategy`]:
+    /// the recharge timer of those charges is the action's [`Cooldown`](crate::cooldown::Cooldown).
+    #[inline]
+    #[must_use]
+    pub fn next_charge_in(&self) -> Option<Duration> {
+        self.recharge_timers.iter().min().copied()
+    }
+
+    /// Ensures that there is exactly one independent timer for each missing charge.
+    ///
+    /// When charges are recovered by other means, the timers closest to finishing are removed,
+    /// and when charges are removed by other means, new timers are started for them.
+    fn sync_recharge_timers(&mut self) {
+        let recharge_time = match self.cooldown_strat {
+            CooldownStrategy::IndependentTimers { recharge_time } => recharge_time,
+            _ => {
+                self.recharge_timers.clear();
+                return;
+            }
+        };
+
+        let missing = usize::from(self.max.saturating_sub(self.current));
+        if self.recharge_timers.len() > missing {
+            self.recharge_timers.sort();
+            let n_recovered = self.recharge_timers.len() - missing;
+            self.recharge_timers.drain(..n_recovered);
+        }
+        while self.recharge_timers.len() < missing {
+            self.recharge_timers.push(recharge_time);
+        }
+    }
+
     /// Adds `charges` to the current number of available charges
     ///
     /// This will never exceed the maximum number of charges.
@@ -341,6 +758,7 @@ impl Charges {
 
         let excess = new_total.saturating_sub(self.max);
         self.current = new_total.min(self.max);
+        self.sync_recharge_timers();
         excess
     }
 
@@ -352,16 +770,22 @@ impl Charges {
     pub fn set_charges(&mut self, charges: u8) -> u8 {
         let excess = charges.saturating_sub(self.max);
         self.current = charges.min(self.max);
+        self.sync_recharge_timers();
         excess
     }
 
     /// Set the maximmum number of available charges
     ///
     /// If the number of charges available is greater than this number, it will be reduced to the new cap.
+    /// Setting this to zero disables the action until the maximum is raised again.
+    /// Any temporary bonus from [`Charges::add_temporary_max`] is removed.
     #[inline]
     pub fn set_max_charges(&mut self, max_charges: u8) {
         self.max = max_charges;
+        self.bonus = 0;
+        self.bonus_remaining = Duration::ZERO;
         self.current = self.current.min(self.max);
+        self.sync_recharge_timers();
     }
 
     /// Is at least one charge available?
@@ -371,6 +795,70 @@ impl Charges {
         self.current > 0
     }
 
+    /// Returns the progress towards recovering the next charge, between `0.0` and `1.0`.
+    ///
+    /// The progress is read from `cooldown`, the [`Cooldown`](crate::cooldown::Cooldown) of the same action, which acts as the recharge timer,
+    /// or from the timer closest to finishing with [`CooldownStrategy::IndependentTimers`].
+    /// This is `1.0` when all charges are available, and `0.0` if no charge is being recovered.
+    #[inline]
+    #[must_use]
+    pub fn recharge_fraction(&self, cooldown: Option<&Cooldown>) -> f32 {
+        if self.current >= self.max {
+            return 1.0;
+        }
+
+        if let CooldownStrategy::IndependentTimers { recharge_time } = self.cooldown_strat {
+            return match self.next_charge_in() {
+                Some(remaining) if recharge_time > Duration::ZERO => {
+                    1.0 - remaining.as_secs_f32() / recharge_time.as_secs_f32()
+                }
+                _ => 0.0,
+            };
+        }
+
+        match cooldown {
+            Some(cooldown)
+                if self.cooldown_strat != CooldownStrategy::RateLimit
+                    && cooldown.ready().is_err() =>
+            {
+                cooldown.elapsed().as_secs_f32() / cooldown.max_time().as_secs_f32()
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Should the corresponding [`Cooldown`](crate::cooldown::Cooldown) be running to recover these charges?
+    ///
+    /// This depends on the [`CooldownStrategy`]: charges are recharging while below the max
+    /// for [`CooldownStrategy::ConstantlyRefresh`], and only while empty for [`CooldownStrategy::RefreshWhenEmpty`].
+    /// Charges with [`CooldownStrategy::Ignore`], [`CooldownStrategy::RateLimit`] or [`CooldownStrategy::IndependentTimers`]
+    /// are never recharging, as their cooldown does not recover them.
+    #[inline]
+    #[must_use]
+    pub fn recharging(&self) -> bool {
+        self.current < self.max
+            && match self.cooldown_strat {
+                CooldownStrategy::Ignore
+                | CooldownStrategy::RateLimit
+                | CooldownStrategy::IndependentTimers { .. } => false,
+                CooldownStrategy::ConstantlyRefresh => true,
+                CooldownStrategy::RefreshWhenEmpty => self.current == 0,
+            }
+    }
+
+    /// Are these charges recovered over time, by their [`Cooldown`] or by [independent timers](CooldownStrategy::IndependentTimers)?
+    ///
+    /// This is `false` for [`CooldownStrategy::Ignore`] and [`CooldownStrategy::RateLimit`], whose charges must be replenished manually.
+    /// Only charges that recover over time are held in place by a closed [`RegenGate`].
+    #[inline]
+    #[must_use]
+    pub fn recharges_over_time(&self) -> bool {
+        !matches!(
+            self.cooldown_strat,
+            CooldownStrategy::Ignore | CooldownStrategy::RateLimit
+        )
+    }
+
     /// Spends one charge for `action` if able.
     ///
     /// Returns a [`Result`] indicating whether a charge was available.
@@ -382,6 +870,7 @@ impl Charges {
         }
 
         self.current = self.current.saturating_sub(1);
+        self.sync_recharge_timers();
         Ok(())
     }
 
@@ -447,6 +936,219 @@ mod tests {
         assert_eq!(charges.charges(), 3);
     }
 
+    #[test]
+    fn zero_max_charges_are_never_available() {
+        use crate::cooldown::Cooldown;
+        use bevy::utils::Duration;
+
+        let mut charges = Some(Charges::replenish_one(0));
+        let mut cooldown = Some(Cooldown::from_secs(1.));
+        assert!(!charges.as_ref().unwrap().available());
+
+        charges.as_mut().unwrap().replenish();
+        assert_eq!(charges.as_ref().unwrap().charges(), 0);
+
+        cooldown
+            .as_mut()
+            .unwrap()
+            .tick(Duration::from_secs(5), &mut charges);
+        assert_eq!(charges.as_ref().unwrap().charges(), 0);
+        assert_eq!(
+            crate::trigger_ability::<crate::NullPool>(&mut charges, &mut cooldown, None, None),
+            Err(CannotUseAbility::NoCharges)
+        );
+    }
+
+    #[test]
+    fn disabling_charges_mid_cooldown_does_not_panic() {
+        use crate::cooldown::Cooldown;
+        use bevy::utils::Duration;
+
+        let mut charges = Some(Charges::replenish_one(2));
+        let mut cooldown = Cooldown::from_secs(1.);
+        charges.as_mut().unwrap().expend().unwrap();
+        cooldown.trigger().unwrap();
+
+        charges.as_mut().unwrap().set_max_charges(0);
+        cooldown.tick(Duration::from_secs_f32(0.5), &mut charges);
+        cooldown.tick(Duration::from_secs(3), &mut charges);
+        assert_eq!(charges.as_ref().unwrap().charges(), 0);
+        assert_eq!(
+            charges.as_mut().unwrap().expend(),
+            Err(CannotUseAbility::NoCharges)
+        );
+    }
+
+    #[test]
+    fn display_info_matches_partially_recharged_state() {
+        use crate as leafwing_abilities;
+        use crate::cooldown::Cooldown;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy)]
+        enum Action {
+            Dash,
+            Jump,
+            Run,
+        }
+
+        let mut charge_state = ChargeState::new([
+            (Action::Dash, Charges::replenish_one(3)),
+            (Action::Jump, Charges::simple(2)),
+        ]);
+        let mut cooldowns = CooldownState::new([(Action::Dash, Cooldown::from_secs(2.))]);
+
+        charge_state
+            .get_mut(Action::Dash)
+            .as_mut()
+            .unwrap()
+            .set_charges(1);
+        cooldowns.trigger(Action::Dash).unwrap();
+        cooldowns.tick(Duration::from_millis(500), None);
+
+        let display_info = charge_state.display_info(&cooldowns);
+        assert_eq!(display_info.len(), 3);
+        assert_eq!(
+            display_info[Action::Dash.index()],
+            Some(ChargeDisplayInfo {
+                charges: 1,
+                max_charges: 3,
+                recharge_fraction: 0.25,
+                time_to_next_charge: Some(Duration::from_millis(1500)),
+                reloading: true,
+            })
+        );
+        assert_eq!(
+            display_info[Action::Jump.index()],
+            Some(ChargeDisplayInfo {
+                charges: 2,
+                max_charges: 2,
+                recharge_fraction: 1.0,
+                time_to_next_charge: None,
+                reloading: false,
+            })
+        );
+        assert_eq!(display_info[Action::Run.index()], None);
+    }
+
+    #[test]
+    fn cooldown_recharges_one_charge_per_cycle() {
+        use crate::cooldown::Cooldown;
+        use crate::{trigger_ability, NullPool};
+
+        let mut charges = Some(Charges::replenish_one(3));
+        let mut cooldown = Some(Cooldown::from_secs(1.));
+
+        trigger_ability::<NullPool>(&mut charges, &mut cooldown, None, None).unwrap();
+        cooldown
+            .as_mut()
+            .unwrap()
+            .tick(Duration::from_millis(500), &mut charges);
+        // The recharge timer is already running, so is not restarted
+        trigger_ability::<NullPool>(&mut charges, &mut cooldown, None, None).unwrap();
+        assert_eq!(charges.as_ref().unwrap().charges(), 1);
+
+        cooldown
+            .as_mut()
+            .unwrap()
+            .tick(Duration::from_millis(500), &mut charges);
+        assert_eq!(charges.as_ref().unwrap().charges(), 2);
+        assert_eq!(cooldown.unwrap().remaining(), Duration::from_secs(1));
+
+        cooldown
+            .as_mut()
+            .unwrap()
+            .tick(Duration::from_secs(5), &mut charges);
+        assert_eq!(charges.as_ref().unwrap().charges(), 3);
+        assert!(cooldown.unwrap().ready().is_ok());
+    }
+
+    #[test]
+    fn cooldown_recharges_all_charges_once_empty() {
+        use crate::cooldown::Cooldown;
+        use crate::{trigger_ability, NullPool};
+
+        let mut charges = Some(Charges::replenish_all(2));
+        let mut cooldown = Some(Cooldown::from_secs(1.));
+
+        trigger_ability::<NullPool>(&mut charges, &mut cooldown, None, None).unwrap();
+        assert!(cooldown.unwrap().ready().is_ok());
+
+        trigger_ability::<NullPool>(&mut charges, &mut cooldown, None, None).unwrap();
+        assert!(cooldown.unwrap().ready().is_err());
+
+        cooldown
+            .as_mut()
+            .unwrap()
+            .tick(Duration::from_secs(1), &mut charges);
+        assert_eq!(charges.as_ref().unwrap().charges(), 2);
+        assert!(cooldown.unwrap().ready().is_ok());
+    }
+
+    #[test]
+    fn rate_limited_charges_need_both_charges_and_cooldown() {
+        use crate::cooldown::Cooldown;
+        use crate::{trigger_ability, NullPool};
+
+        let mut charges = Some(Charges::new(
+            2,
+            ReplenishStrategy::AllAtOnce,
+            CooldownStrategy::RateLimit,
+        ));
+        let mut cooldown = Some(Cooldown::from_secs(1.));
+
+        // Each shot spends a shell and starts the fire-rate cooldown
+        trigger_ability::<NullPool>(&mut charges, &mut cooldown, None, None).unwrap();
+        assert_eq!(charges.as_ref().unwrap().charges(), 1);
+        assert_eq!(
+            trigger_ability::<NullPool>(&mut charges, &mut cooldown, None, None),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(1)
+            })
+        );
+        assert_eq!(charges.as_ref().unwrap().charges(), 1);
+
+        // The cooldown does not reload the shells
+        cooldown
+            .as_mut()
+            .unwrap()
+            .tick(Duration::from_secs(1), &mut charges);
+        trigger_ability::<NullPool>(&mut charges, &mut cooldown, None, None).unwrap();
+        cooldown
+            .as_mut()
+            .unwrap()
+            .tick(Duration::from_secs(5), &mut charges);
+        assert_eq!(charges.as_ref().unwrap().charges(), 0);
+        assert_eq!(
+            trigger_ability::<NullPool>(&mut charges, &mut cooldown, None, None),
+            Err(CannotUseAbility::NoCharges)
+        );
+
+        charges.as_mut().unwrap().replenish();
+        assert_eq!(charges.as_ref().unwrap().charges(), 2);
+        trigger_ability::<NullPool>(&mut charges, &mut cooldown, None, None).unwrap();
+    }
+
+    #[test]
+    fn recharge_fraction_tracks_cooldown() {
+        let mut charges = Some(Charges::replenish_one(2));
+        let mut cooldown = Cooldown::from_secs(2.);
+        assert_eq!(
+            charges.as_ref().unwrap().recharge_fraction(Some(&cooldown)),
+            1.0
+        );
+
+        charges.as_mut().unwrap().expend().unwrap();
+        assert_eq!(charges.as_ref().unwrap().recharge_fraction(None), 0.0);
+
+        cooldown.trigger().unwrap();
+        cooldown.tick(Duration::from_millis(500), &mut charges);
+        assert_eq!(
+            charges.as_ref().unwrap().recharge_fraction(Some(&cooldown)),
+            0.25
+        );
+    }
+
     #[test]
     fn charges_replenish_all_at_once() {
         let mut charges = Charges::replenish_all(3);
@@ -455,4 +1157,121 @@ mod tests {
         charges.replenish();
         assert_eq!(charges.charges(), 3);
     }
+
+    #[test]
+    fn max_charges_can_change_at_runtime() {
+        use crate as leafwing_abilities;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy)]
+        enum Action {
+            Dash,
+            Jump,
+        }
+
+        let mut charge_state = ChargeState::new([(Action::Dash, Charges::replenish_one(2))]);
+        let dash_charges = |charge_state: &ChargeState<Action>| {
+            let charges = charge_state.get(Action::Dash).as_ref().unwrap();
+            (charges.charges(), charges.max_charges())
+        };
+
+        // Raising the max does not fill the new slot
+        charge_state.set_max_charges(Action::Dash, 3);
+        assert_eq!(dash_charges(&charge_state), (2, 3));
+        assert_eq!(charge_state.add_charges(Action::Dash, 2), 1);
+        assert_eq!(dash_charges(&charge_state), (3, 3));
+
+        // Lowering the max below the current charges removes the excess
+        charge_state.set_max_charges(Action::Dash, 1);
+        assert_eq!(dash_charges(&charge_state), (1, 1));
+
+        // Actions without charges are unaffected
+        charge_state.set_max_charges(Action::Jump, 3);
+        assert_eq!(charge_state.add_charges(Action::Jump, 1), 0);
+        assert!(charge_state.get(Action::Jump).is_none());
+    }
+
+    #[test]
+    fn pool_cost_to_full_counts_missing_charges() {
+        use crate as leafwing_abilities;
+        use crate::premade_pools::mana::Mana;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy)]
+        enum Action {
+            Fireball,
+            Frostbolt,
+        }
+
+        let mut charge_state = ChargeState::new([(Action::Fireball, Charges::simple(4))]);
+        charge_state
+            .get_mut(Action::Fireball)
+            .as_mut()
+            .unwrap()
+            .set_charges(1);
+
+        assert_eq!(
+            charge_state.pool_cost_to_full(Action::Fireball, Mana(25.)),
+            Mana(75.)
+        );
+        // Actions without charges never need refilling
+        assert_eq!(
+            charge_state.pool_cost_to_full(Action::Frostbolt, Mana(25.)),
+            Mana(0.)
+        );
+    }
+
+    #[test]
+    fn temporary_max_charges_expire() {
+        let mut charges = Charges::simple(2);
+        charges.expend().unwrap();
+
+        charges.add_temporary_max(1, Duration::from_secs(5));
+        assert_eq!(charges.max_charges(), 3);
+        assert_eq!(charges.base_max_charges(), 2);
+        assert_eq!(charges.charges(), 2);
+
+        charges.tick_bonus(Duration::from_secs(4));
+        assert_eq!(charges.max_charges(), 3);
+        charges.replenish();
+        assert_eq!(charges.charges(), 3);
+
+        // The bonus charge is removed once the buff expires
+        charges.tick_bonus(Duration::from_secs(1));
+        assert_eq!(charges.max_charges(), 2);
+        assert_eq!(charges.charges(), 2);
+        assert_eq!(charges.bonus_remaining(), Duration::ZERO);
+    }
+
+    #[test]
+    fn independent_timers_recover_charges_individually() {
+        let mut charges = Charges::independent(3, Duration::from_secs(4));
+        assert_eq!(charges.next_charge_in(), None);
+
+        // Spend charges at staggered times
+        charges.expend().unwrap();
+        charges.tick_recharge_timers(Duration::from_secs(1));
+        charges.expend().unwrap();
+        charges.tick_recharge_timers(Duration::from_secs(1));
+        charges.expend().unwrap();
+        assert_eq!(charges.charges(), 0);
+        assert_eq!(charges.next_charge_in(), Some(Duration::from_secs(2)));
+
+        // Each charge returns four seconds after it was spent
+        charges.tick_recharge_timers(Duration::from_secs(2));
+        assert_eq!(charges.charges(), 1);
+        charges.tick_recharge_timers(Duration::from_secs(1));
+        assert_eq!(charges.charges(), 2);
+        assert_eq!(charges.next_charge_in(), Some(Duration::from_secs(1)));
+
+        // Charges restored by other means cancel the timer closest to finishing
+        assert_eq!(charges.add_charges(1), 0);
+        assert_eq!(charges.charges(), 3);
+        assert_eq!(charges.next_charge_in(), None);
+
+        // A single large tick can recover several charges at once
+        charges.set_charges(0);
+        charges.tick_recharge_timers(Duration::from_secs(10));
+        assert_eq!(charges.charges(), 3);
+    }
 }
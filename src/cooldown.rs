@@ -1,11 +1,13 @@
 //! Cooldowns tick down until actions are ready to be used.
 
 use crate::{
-    charges::{ChargeState, Charges},
+    charges::{ChargeState, Charges, CooldownStrategy},
+    pool::RegenGate,
     Abilitylike, CannotUseAbility,
 };
 
-use bevy::ecs::prelude::{Component, Resource};
+use bevy::ecs::prelude::{Component, Entity, ReflectComponent, ReflectResource, Resource};
+use bevy::reflect::{Enum, FromReflect, Reflect};
 use bevy::utils::Duration;
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
@@ -47,9 +49,14 @@ use std::marker::PhantomData;
 /// }
 ///
 /// // We just jumped, so the cooldown isn't ready yet
-/// assert_eq!(cooldowns.ready(Action::Jump), Err(CannotUseAbility::OnCooldown));
+/// assert_eq!(
+///     cooldowns.ready(Action::Jump),
+///     Err(CannotUseAbility::OnCooldown { remaining: Duration::from_secs(1) })
+/// );
 /// ```
-#[derive(Resource, Component, Debug, Clone, PartialEq, Eq)]
+#[derive(Resource, Component, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Reflect)]
+#[serde(bound = "")]
+#[reflect(Component, Resource)]
 pub struct CooldownState<A: Abilitylike> {
     /// The [`Cooldown`] of each action
     ///
@@ -62,6 +69,25 @@ pub struct CooldownState<A: Abilitylike> {
     /// Whenever any cooldown for an action of type `A` is triggered,
     /// this global cooldown is triggered.
     pub global_cooldown: Option<Cooldown>,
+    /// Does each action ignore the global cooldown?
+    ///
+    /// The position in this vector corresponds to [`Actionlike::index`].
+    ignores_gcd_vec: Vec<bool>,
+    /// The shared cooldown of each [`CooldownGroup`].
+    ///
+    /// The position in this vector corresponds to the index of the group.
+    #[serde(default)]
+    group_cooldowns: Vec<Cooldown>,
+    /// The [`CooldownGroup`] that each action belongs to, if any.
+    ///
+    /// The position in this vector corresponds to [`Actionlike::index`].
+    #[serde(
+        default = "default_group_vec::<A>",
+        deserialize_with = "deserialize_group_vec::<A, _>"
+    )]
+    group_vec: Vec<Option<CooldownGroup>>,
+    #[serde(skip)]
+    #[reflect(ignore)]
     _phantom: PhantomData<A>,
 }
 
@@ -71,11 +97,21 @@ impl<A: Abilitylike> Default for CooldownState<A> {
         CooldownState {
             cooldown_vec: A::variants().map(|_| None).collect(),
             global_cooldown: None,
+            ignores_gcd_vec: A::variants().map(|_| false).collect(),
+            group_cooldowns: Vec::new(),
+            group_vec: A::variants().map(|_| None).collect(),
             _phantom: PhantomData::default(),
         }
     }
 }
 
+impl<A: Abilitylike> FromIterator<(A, Cooldown)> for CooldownState<A> {
+    /// Collects `(action, cooldown)` pairs into a [`CooldownState`], like [`CooldownState::new`].
+    fn from_iter<I: IntoIterator<Item = (A, Cooldown)>>(action_cooldown_pairs: I) -> Self {
+        CooldownState::new(action_cooldown_pairs)
+    }
+}
+
 impl<A: Abilitylike> CooldownState<A> {
     /// Creates a new [`CooldownState`] from an iterator of `(cooldown, action)` pairs
     ///
@@ -112,18 +148,111 @@ impl<A: Abilitylike> CooldownState<A> {
         cooldowns
     }
 
+    /// Creates a new [`CooldownState`] by calling `cooldown_fn` on each action, in [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) order.
+    ///
+    /// Actions for which [`None`] is returned do not have a cooldown.
+    /// Because every variant is visited, newly added actions cannot be forgotten.
+    ///
+    /// # Example
+    /// ```rust
+    /// use leafwing_abilities::cooldown::{Cooldown, CooldownState};
+    /// use leafwing_abilities::Abilitylike;
+    /// use leafwing_input_manager::Actionlike;
+    ///
+    /// #[derive(Actionlike, Abilitylike, Clone, Copy, PartialEq, Eq, Debug)]
+    /// enum Action {
+    ///     Run,
+    ///     Shoot,
+    /// }
+    ///
+    /// impl Action {
+    ///     fn cooldown(&self) -> Option<Cooldown> {
+    ///         match self {
+    ///             Action::Run => None,
+    ///             Action::Shoot => Some(Cooldown::from_secs(0.1)),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let cooldowns = CooldownState::new_with(|action: Action| action.cooldown());
+    /// assert!(cooldowns.get(Action::Run).is_none());
+    /// assert!(cooldowns.get(Action::Shoot).is_some());
+    /// ```
+    #[must_use]
+    pub fn new_with(mut cooldown_fn: impl FnMut(A) -> Option<Cooldown>) -> Self {
+        let mut cooldowns = CooldownState::default();
+        for action in A::variants() {
+            if let Some(cooldown) = cooldown_fn(action.clone()) {
+                cooldowns.set(action, cooldown);
+            }
+        }
+        cooldowns
+    }
+
+    /// Creates a new [`CooldownState`] from the [`Abilitylike::base_cooldown`] of each action.
+    ///
+    /// This is shorthand for calling [`CooldownState::new_with`] with [`Abilitylike::base_cooldown`].
+    #[must_use]
+    pub fn from_abilitylike() -> Self {
+        CooldownState::new_with(|action: A| action.base_cooldown())
+    }
+
     /// Triggers the cooldown of the `action` if it is available to be used.
     ///
     /// This can be paired with [`Cooldowns::ready`],
     /// to check if the action can be used before triggering its cooldown,
     /// or this can be used on its own,
     /// reading the returned [`Result`] to determine if the ability was used.
+    /// The cooldown of the action's [group](Self::set_group) is triggered too, if any,
+    /// as is the global cooldown unless the action [ignores it](Self::ignores_global_cooldown).
+    ///
+    /// Actions without a [`Cooldown`] are always ready, so triggering them always succeeds,
+    /// unless they are blocked by their group cooldown or the global cooldown.
     #[inline]
     pub fn trigger(&mut self, action: A) -> Result<(), CannotUseAbility> {
-        if let Some(cooldown) = self.get_mut(action) {
+        self.ready(action.clone())?;
+
+        if let Some(cooldown) = self.get_mut(action.clone()) {
             cooldown.trigger()?;
         }
 
+        self.trigger_group_cooldown(action.clone())?;
+        self.trigger_global_cooldown(action)
+    }
+
+    /// Triggers the cooldown of `action` like [`trigger`](Self::trigger),
+    /// but its cooldown takes `duration` to recover for this cycle only, as set by [`Cooldown::trigger_with_duration`].
+    ///
+    /// Any group cooldown and the global cooldown use their usual durations.
+    /// If `action` has no [`Cooldown`], this is equivalent to [`trigger`](Self::trigger).
+    ///
+    /// # Panics
+    ///
+    /// The provided duration cannot be [`Duration::ZERO`].
+    pub fn trigger_with_duration(
+        &mut self,
+        action: A,
+        duration: Duration,
+    ) -> Result<(), CannotUseAbility> {
+        self.ready(action.clone())?;
+
+        if let Some(cooldown) = self.get_mut(action.clone()) {
+            cooldown.trigger_with_duration(duration)?;
+        }
+
+        self.trigger_group_cooldown(action.clone())?;
+        self.trigger_global_cooldown(action)
+    }
+
+    /// Triggers the global cooldown, unless `action` [ignores the global cooldown](Self::ignores_global_cooldown).
+    ///
+    /// This is called when an ability is successfully triggered, and has no effect if no global cooldown is set.
+    #[inline]
+    pub fn trigger_global_cooldown(&mut self, action: A) -> Result<(), CannotUseAbility> {
+        if self.ignores_global_cooldown(action) {
+            return Ok(());
+        }
+
         if let Some(global_cooldown) = self.global_cooldown.as_mut() {
             global_cooldown.trigger()?;
         }
@@ -135,283 +264,1663 @@ impl<A: Abilitylike> CooldownState<A> {
     ///
     /// This will be `Ok` if the underlying [`Cooldown::ready`] call is true,
     /// or if no cooldown is stored for this action.
+    /// The cooldown of the action's [group](Self::set_group) must also be ready,
+    /// as must the global cooldown unless the action [ignores it](Self::ignores_global_cooldown).
     #[inline]
     pub fn ready(&self, action: A) -> Result<(), CannotUseAbility> {
-        self.gcd_ready()?;
+        if let Some(cooldown) = self.get(action.clone()) {
+            cooldown.ready()?;
+        }
 
-        if let Some(cooldown) = self.get(action) {
-            cooldown.ready()
-        } else {
+        self.group_ready(action.clone())?;
+
+        if self.ignores_global_cooldown(action) {
             Ok(())
+        } else {
+            self.gcd_ready()
         }
     }
 
-    /// Has the global cooldown for actions of type `A` expired?
+    /// Does `action` recover instantly, such that no cooldown needs to be displayed for it?
     ///
-    /// Returns `Ok(())` if no GCD is set.
+    /// This is `true` only if the action's cooldown [is instant](Cooldown::is_instant),
+    /// which can only happen for cooldowns loaded through deserialization.
+    /// Actions without a [`Cooldown`] return `false`: check whether [`CooldownState::get`] returns [`None`] for those.
     #[inline]
-    pub fn gcd_ready(&self) -> Result<(), CannotUseAbility> {
-        if let Some(global_cooldown) = self.global_cooldown.as_ref() {
-            global_cooldown.ready()
-        } else {
-            Ok(())
+    #[must_use]
+    pub fn is_instant(&self, action: A) -> bool {
+        match self.get(action) {
+            Some(cooldown) => cooldown.is_instant(),
+            None => false,
         }
     }
 
-    /// Advances each underlying [`Cooldown`] according to the elapsed `delta_time`.
+    /// Is the cooldown of `action` itself ready?
     ///
-    /// When you have a [`Option<Mut<ActionCharges<A>>>`](bevy::ecs::change_detection::Mut),
-    /// use `charges.map(|res| res.into_inner())` to convert it to the correct form.
-    pub fn tick(&mut self, delta_time: Duration, maybe_charges: Option<&mut ChargeState<A>>) {
-        if let Some(charge_state) = maybe_charges {
-            for action in A::variants() {
-                if let Some(ref mut cooldown) = self.get_mut(action.clone()) {
-                    let charges = charge_state.get_mut(action.clone());
-                    cooldown.tick(delta_time, charges);
-                }
-            }
-        } else {
-            for action in A::variants() {
-                if let Some(ref mut cooldown) = self.get_mut(action.clone()) {
-                    cooldown.tick(delta_time, &mut None);
-                }
-            }
+    /// Unlike [`CooldownState::ready`], this ignores the action's [group](Self::set_group) cooldown and the global cooldown,
+    /// which is useful for drawing a per-ability cooldown sweep.
+    /// Returns `true` if no cooldown is stored for this action.
+    /// Charges are not considered: see [`ChargeState::available`] for those.
+    #[inline]
+    #[must_use]
+    pub fn is_ready(&self, action: A) -> bool {
+        match self.get(action) {
+            Some(cooldown) => cooldown.ready().is_ok(),
+            None => true,
         }
+    }
 
-        if let Some(global_cooldown) = self.global_cooldown.as_mut() {
-            global_cooldown.tick(delta_time, &mut None);
+    /// Migrates an existing [`CooldownState`] to a new set of actions, matching actions by their variant name.
+    ///
+    /// This is intended for hot-reloading ability definitions during development:
+    /// the entries of actions that exist in both `old` and the new action type `A` are preserved,
+    /// while actions that are new to `A` are initialized from `defaults`.
+    /// Entries for actions that were removed are dropped, and the global cooldown is preserved.
+    /// [Cooldown groups](Self::set_group) are preserved too, but actions that are new to `A` do not join any group.
+    ///
+    /// Both action types must derive [`Reflect`](bevy::reflect::Reflect), which is used to look up variant names.
+    #[must_use]
+    pub fn migrate_from<B: Abilitylike + Enum>(
+        old: &CooldownState<B>,
+        defaults: &CooldownState<A>,
+    ) -> Self
+    where
+        A: Enum,
+    {
+        let mut migrated = CooldownState {
+            global_cooldown: old.global_cooldown,
+            group_cooldowns: old.group_cooldowns.clone(),
+            ..Default::default()
+        };
+
+        for action in A::variants() {
+            let old_action =
+                B::variants().find(|old_action| old_action.variant_name() == action.variant_name());
+
+            let (cooldown, ignores_gcd, group) = match old_action {
+                Some(old_action) => (
+                    *old.get(old_action.clone()),
+                    old.ignores_global_cooldown(old_action.clone()),
+                    old.group(old_action),
+                ),
+                None => (
+                    *defaults.get(action.clone()),
+                    defaults.ignores_global_cooldown(action.clone()),
+                    None,
+                ),
+            };
+            *migrated.get_mut(action.clone()) = cooldown;
+            migrated.group_vec[action.index()] = group;
+            migrated.set_ignores_global_cooldown(action, ignores_gcd);
         }
+
+        migrated
     }
 
-    /// The cooldown associated with the specified `action`, if any.
+    /// Returns the total time that `action` has spent in its active phase.
+    ///
+    /// This is useful for measuring the uptime of buffs and other abilities with an [active duration](Cooldown::with_active_duration).
+    /// Returns [`Duration::ZERO`] if the action has no [`Cooldown`].
     #[inline]
     #[must_use]
-    pub fn get(&self, action: A) -> &Option<Cooldown> {
-        &self.cooldown_vec[action.index()]
+    pub fn uptime(&self, action: A) -> Duration {
+        match self.get(action) {
+            Some(cooldown) => cooldown.uptime(),
+            None => Duration::ZERO,
+        }
     }
 
-    /// A mutable reference to the cooldown associated with the specified `action`, if any.
+    /// Returns the fraction of the time `since` the uptime was last reset that `action` has spent in its active phase.
+    ///
+    /// Returns `0.0` if `since` is [`Duration::ZERO`].
     #[inline]
     #[must_use]
-    pub fn get_mut(&mut self, action: A) -> &mut Option<Cooldown> {
-        &mut self.cooldown_vec[action.index()]
+    pub fn uptime_fraction(&self, action: A, since: Duration) -> f32 {
+        if since == Duration::ZERO {
+            return 0.0;
+        }
+
+        self.uptime(action).as_secs_f32() / since.as_secs_f32()
     }
 
-    /// Set a cooldown for the specified `action`.
+    /// Sets the global cooldown, which is shared between all actions of type `A`.
     ///
-    /// If a cooldown already existed, it will be replaced by a new cooldown with the specified duration.
+    /// Whenever an action is triggered, the global cooldown is triggered as well,
+    /// and no other action can be used until it has elapsed.
     #[inline]
-    pub fn set(&mut self, action: A, cooldown: Cooldown) -> &mut Self {
-        *self.get_mut(action) = Some(cooldown);
+    pub fn set_global_cooldown(&mut self, global_cooldown: Cooldown) -> &mut Self {
+        self.global_cooldown = Some(global_cooldown);
         self
     }
 
-    /// Collects a `&mut Self` into a `Self`.
+    /// Does `action` ignore the global cooldown?
     ///
-    /// Used to conclude the builder pattern. Actually just calls `self.clone()`.
+    /// Such actions can be used while the global cooldown is running, and do not trigger it themselves.
+    /// This is useful for instant-cast utility abilities.
     #[inline]
     #[must_use]
-    pub fn build(&mut self) -> Self {
-        self.clone()
+    pub fn ignores_global_cooldown(&self, action: A) -> bool {
+        self.ignores_gcd_vec[action.index()]
     }
 
-    /// Returns an iterator of references to the underlying non-[`None`] [`Cooldown`]s
+    /// Sets whether `action` [ignores the global cooldown](Self::ignores_global_cooldown).
     #[inline]
-    pub fn iter(&self) -> impl Iterator<Item = &Cooldown> {
-        self.cooldown_vec.iter().flatten()
+    pub fn set_ignores_global_cooldown(&mut self, action: A, ignores: bool) -> &mut Self {
+        self.ignores_gcd_vec[action.index()] = ignores;
+        self
     }
 
-    /// Returns an iterator of mutable references to the underlying non-[`None`] [`Cooldown`]s
-    #[inline]
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Cooldown> {
-        self.cooldown_vec.iter_mut().flatten()
+    /// Sets the [rate](Cooldown::set_rate) of every [`Cooldown`] (including group cooldowns and the global cooldown), such as for a character-wide haste effect.
+    pub fn set_global_rate(&mut self, rate: f32) {
+        for cooldown in self
+            .cooldown_vec
+            .iter_mut()
+            .flatten()
+            .chain(self.group_cooldowns.iter_mut())
+        {
+            cooldown.set_rate(rate);
+        }
+
+        if let Some(global_cooldown) = self.global_cooldown.as_mut() {
+            global_cooldown.set_rate(rate);
+        }
     }
-}
 
-/// A timer-like struct that records the amount of time until an action is available to be used again.
-///
-/// Cooldowns are typically stored in an [`ActionState`](crate::action_state::ActionState), associated with an action that is to be
-/// cooldown-regulated.
-///
-/// When initialized, cooldowns are always fully available.
-///
-/// ```rust
-/// use bevy::utils::Duration;
-/// use leafwing_abilities::cooldown::Cooldown;
-/// use leafwing_abilities::CannotUseAbility;
-///
-/// let mut cooldown = Cooldown::new(Duration::from_secs(3));
-/// assert_eq!(cooldown.remaining(), Duration::ZERO);
-///
-/// cooldown.trigger();
-/// assert_eq!(cooldown.remaining(), Duration::from_secs(3));
-///
-/// cooldown.tick(Duration::from_secs(1), &mut None);
-/// assert_eq!(cooldown.ready(), Err(CannotUseAbility::OnCooldown));
-///
-/// cooldown.tick(Duration::from_secs(5), &mut None);
-/// let triggered = cooldown.trigger();
-/// assert!(triggered.is_ok());
-///
-/// cooldown.refresh();
-/// assert!(cooldown.ready().is_ok());
-/// ```
-#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
-pub struct Cooldown {
-    max_time: Duration,
-    /// The amount of time that has elapsed since all [`Charges`](crate::charges::Charges) were fully replenished.
-    elapsed_time: Duration,
-}
+    /// Has the global cooldown for actions of type `A` expired?
+    ///
+    /// Returns `Ok(())` if no GCD is set, and [`CannotUseAbility::OnGlobalCooldown`] if it is not ready.
+    #[inline]
+    pub fn gcd_ready(&self) -> Result<(), CannotUseAbility> {
+        match self.global_cooldown.as_ref() {
+            Some(global_cooldown) if global_cooldown.ready().is_err() => {
+                Err(CannotUseAbility::OnGlobalCooldown {
+                    remaining: global_cooldown.time_until_ready(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
 
-impl Cooldown {
-    /// Creates a new [`Cooldown`], which will take `max_time` after it is used until it is ready again.
+    /// Creates a new [`CooldownGroup`] containing `actions`, which share the provided `cooldown`.
     ///
-    /// # Panics
+    /// Triggering any member of the group starts the shared cooldown, and no member can be used until it has elapsed.
+    /// This is independent of both the cooldowns of the individual actions and the global cooldown:
+    /// for example, all potions could share a category cooldown.
     ///
-    /// The provided max time cannot be [`Duration::ZERO`].
-    /// Instead, use [`None`] in the [`Cooldowns`] struct for an action without a cooldown.
-    pub fn new(max_time: Duration) -> Cooldown {
-        assert!(max_time != Duration::ZERO);
+    /// Each action belongs to at most one group: actions that were already in a group are moved to the new one.
+    /// The returned [`CooldownGroup`] can be used to look up the shared cooldown later.
+    ///
+    /// ```rust
+    /// use leafwing_abilities::prelude::*;
+    /// use leafwing_input_manager::Actionlike;
+    ///
+    /// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+    /// enum Item {
+    ///     HealthPotion,
+    ///     ManaPotion,
+    ///     Bomb,
+    /// }
+    ///
+    /// let mut cooldowns = CooldownState::<Item>::default();
+    /// let potions = cooldowns.set_group([Item::HealthPotion, Item::ManaPotion], Cooldown::from_secs(30.));
+    ///
+    /// cooldowns.trigger(Item::HealthPotion).unwrap();
+    /// assert!(cooldowns.ready(Item::ManaPotion).is_err());
+    /// assert!(cooldowns.ready(Item::Bomb).is_ok());
+    /// assert!(cooldowns.group_cooldown(potions).ready().is_err());
+    /// ```
+    pub fn set_group(
+        &mut self,
+        actions: impl IntoIterator<Item = A>,
+        cooldown: Cooldown,
+    ) -> CooldownGroup {
+        let group = CooldownGroup(self.group_cooldowns.len());
+        self.group_cooldowns.push(cooldown);
 
-        Cooldown {
-            max_time,
-            elapsed_time: max_time,
+        for action in actions {
+            self.group_vec[action.index()] = Some(group);
         }
+
+        group
     }
 
-    /// Creates a new [`Cooldown`] with a [`f32`] number of seconds, which will take `max_time` after it is used until it is ready again.
+    /// The [`CooldownGroup`] that `action` belongs to, if any.
+    #[inline]
+    #[must_use]
+    pub fn group(&self, action: A) -> Option<CooldownGroup> {
+        self.group_vec[action.index()]
+    }
+
+    /// The shared [`Cooldown`] of the `group`.
     ///
     /// # Panics
     ///
-    /// The provided max time must be greater than 0.
-    /// Instead, use [`None`] in the [`CooldownState`] struct for an action without a cooldown.
-    pub fn from_secs(max_time: f32) -> Cooldown {
-        assert!(max_time > 0.);
-        let max_time = Duration::from_secs_f32(max_time);
+    /// Panics if `group` was not created by this [`CooldownState`].
+    #[inline]
+    #[must_use]
+    pub fn group_cooldown(&self, group: CooldownGroup) -> &Cooldown {
+        &self.group_cooldowns[group.0]
+    }
 
-        Cooldown::new(max_time)
+    /// A mutable reference to the shared [`Cooldown`] of the `group`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `group` was not created by this [`CooldownState`].
+    #[inline]
+    #[must_use]
+    pub fn group_cooldown_mut(&mut self, group: CooldownGroup) -> &mut Cooldown {
+        &mut self.group_cooldowns[group.0]
     }
 
-    /// Advance the cooldown by `delta_time`.
+    /// Is the cooldown of the [group](Self::set_group) that `action` belongs to ready?
     ///
-    /// If the elapsed time is enough to reset the cooldown, the number of available charges.
-    pub fn tick(&mut self, delta_time: Duration, charges: &mut Option<Charges>) {
-        // Don't tick cooldowns when they are fully elapsed
-        if self.elapsed_time == self.max_time {
-            return;
+    /// Returns `Ok(())` if the action is not in a group, and [`CannotUseAbility::OnCooldown`] if the group's cooldown is not ready.
+    #[inline]
+    pub fn group_ready(&self, action: A) -> Result<(), CannotUseAbility> {
+        match self.group(action) {
+            Some(group) => self.group_cooldown(group).ready(),
+            None => Ok(()),
         }
+    }
 
-        assert!(self.max_time != Duration::ZERO);
+    /// Triggers the cooldown of the [group](Self::set_group) that `action` belongs to.
+    ///
+    /// This is called when an ability is successfully triggered, and has no effect if the action is not in a group.
+    #[inline]
+    pub fn trigger_group_cooldown(&mut self, action: A) -> Result<(), CannotUseAbility> {
+        match self.group(action) {
+            Some(group) => self.group_cooldown_mut(group).trigger(),
+            None => Ok(()),
+        }
+    }
+
+    /// Advances each underlying [`Cooldown`] according to the elapsed `delta_time`.
+    ///
+    /// When you have a [`Option<Mut<ChargeState<A>>>`](bevy::ecs::change_detection::Mut),
+    /// use `charges.map(|res| res.into_inner())` to convert it to the correct form.
+    ///
+    /// This is called by the [`tick_cooldowns`](crate::systems::tick_cooldowns) system, but is safe to call manually at any time,
+    /// such as to advance a single entity's cooldowns mid-frame for a time-warp effect.
+    /// Manual ticks compose with the automatic ones: each call simply adds more elapsed time.
+    /// To control an entity's time entirely by hand, add [`CooldownsPaused`] to it so that the system skips it.
+    /// Unlike the system, this does not apply a [`CooldownReduction`] or send [`CooldownReady`] events,
+    /// and does not advance the temporary bonuses of the charges: call [`ChargeState::tick`] for those.
+    pub fn tick(&mut self, delta_time: Duration, maybe_charges: Option<&mut ChargeState<A>>) {
+        self.tick_gated(delta_time, maybe_charges, None);
+    }
 
-        if let Some(charges) = charges {
-            let total_time = self.elapsed_time.saturating_add(delta_time);
+    /// Advances each underlying [`Cooldown`] like [`tick`](Self::tick), but holds the cooldowns that recover charges in place while the `regen_gate` is closed.
+    ///
+    /// Only the cooldowns of actions whose [`Charges`] are [recovered over time](Charges::recharges_over_time) are held:
+    /// all other cooldowns, the group cooldowns and the global cooldown advance as usual.
+    pub fn tick_gated(
+        &mut self,
+        delta_time: Duration,
+        mut maybe_charges: Option<&mut ChargeState<A>>,
+        regen_gate: Option<&RegenGate>,
+    ) {
+        for action in A::variants() {
+            if let Some(charges) = maybe_charges.as_deref() {
+                if charges.recharge_held(action.clone(), regen_gate) {
+                    continue;
+                }
+            }
 
-            let total_nanos: u64 = total_time.as_nanos().try_into().unwrap_or(u64::MAX);
-            let max_nanos: u64 = self.max_time.as_nanos().try_into().unwrap_or(u64::MAX);
+            self.tick_one(action, delta_time, maybe_charges.as_deref_mut());
+        }
 
-            let n_completed = (total_nanos / max_nanos).try_into().unwrap_or(u8::MAX);
-            let extra_time = Duration::from_nanos(total_nanos % max_nanos);
+        self.tick_group_cooldowns(delta_time);
 
-            let excess_completions = charges.add_charges(n_completed);
-            if excess_completions == 0 {
-                self.elapsed_time =
-                    (self.elapsed_time.saturating_add(extra_time)).min(self.max_time);
-            } else {
-                self.elapsed_time = self.max_time;
-            }
-        } else {
-            self.elapsed_time = self
-                .elapsed_time
-                .saturating_add(delta_time)
-                .min(self.max_time);
+        if let Some(global_cooldown) = self.global_cooldown.as_mut() {
+            global_cooldown.tick(delta_time, &mut None);
         }
     }
 
-    /// Is this action ready to be used?
+    /// Advances the shared cooldown of each [`CooldownGroup`] according to the elapsed `delta_time`.
+    pub(crate) fn tick_group_cooldowns(&mut self, delta_time: Duration) {
+        for group_cooldown in self.group_cooldowns.iter_mut() {
+            group_cooldown.tick(delta_time, &mut None);
+        }
+    }
+
+    /// Advances only the [`Cooldown`] of `action` according to the elapsed `delta_time`.
     ///
-    /// This will be true if and only if at least one charge is available.
-    /// For cooldowns without charges, this will be true if `time_remaining` is [`Duration::Zero`].
-    pub fn ready(&self) -> Result<(), CannotUseAbility> {
-        match self.elapsed_time >= self.max_time {
-            true => Ok(()),
-            false => Err(CannotUseAbility::OnCooldown),
+    /// The cooldowns of other actions, group cooldowns and the global cooldown are unaffected.
+    /// If `action` has no [`Cooldown`], this has no effect.
+    pub fn tick_one(
+        &mut self,
+        action: A,
+        delta_time: Duration,
+        maybe_charges: Option<&mut ChargeState<A>>,
+    ) {
+        if let Some(ref mut cooldown) = self.get_mut(action.clone()) {
+            match maybe_charges {
+                Some(charge_state) => cooldown.tick(delta_time, charge_state.get_mut(action)),
+                None => cooldown.tick(delta_time, &mut None),
+            }
         }
     }
 
-    /// Refreshes the cooldown, causing the underlying action to be ready to use immediately.
+    /// Refreshes every [`Cooldown`] (including group cooldowns and the global cooldown), making all actions ready to use immediately.
     ///
-    /// If this cooldown has charges, the number of available charges is increased by one (but the point within the cycle is unchanged).
+    /// Calls [`Cooldown::refresh`] on each cooldown.
     #[inline]
-    pub fn refresh(&mut self) {
-        self.elapsed_time = self.max_time
+    pub fn reset_all(&mut self) {
+        for cooldown in self
+            .cooldown_vec
+            .iter_mut()
+            .flatten()
+            .chain(self.group_cooldowns.iter_mut())
+        {
+            cooldown.refresh();
+        }
+
+        if let Some(global_cooldown) = self.global_cooldown.as_mut() {
+            global_cooldown.refresh();
+        }
     }
 
-    /// Use the underlying cooldown if and only if it is ready, resetting the cooldown to its maximum value.
+    /// Refreshes the [`Cooldown`] of `action`, making it ready to use immediately.
     ///
-    /// If this cooldown has multiple charges, only one will be consumed.
+    /// Calls [`Cooldown::refresh`]. If `action` has no [`Cooldown`], this has no effect.
+    /// The global cooldown is unaffected.
+    #[inline]
+    pub fn reset(&mut self, action: A) {
+        if let Some(cooldown) = self.get_mut(action) {
+            cooldown.refresh();
+        }
+    }
+
+    /// Refunds `amount` of the [`Cooldown`] of `action`, bringing it closer to being ready.
     ///
-    /// Returns a result indicating whether the cooldown was ready.
-    /// If the cooldown was not ready, [`CannotUseAbility::OnCooldown`] is returned and this call has no effect.
+    /// Calls [`Cooldown::refund`]. If `action` has no [`Cooldown`], this has no effect.
+    /// As the [group cooldown](Self::set_group) of `action` was started by the same trigger, it is refunded too.
+    /// The global cooldown is unaffected.
     #[inline]
-    pub fn trigger(&mut self) -> Result<(), CannotUseAbility> {
-        self.ready()?;
-        self.elapsed_time = Duration::ZERO;
+    pub fn refund(&mut self, action: A, amount: Duration) {
+        if let Some(cooldown) = self.get_mut(action.clone()) {
+            cooldown.refund(amount);
+        }
 
-        Ok(())
+        if let Some(group) = self.group(action) {
+            self.group_cooldown_mut(group).refund(amount);
+        }
     }
 
-    /// Returns the time that it will take for this action to be ready to use again after being triggered.
+    /// Restarts every [`Cooldown`] (including group cooldowns and the global cooldown) from the beginning, locking out all actions for their full duration.
+    ///
+    /// Unlike [`trigger`](Cooldown::trigger), this does not start an active phase: any current active phase is ended.
+    /// The configured durations of each cooldown are unchanged.
     #[inline]
-    pub fn max_time(&self) -> Duration {
-        self.max_time
+    pub fn refresh_all(&mut self) {
+        let cooldowns = self.cooldown_vec.iter_mut().flatten();
+        for cooldown in cooldowns
+            .chain(self.group_cooldowns.iter_mut())
+            .chain(self.global_cooldown.as_mut())
+        {
+            cooldown.elapsed_time = Duration::ZERO;
+            cooldown.active_remaining = Duration::ZERO;
+        }
+    }
+
+    /// Captures the complete state of these cooldowns, so that it can later be [restored](Self::restore).
+    ///
+    /// Snapshots exist for rollback networking: keep the snapshot of the last confirmed frame,
+    /// and restore it before re-simulating the frames that followed once late inputs arrive.
+    /// [`ChargeState`](crate::charges::ChargeState) and [`Pool`](crate::pool::Pool) offer the same pair of methods,
+    /// and should be snapshotted on the same frames.
+    ///
+    /// The elapsed time, active phase and uptime of each [`Cooldown`] (and the global cooldown) are stored as [`Duration`]s,
+    /// so restoring a snapshot makes these cooldowns bit-identical to when it was taken.
+    ///
+    /// Ticking is only deterministic if the floating point [rates](Cooldown::set_rate) and any [`CooldownReduction`]
+    /// or [`ExhaustionState`] are identical on each peer: the exhaustion values are state in their own right,
+    /// and should be snapshotted alongside the cooldowns.
+    #[inline]
+    #[must_use]
+    pub fn snapshot(&self) -> Self {
+        self.clone()
+    }
+
+    /// Restores the state captured by [`snapshot`](Self::snapshot).
+    ///
+    /// The existing allocations are reused, so this is cheap enough to call every frame.
+    #[inline]
+    pub fn restore(&mut self, snapshot: &Self) {
+        self.clone_from(snapshot);
+    }
+
+    /// Returns the time remaining on the cooldown of `action`.
+    ///
+    /// Returns [`None`] if no cooldown is set for this action. The global cooldown is not considered.
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self, action: A) -> Option<Duration> {
+        self.get(action).as_ref().map(Cooldown::remaining)
+    }
+
+    /// The cooldown associated with the specified `action`, if any.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, action: A) -> &Option<Cooldown> {
+        &self.cooldown_vec[action.index()]
+    }
+
+    /// A mutable reference to the cooldown associated with the specified `action`, if any.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, action: A) -> &mut Option<Cooldown> {
+        &mut self.cooldown_vec[action.index()]
+    }
+
+    /// Set a cooldown for the specified `action`.
+    ///
+    /// If a cooldown already existed, it will be replaced by a new cooldown with the specified duration.
+    #[inline]
+    pub fn set(&mut self, action: A, cooldown: Cooldown) -> &mut Self {
+        *self.get_mut(action) = Some(cooldown);
+        self
+    }
+
+    /// Collects a `&mut Self` into a `Self`.
+    ///
+    /// Used to conclude the builder pattern. Actually just calls `self.clone()`.
+    #[inline]
+    #[must_use]
+    pub fn build(&mut self) -> Self {
+        self.clone()
+    }
+
+    /// Returns an iterator of references to the underlying non-[`None`] [`Cooldown`]s
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Cooldown> {
+        self.cooldown_vec.iter().flatten()
+    }
+
+    /// Returns an iterator of mutable references to the underlying non-[`None`] [`Cooldown`]s
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Cooldown> {
+        self.cooldown_vec.iter_mut().flatten()
+    }
+
+    /// Returns an iterator over each action that has a [`Cooldown`], paired with a reference to that cooldown.
+    ///
+    /// Actions are visited in [`Actionlike::variants`] order. The global cooldown is not included.
+    ///
+    /// ```rust
+    /// use leafwing_abilities::prelude::*;
+    /// use leafwing_input_manager::Actionlike;
+    ///
+    /// #[derive(Actionlike, Abilitylike, Clone, Copy, PartialEq, Debug)]
+    /// enum Action {
+    ///     Run,
+    ///     Jump,
+    /// }
+    ///
+    /// let cooldowns = CooldownState::new([(Action::Jump, Cooldown::from_secs(1.))]);
+    /// for (action, cooldown) in cooldowns.iter_pairs() {
+    ///     assert_eq!(action, Action::Jump);
+    ///     assert!(cooldown.ready().is_ok());
+    /// }
+    /// ```
+    #[inline]
+    pub fn iter_pairs(&self) -> impl Iterator<Item = (A, &Cooldown)> {
+        A::variants()
+            .zip(self.cooldown_vec.iter())
+            .filter_map(|(action, cooldown)| Some((action, cooldown.as_ref()?)))
+    }
+
+    /// Returns an iterator over each action that has a [`Cooldown`], paired with a mutable reference to that cooldown.
+    ///
+    /// Actions are visited in [`Actionlike::variants`] order. The global cooldown is not included.
+    #[inline]
+    pub fn iter_pairs_mut(&mut self) -> impl Iterator<Item = (A, &mut Cooldown)> {
+        A::variants()
+            .zip(self.cooldown_vec.iter_mut())
+            .filter_map(|(action, cooldown)| Some((action, cooldown.as_mut()?)))
+    }
+}
+
+/// A set of actions that share a cooldown, created by [`CooldownState::set_group`].
+///
+/// Triggering any action in the group starts the group's cooldown, which blocks every action in the group.
+/// Groups are identified by the order in which they were created, and are only meaningful for the [`CooldownState`] that created them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect, FromReflect)]
+pub struct CooldownGroup(usize);
+
+/// A resource storing the [`CooldownState`] that newly spawned entities should use by default.
+///
+/// Inserted by [`AbilityPlugin::with_default_cooldowns`](crate::plugin::AbilityPlugin::with_default_cooldowns).
+/// The [`apply_default_cooldowns`](crate::systems::apply_default_cooldowns) system copies it onto
+/// each newly added [`CooldownState`] component that has no cooldowns of its own,
+/// such as one created by [`AbilitiesBundle::default`](crate::AbilitiesBundle).
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct DefaultCooldowns<A: Abilitylike>(pub CooldownState<A>);
+
+/// An event sent when the [`Cooldown`] of an ability of type `A` becomes ready to use again.
+///
+/// Sent by the [`tick_cooldowns`](crate::systems::tick_cooldowns) system, on the frame that the cooldown finishes.
+/// Changes to the global cooldown do not send this event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CooldownReady<A: Abilitylike> {
+    /// The entity whose cooldown became ready, or [`None`] if the [`CooldownState`] is stored as a resource.
+    pub entity: Option<Entity>,
+    /// The ability whose cooldown became ready.
+    pub action: A,
+}
+
+/// A marker component that freezes the [`CooldownState`] and [`ChargeState`] of the entity it is added to.
+///
+/// Paused entities are skipped entirely by the [`tick_cooldowns`](crate::systems::tick_cooldowns) system,
+/// rather than being advanced by zero, so no time is lost or gained while paused.
+/// Charges are replenished by cooldowns, so charge regeneration is paused as well,
+/// resuming from the same point in the cycle once this component is removed.
+///
+/// This affects every ability type on the entity. To pause the cooldowns of all entities,
+/// add a run condition to [`AbilitySystem::Tick`](crate::plugin::AbilitySystem::Tick) instead.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CooldownsPaused;
+
+/// A stat that speeds up the [`CooldownState`] of the entity (or resource) that it is stored alongside.
+///
+/// The reduction is a fraction between `0.0` and [`CooldownReduction::MAX`]:
+/// a reduction of `0.3` makes each cooldown take 30% less time to recover,
+/// and a reduction of `0.5` halves the time needed.
+///
+/// This is read by the [`tick_cooldowns`](crate::systems::tick_cooldowns) system.
+///
+/// ```rust
+/// use bevy::utils::Duration;
+/// use leafwing_abilities::cooldown::CooldownReduction;
+///
+/// let reduction = CooldownReduction::new(0.5);
+/// assert_eq!(reduction.rate(), 2.0);
+/// assert_eq!(reduction.scale(Duration::from_secs(1)), Duration::from_secs(2));
+///
+/// // Reductions are clamped to a sensible range
+/// assert_eq!(CooldownReduction::new(3.0).reduction(), CooldownReduction::MAX);
+/// assert_eq!(CooldownReduction::new(-1.0).reduction(), 0.0);
+/// ```
+#[derive(Resource, Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct CooldownReduction(f32);
+
+impl CooldownReduction {
+    /// The largest permitted cooldown reduction.
+    ///
+    /// Higher values would make cooldowns recover arbitrarily fast.
+    pub const MAX: f32 = 0.9;
+
+    /// Creates a new [`CooldownReduction`] from a fraction of the cooldown time.
+    ///
+    /// The provided `reduction` is clamped between `0.0` and [`CooldownReduction::MAX`].
+    /// Non-finite values are treated as no reduction.
+    #[inline]
+    #[must_use]
+    pub fn new(reduction: f32) -> CooldownReduction {
+        if reduction.is_finite() {
+            CooldownReduction(reduction.clamp(0.0, Self::MAX))
+        } else {
+            CooldownReduction(0.0)
+        }
+    }
+
+    /// The fraction of cooldown time that is removed.
+    #[inline]
+    #[must_use]
+    pub fn reduction(&self) -> f32 {
+        self.0
+    }
+
+    /// The rate at which cooldowns recover, relative to their normal speed.
+    #[inline]
+    #[must_use]
+    pub fn rate(&self) -> f32 {
+        1.0 / (1.0 - self.0)
+    }
+
+    /// Scales the elapsed `delta_time` by the [`rate`](Self::rate) of this reduction.
+    #[inline]
+    #[must_use]
+    pub fn scale(&self, delta_time: Duration) -> Duration {
+        // Work in nanoseconds to avoid losing precision to floating point seconds
+        let scaled_nanos = delta_time.as_nanos() as f64 / (1.0 - self.0 as f64);
+        Duration::from_nanos(scaled_nanos.round() as u64)
+    }
+}
+
+/// Tracks how exhausted each ability of type `A` is, slowing its cooldown when it is used in rapid succession.
+///
+/// Each use of an ability adds [`per_use`](Self::per_use) to its exhaustion,
+/// which then decays by [`decay_per_second`](Self::decay_per_second) over time.
+/// While exhausted, the ability's cooldown recovers at a [`rate`](Self::rate) of `1 / (1 + exhaustion)`:
+/// an exhaustion of `1.0` halves the recovery speed.
+/// The global cooldown is unaffected.
+///
+/// Uses are recorded by the methods of [`AbilityState`](crate::AbilityState),
+/// and this is read and decayed by the [`tick_cooldowns`](crate::systems::tick_cooldowns) system.
+///
+/// ```rust
+/// use bevy::utils::Duration;
+/// use leafwing_abilities::cooldown::ExhaustionState;
+/// use leafwing_abilities::prelude::*;
+/// use leafwing_input_manager::Actionlike;
+///
+/// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+/// enum Action {
+///     Dodge,
+/// }
+///
+/// let mut exhaustion = ExhaustionState::<Action>::new(0.5, 0.25);
+/// exhaustion.record_use(Action::Dodge);
+/// exhaustion.record_use(Action::Dodge);
+/// assert_eq!(exhaustion.rate(Action::Dodge), 0.5);
+///
+/// exhaustion.decay(Duration::from_secs(4));
+/// assert_eq!(exhaustion.rate(Action::Dodge), 1.0);
+/// ```
+#[derive(Resource, Component, Debug, Clone, PartialEq)]
+pub struct ExhaustionState<A: Abilitylike> {
+    /// The exhaustion of each action, stored in [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) order.
+    exhaustion_vec: Vec<f32>,
+    /// The exhaustion added each time an ability is used.
+    pub per_use: f32,
+    /// The exhaustion removed from each ability per second.
+    pub decay_per_second: f32,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Abilitylike> ExhaustionState<A> {
+    /// Creates a new [`ExhaustionState`], where no abilities are exhausted.
+    #[must_use]
+    pub fn new(per_use: f32, decay_per_second: f32) -> Self {
+        ExhaustionState {
+            exhaustion_vec: A::variants().map(|_| 0.0).collect(),
+            per_use,
+            decay_per_second,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Records that `action` was used, increasing its exhaustion by [`per_use`](Self::per_use).
+    #[inline]
+    pub fn record_use(&mut self, action: A) {
+        if self.per_use.is_finite() && self.per_use > 0.0 {
+            self.exhaustion_vec[action.index()] += self.per_use;
+        }
+    }
+
+    /// The current exhaustion of `action`.
+    #[inline]
+    #[must_use]
+    pub fn exhaustion(&self, action: A) -> f32 {
+        self.exhaustion_vec[action.index()]
+    }
+
+    /// The rate at which the cooldown of `action` recovers, relative to its normal speed.
+    #[inline]
+    #[must_use]
+    pub fn rate(&self, action: A) -> f32 {
+        1.0 / (1.0 + self.exhaustion(action))
+    }
+
+    /// Scales the elapsed `delta_time` by the [`rate`](Self::rate) of `action`.
+    #[inline]
+    #[must_use]
+    pub fn scale(&self, action: A, delta_time: Duration) -> Duration {
+        delta_time.mul_f32(self.rate(action))
+    }
+
+    /// Reduces the exhaustion of every action according to the elapsed `delta_time`.
+    ///
+    /// Exhaustion never falls below zero.
+    pub fn decay(&mut self, delta_time: Duration) {
+        let decay = self.decay_per_second * delta_time.as_secs_f32();
+        if !decay.is_finite() || decay <= 0.0 {
+            return;
+        }
+
+        for exhaustion in self.exhaustion_vec.iter_mut() {
+            *exhaustion = (*exhaustion - decay).max(0.0);
+        }
+    }
+
+    /// Advances each [`Cooldown`] in `cooldowns` according to the elapsed `delta_time`, slowed by the exhaustion of its action.
+    ///
+    /// This is a drop-in replacement for [`CooldownState::tick_gated`]. The global cooldown is ticked normally.
+    pub fn tick_cooldowns(
+        &self,
+        cooldowns: &mut CooldownState<A>,
+        delta_time: Duration,
+        mut maybe_charges: Option<&mut ChargeState<A>>,
+        regen_gate: Option<&RegenGate>,
+    ) {
+        for action in A::variants() {
+            if let Some(charges) = maybe_charges.as_deref() {
+                if charges.recharge_held(action.clone(), regen_gate) {
+                    continue;
+                }
+            }
+
+            let delta_time = self.scale(action.clone(), delta_time);
+            cooldowns.tick_one(action, delta_time, maybe_charges.as_deref_mut());
+        }
+
+        cooldowns.tick_group_cooldowns(delta_time);
+        if let Some(global_cooldown) = cooldowns.global_cooldown.as_mut() {
+            global_cooldown.tick(delta_time, &mut None);
+        }
+    }
+}
+
+/// Limits how many abilities of type `A` with an [active duration](Cooldown::with_active_duration) can be active at once.
+///
+/// For example, a character might only be able to maintain two auras at a time.
+/// When an ability with an active duration is triggered while the limit is reached,
+/// the [`ConcurrencyOverflow`] policy determines what happens.
+/// Abilities without an active duration are never limited.
+///
+/// This is checked by the methods of [`AbilityState`](crate::AbilityState).
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConcurrencyLimit<A: Abilitylike> {
+    /// The maximum number of abilities that can be active at once.
+    pub max_active: usize,
+    /// What happens when an ability is triggered while `max_active` abilities are already active.
+    pub overflow: ConcurrencyOverflow,
+    _phantom: PhantomData<A>,
+}
+
+/// The policy used by a [`ConcurrencyLimit`] when too many abilities would be active at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConcurrencyOverflow {
+    /// The new ability cannot be used, returning [`CannotUseAbility::TooManyActive`].
+    #[default]
+    Reject,
+    /// The ability that has been active the longest is [deactivated](Cooldown::deactivate) to make room for the new ability.
+    DeactivateOldest,
+}
+
+impl<A: Abilitylike> ConcurrencyLimit<A> {
+    /// Creates a new [`ConcurrencyLimit`], allowing at most `max_active` abilities to be active at once.
+    #[inline]
+    #[must_use]
+    pub fn new(max_active: usize, overflow: ConcurrencyOverflow) -> Self {
+        ConcurrencyLimit {
+            max_active,
+            overflow,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of abilities in `cooldowns` that are currently in their active phase.
+    #[must_use]
+    pub fn n_active(cooldowns: &CooldownState<A>) -> usize {
+        cooldowns
+            .iter()
+            .filter(|cooldown| cooldown.is_active())
+            .count()
+    }
+
+    /// Could `action` be activated without exceeding this limit?
+    ///
+    /// Returns [`CannotUseAbility::TooManyActive`] if the limit is reached and the overflow policy is [`ConcurrencyOverflow::Reject`].
+    pub fn check(&self, action: A, cooldowns: &CooldownState<A>) -> Result<(), CannotUseAbility> {
+        if !self.is_limited(action, cooldowns) || Self::n_active(cooldowns) < self.max_active {
+            return Ok(());
+        }
+
+        match self.overflow {
+            ConcurrencyOverflow::DeactivateOldest if self.max_active > 0 => Ok(()),
+            _ => Err(CannotUseAbility::TooManyActive),
+        }
+    }
+
+    /// Ensures that `action` can be activated without exceeding this limit, applying the overflow policy if needed.
+    ///
+    /// With [`ConcurrencyOverflow::DeactivateOldest`], the abilities that have been active for the longest are deactivated until there is room.
+    pub fn make_room(
+        &self,
+        action: A,
+        cooldowns: &mut CooldownState<A>,
+    ) -> Result<(), CannotUseAbility> {
+        self.check(action.clone(), cooldowns)?;
+
+        if !self.is_limited(action, cooldowns) {
+            return Ok(());
+        }
+
+        while Self::n_active(cooldowns) >= self.max_active {
+            let oldest = cooldowns
+                .iter_mut()
+                .filter(|cooldown| cooldown.is_active())
+                .max_by_key(|cooldown| cooldown.active_duration() - cooldown.active_remaining());
+
+            match oldest {
+                Some(oldest) => oldest.deactivate(),
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Is `action` subject to this limit?
+    fn is_limited(&self, action: A, cooldowns: &CooldownState<A>) -> bool {
+        match cooldowns.get(action) {
+            Some(cooldown) => cooldown.active_duration() > Duration::ZERO,
+            None => false,
+        }
+    }
+}
+
+/// A timer-like struct that records the amount of time until an action is available to be used again.
+///
+/// Cooldowns are typically stored in an [`ActionState`](crate::action_state::ActionState), associated with an action that is to be
+/// cooldown-regulated.
+///
+/// When initialized, cooldowns are always fully available.
+///
+/// All times are stored and accumulated as [`Duration`]s, so even very long cooldowns tick without drifting.
+///
+/// ```rust
+/// use bevy::utils::Duration;
+/// use leafwing_abilities::cooldown::Cooldown;
+/// use leafwing_abilities::CannotUseAbility;
+///
+/// let mut cooldown = Cooldown::new(Duration::from_secs(3));
+/// assert_eq!(cooldown.remaining(), Duration::ZERO);
+///
+/// cooldown.trigger();
+/// assert_eq!(cooldown.remaining(), Duration::from_secs(3));
+///
+/// cooldown.tick(Duration::from_secs(1), &mut None);
+/// assert_eq!(
+///     cooldown.ready(),
+///     Err(CannotUseAbility::OnCooldown { remaining: Duration::from_secs(2) })
+/// );
+///
+/// cooldown.tick(Duration::from_secs(5), &mut None);
+/// let triggered = cooldown.trigger();
+/// assert!(triggered.is_ok());
+///
+/// cooldown.refresh();
+/// assert!(cooldown.ready().is_ok());
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize, Reflect, FromReflect)]
+pub struct Cooldown {
+    max_time: Duration,
+    /// The amount of time that has elapsed since all [`Charges`](crate::charges::Charges) were fully replenished.
+    elapsed_time: Duration,
+    /// How long the ability stays active after being triggered, before the cooldown begins.
+    active_duration: Duration,
+    /// The amount of time remaining in the current active phase.
+    active_remaining: Duration,
+    /// The total time spent in the active phase, since this cooldown was created or [`Cooldown::reset_uptime`] was called.
+    uptime: Duration,
+    /// The rate at which this cooldown recovers, relative to the passage of time.
+    ///
+    /// Always finite and non-negative, which is what allows [`Cooldown`] to implement [`Eq`].
+    #[serde(default = "default_rate", deserialize_with = "deserialize_rate")]
+    rate: f32,
+    /// The configured max time, while `max_time` is overridden for the current cycle by [`Cooldown::trigger_with_duration`].
+    #[serde(default)]
+    configured_max_time: Option<Duration>,
+}
+
+/// The default groups of a [`CooldownState`], used when deserializing cooldowns that were saved without any.
+fn default_group_vec<A: Abilitylike>() -> Vec<Option<CooldownGroup>> {
+    A::variants().map(|_| None).collect()
+}
+
+/// Deserializes the group of each action in a [`CooldownState`], with one entry for each variant of `A`.
+///
+/// Actions that were added to `A` since the cooldowns were saved have no group.
+fn deserialize_group_vec<'de, A: Abilitylike, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Option<CooldownGroup>>, D::Error> {
+    let mut group_vec = Vec::<Option<CooldownGroup>>::deserialize(deserializer)?;
+    group_vec.resize(A::n_variants(), None);
+    Ok(group_vec)
+}
+
+/// The default [`Cooldown::rate`], used when deserializing cooldowns that were saved without one.
+fn default_rate() -> f32 {
+    1.0
+}
+
+/// Deserializes a [`Cooldown::rate`], applying the same rules as [`Cooldown::set_rate`].
+fn deserialize_rate<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    let mut cooldown = Cooldown::from_secs(1.);
+    cooldown.set_rate(f32::deserialize(deserializer)?);
+    Ok(cooldown.rate)
+}
+
+// The rate is never NaN, so equality is reflexive
+impl Eq for Cooldown {}
+
+impl Cooldown {
+    /// Creates a new [`Cooldown`], which will take `max_time` after it is used until it is ready again.
+    ///
+    /// # Panics
+    ///
+    /// The provided max time cannot be [`Duration::ZERO`].
+    /// Instead, use [`None`] in the [`Cooldowns`] struct for an action without a cooldown.
+    pub fn new(max_time: Duration) -> Cooldown {
+        assert!(max_time != Duration::ZERO);
+
+        Cooldown {
+            max_time,
+            elapsed_time: max_time,
+            active_duration: Duration::ZERO,
+            active_remaining: Duration::ZERO,
+            uptime: Duration::ZERO,
+            rate: 1.0,
+            configured_max_time: None,
+        }
+    }
+
+    /// Sets how long the ability stays active after being triggered.
+    ///
+    /// While active, the ability cannot be triggered again and the cooldown does not advance:
+    /// the cooldown only begins once the active phase has ended.
+    /// This is useful for temporary buffs, whose cooldown should start when the buff expires.
+    ///
+    /// ```rust
+    /// use bevy::utils::Duration;
+    /// use leafwing_abilities::cooldown::Cooldown;
+    ///
+    /// let mut cooldown = Cooldown::from_secs(5.).with_active_duration(Duration::from_secs(2));
+    /// cooldown.trigger().unwrap();
+    /// assert!(cooldown.is_active());
+    ///
+    /// cooldown.tick(Duration::from_secs(3), &mut None);
+    /// assert!(!cooldown.is_active());
+    /// assert_eq!(cooldown.remaining(), Duration::from_secs(4));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_active_duration(mut self, active_duration: Duration) -> Cooldown {
+        self.active_duration = active_duration;
+        self
+    }
+
+    /// Starts this cooldown at the beginning of its cycle, rather than ready to use.
+    ///
+    /// Newly created cooldowns are ready immediately.
+    /// This is useful for abilities that cannot be used at the start of a match, such as an ultimate.
+    /// No active phase is started.
+    ///
+    /// ```rust
+    /// use bevy::utils::Duration;
+    /// use leafwing_abilities::cooldown::Cooldown;
+    ///
+    /// let ultimate = Cooldown::from_secs(30.).starting_on_cooldown();
+    /// assert!(ultimate.ready().is_err());
+    /// assert_eq!(ultimate.remaining(), Duration::from_secs(30));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn starting_on_cooldown(mut self) -> Cooldown {
+        self.elapsed_time = Duration::ZERO;
+        self
+    }
+
+    /// Creates a new [`Cooldown`] with a [`f32`] number of seconds, which will take `max_time` after it is used until it is ready again.
+    ///
+    /// A [`f32`] cannot exactly represent every long duration, such as `86400.1` seconds:
+    /// use [`Cooldown::new`] when the exact duration matters.
+    ///
+    /// # Panics
+    ///
+    /// The provided max time must be greater than 0.
+    /// Instead, use [`None`] in the [`CooldownState`] struct for an action without a cooldown.
+    pub fn from_secs(max_time: f32) -> Cooldown {
+        assert!(max_time > 0.);
+        let max_time = Duration::from_secs_f32(max_time);
+
+        Cooldown::new(max_time)
+    }
+
+    /// Creates a new [`Cooldown`] with a whole number of milliseconds, which will take `max_time` after it is used until it is ready again.
+    ///
+    /// Unlike [`Cooldown::from_secs`], no precision is lost converting from a float.
+    ///
+    /// # Panics
+    ///
+    /// The provided max time must be greater than 0.
+    /// Instead, use [`None`] in the [`CooldownState`] struct for an action without a cooldown.
+    pub fn from_millis(max_time: u64) -> Cooldown {
+        Cooldown::new(Duration::from_millis(max_time))
+    }
+
+    /// Advance the cooldown by `delta_time`.
+    ///
+    /// Each time the cooldown completes, the `charges` are [replenished](Charges::replenish) according to their [`CooldownStrategy`],
+    /// and the cooldown restarts if they are still [recharging](Charges::recharging).
+    /// Once any active phase has ended, the time that the cooldown recovers by is scaled by its [`rate`](Self::rate).
+    pub fn tick(&mut self, delta_time: Duration, charges: &mut Option<Charges>) {
+        // The cooldown only begins once the active phase has ended
+        let delta_time = if self.active_remaining > Duration::ZERO {
+            self.uptime += delta_time.min(self.active_remaining);
+            let leftover_time = delta_time.saturating_sub(self.active_remaining);
+            self.active_remaining = self.active_remaining.saturating_sub(delta_time);
+            leftover_time
+        } else {
+            delta_time
+        };
+
+        let delta_time = if self.rate == 1.0 {
+            delta_time
+        } else {
+            Duration::try_from_secs_f64(delta_time.as_secs_f64() * self.rate as f64)
+                .unwrap_or(Duration::MAX)
+        };
+
+        // Don't tick cooldowns when they are fully elapsed
+        if self.elapsed_time == self.max_time {
+            return;
+        }
+
+        assert!(self.max_time != Duration::ZERO);
+
+        match charges {
+            Some(charges)
+                if matches!(
+                    charges.cooldown_strat,
+                    CooldownStrategy::ConstantlyRefresh | CooldownStrategy::RefreshWhenEmpty
+                ) =>
+            {
+                let mut total_time = self.elapsed_time.saturating_add(delta_time);
+
+                // Each completed cycle replenishes the charges,
+                // and the cooldown only restarts if they are still recharging
+                while total_time >= self.max_time {
+                    total_time -= self.max_time;
+                    self.restore_max_time();
+                    charges.replenish();
+
+                    if !charges.recharging() {
+                        self.elapsed_time = self.max_time;
+                        return;
+                    }
+                }
+
+                self.elapsed_time = total_time;
+            }
+            _ => self.add_elapsed(delta_time),
+        }
+    }
+
+    /// Advances the cooldown by an arbitrary `amount` of progress, measured in the same units as its duration (seconds).
+    ///
+    /// Unlike [`Cooldown::tick`], this does not depend on the passage of time:
+    /// it can be used to drive a cooldown by distance traveled, ammunition collected and so on.
+    /// The active phase and any [`Charges`] are unaffected.
+    /// Negative and NaN amounts are ignored, while amounts too large to represent (including infinity) complete the cooldown.
+    ///
+    /// ```rust
+    /// use leafwing_abilities::cooldown::Cooldown;
+    ///
+    /// // Reloads after traveling 10 meters
+    /// let mut reload = Cooldown::from_secs(10.);
+    /// reload.trigger().unwrap();
+    ///
+    /// reload.advance(4.);
+    /// assert!(reload.ready().is_err());
+    ///
+    /// reload.advance(6.);
+    /// assert!(reload.ready().is_ok());
+    /// ```
+    #[inline]
+    pub fn advance(&mut self, amount: f32) {
+        if amount.is_nan() || amount <= 0.0 {
+            return;
+        }
+
+        let amount = Duration::try_from_secs_f32(amount).unwrap_or(Duration::MAX);
+        self.add_elapsed(amount);
+    }
+
+    /// Adds `amount` to the elapsed time, without exceeding the max time.
+    #[inline]
+    fn add_elapsed(&mut self, amount: Duration) {
+        self.elapsed_time = self.elapsed_time.saturating_add(amount).min(self.max_time);
+        if self.elapsed_time == self.max_time {
+            self.restore_max_time();
+        }
+    }
+
+    /// Ends any override of the max time made by [`Cooldown::trigger_with_duration`].
+    ///
+    /// If the cooldown was ready, it remains ready.
+    fn restore_max_time(&mut self) {
+        if let Some(max_time) = self.configured_max_time.take() {
+            self.elapsed_time = match self.elapsed_time >= self.max_time {
+                true => max_time,
+                false => self.elapsed_time.min(max_time),
+            };
+            self.max_time = max_time;
+        }
+    }
+
+    /// Refunds `amount` of this cooldown, bringing it closer to being ready.
+    ///
+    /// This is useful for effects like "critical hits reduce this cooldown by 2 seconds".
+    /// The cooldown never recovers past ready: refunding a cooldown that is already ready has no effect.
+    /// Unlike [`tick`](Self::tick), the refund is not scaled by the [`rate`](Self::rate) and does not shorten any active phase.
+    ///
+    /// ```rust
+    /// use bevy::utils::Duration;
+    /// use leafwing_abilities::prelude::Cooldown;
+    ///
+    /// let mut cooldown = Cooldown::from_secs(10.);
+    /// cooldown.trigger().unwrap();
+    ///
+    /// cooldown.refund(Duration::from_secs(2));
+    /// assert_eq!(cooldown.remaining(), Duration::from_secs(8));
+    ///
+    /// cooldown.refund(Duration::from_secs(20));
+    /// assert!(cooldown.ready().is_ok());
+    /// ```
+    #[inline]
+    pub fn refund(&mut self, amount: Duration) {
+        self.add_elapsed(amount);
+    }
+
+    /// Pushes this cooldown back by `amount`, such as for "lock out" effects.
+    ///
+    /// The remaining time never exceeds the [`max_time`](Self::max_time) of this cooldown.
+    /// Cooldowns that are ready are locked out as well.
+    ///
+    /// ```rust
+    /// use bevy::utils::Duration;
+    /// use leafwing_abilities::prelude::Cooldown;
+    ///
+    /// let mut cooldown = Cooldown::from_secs(10.);
+    /// cooldown.add_time(Duration::from_secs(3));
+    /// assert_eq!(cooldown.remaining(), Duration::from_secs(3));
+    ///
+    /// cooldown.add_time(Duration::from_secs(20));
+    /// assert_eq!(cooldown.remaining(), Duration::from_secs(10));
+    /// ```
+    #[inline]
+    pub fn add_time(&mut self, amount: Duration) {
+        self.elapsed_time = self.elapsed_time.saturating_sub(amount);
+    }
+
+    /// Is this action ready to be used?
+    ///
+    /// This will be true if and only if at least one charge is available.
+    /// For cooldowns without charges, this will be true if `time_remaining` is [`Duration::Zero`].
+    pub fn ready(&self) -> Result<(), CannotUseAbility> {
+        match self.elapsed_time >= self.max_time {
+            true => Ok(()),
+            false => Err(CannotUseAbility::OnCooldown {
+                remaining: self.time_until_ready(),
+            }),
+        }
+    }
+
+    /// Returns the time until this cooldown is ready, including any active phase during which it does not advance.
+    ///
+    /// This is the time reported by [`CannotUseAbility::OnCooldown`] when the cooldown is not ready,
+    /// and is [`Duration::ZERO`] once it is.
+    #[inline]
+    #[must_use]
+    pub fn time_until_ready(&self) -> Duration {
+        self.active_remaining.saturating_add(self.remaining())
+    }
+
+    /// Refreshes the cooldown, causing the underlying action to be ready to use immediately.
+    ///
+    /// If this cooldown has charges, the number of available charges is increased by one (but the point within the cycle is unchanged).
+    /// Any active phase is ended.
+    #[inline]
+    pub fn refresh(&mut self) {
+        self.restore_max_time();
+        self.elapsed_time = self.max_time;
+        self.active_remaining = Duration::ZERO;
+    }
+
+    /// Use the underlying cooldown if and only if it is ready, resetting the cooldown to its maximum value.
+    ///
+    /// If this cooldown has multiple charges, only one will be consumed.
+    ///
+    /// Returns a result indicating whether the cooldown was ready.
+    /// If the cooldown was not ready, [`CannotUseAbility::OnCooldown`] is returned and this call has no effect.
+    #[inline]
+    pub fn trigger(&mut self) -> Result<(), CannotUseAbility> {
+        self.ready()?;
+        self.restore_max_time();
+        self.elapsed_time = Duration::ZERO;
+        self.active_remaining = self.active_duration;
+
+        Ok(())
+    }
+
+    /// Triggers the cooldown like [`Cooldown::trigger`], but takes `duration` to recover instead of the [`max_time`](Self::max_time).
+    ///
+    /// The override only lasts for this cycle: once the cooldown is ready again (or is [refreshed](Self::refresh)),
+    /// it reverts to its configured max time.
+    /// This is useful for charge-up abilities, whose cooldown grows with the strength of the shot.
+    ///
+    /// # Panics
+    ///
+    /// The provided duration cannot be [`Duration::ZERO`].
+    ///
+    /// ```rust
+    /// use bevy::utils::Duration;
+    /// use leafwing_abilities::cooldown::Cooldown;
+    ///
+    /// let mut cooldown = Cooldown::from_secs(1.);
+    /// cooldown.trigger_with_duration(Duration::from_secs(3)).unwrap();
+    /// assert_eq!(cooldown.remaining(), Duration::from_secs(3));
+    ///
+    /// cooldown.tick(Duration::from_secs(3), &mut None);
+    /// assert!(cooldown.ready().is_ok());
+    /// assert_eq!(cooldown.max_time(), Duration::from_secs(1));
+    /// ```
+    pub fn trigger_with_duration(&mut self, duration: Duration) -> Result<(), CannotUseAbility> {
+        assert!(duration != Duration::ZERO);
+
+        self.trigger()?;
+        self.configured_max_time = Some(self.max_time);
+        self.max_time = duration;
+        Ok(())
+    }
+
+    /// Returns how long the ability stays active after being triggered, before the cooldown begins.
+    ///
+    /// Set this using [`Cooldown::with_active_duration`].
+    #[inline]
+    pub fn active_duration(&self) -> Duration {
+        self.active_duration
+    }
+
+    /// Returns the time remaining in the current active phase.
+    ///
+    /// This is [`Duration::ZERO`] when the ability is not active.
+    #[inline]
+    pub fn active_remaining(&self) -> Duration {
+        self.active_remaining
+    }
+
+    /// Is the ability currently in its active phase?
+    ///
+    /// The cooldown does not advance while the ability is active.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.active_remaining > Duration::ZERO
+    }
+
+    /// Ends the current active phase early, starting the cooldown immediately.
+    ///
+    /// Has no effect if the ability is not active.
+    #[inline]
+    pub fn deactivate(&mut self) {
+        self.active_remaining = Duration::ZERO;
+    }
+
+    /// Returns the total time that this ability has spent in its active phase.
+    ///
+    /// This accumulates as the cooldown is ticked, and can be cleared with [`Cooldown::reset_uptime`].
+    #[inline]
+    pub fn uptime(&self) -> Duration {
+        self.uptime
+    }
+
+    /// Clears the accumulated [`uptime`](Cooldown::uptime), starting a new measurement period.
+    #[inline]
+    pub fn reset_uptime(&mut self) {
+        self.uptime = Duration::ZERO;
+    }
+
+    /// Returns the rate at which this cooldown recovers, relative to the passage of time.
+    ///
+    /// This is `1.0` by default.
+    #[inline]
+    #[must_use]
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    /// Sets the rate at which this cooldown recovers, relative to the passage of time.
+    ///
+    /// A rate of `2.0` recovers twice as quickly (such as from a haste effect),
+    /// a rate of `0.5` recovers half as quickly, and a rate of `0.0` freezes the cooldown.
+    /// The progress already made is kept, so [`Cooldown::fraction_remaining`] is unaffected by changing the rate.
+    ///
+    /// Negative rates are treated as `0.0`, and non-finite rates are ignored.
+    #[inline]
+    pub fn set_rate(&mut self, rate: f32) {
+        if rate.is_finite() {
+            self.rate = rate.max(0.0);
+        }
+    }
+
+    /// Returns the time that it will take for this action to be ready to use again after being triggered.
+    ///
+    /// While the current cycle was started by [`Cooldown::trigger_with_duration`], this is the overridden duration.
+    #[inline]
+    pub fn max_time(&self) -> Duration {
+        self.max_time
+    }
+
+    /// Is the [`max_time`](Self::max_time) of this cooldown zero, so that it is always ready?
+    ///
+    /// The constructors of [`Cooldown`] reject zero durations,
+    /// but cooldowns loaded through deserialization may still have one.
+    #[inline]
+    #[must_use]
+    pub fn is_instant(&self) -> bool {
+        self.max_time == Duration::ZERO
     }
 
     /// Sets the time that it will take for this action to be ready to use again after being triggered.
     ///
-    /// If the current time remaining is greater than the new max time, it will be clamped to the `max_time`.
+    /// If the current time remaining is greater than the new max time, it will be clamped to the `max_time`.
+    ///
+    /// # Panics
+    ///
+    /// The provided max time cannot be [`Duration::ZERO`].
+    /// Instead, use [`None`] in the [`Cooldowns`] struct for an action without a cooldown.
+    #[inline]
+    pub fn set_max_time(&mut self, max_time: Duration) {
+        assert!(max_time != Duration::ZERO);
+
+        self.configured_max_time = None;
+        self.max_time = max_time;
+        self.elapsed_time = self.elapsed_time.min(max_time);
+    }
+
+    /// Returns the time that has passed since the cooldown was triggered.
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed_time
+    }
+
+    /// Sets the time that has passed since the cooldown was triggered.
+    ///
+    /// This will always be clamped between [`Duration::ZERO`] and the `max_time` of this cooldown.
+    #[inline]
+    pub fn set_elapsed(&mut self, elapsed_time: Duration) {
+        self.elapsed_time = elapsed_time.clamp(Duration::ZERO, self.max_time);
+    }
+
+    /// Returns the time remaining until the next charge is ready.
+    ///
+    /// When a cooldown is fully charged, this will return [`Duration::ZERO`].
+    #[inline]
+    pub fn remaining(&self) -> Duration {
+        self.max_time.saturating_sub(self.elapsed_time)
+    }
+
+    /// Sets the time remaining until the next charge is ready.
+    ///
+    /// This will always be clamped between [`Duration::ZERO`] and the `max_time` of this cooldown.
+    #[inline]
+    pub fn set_remaining(&mut self, time_remaining: Duration) {
+        self.elapsed_time = self
+            .max_time
+            .saturating_sub(time_remaining.clamp(Duration::ZERO, self.max_time));
+    }
+
+    /// Returns the fraction of the cooldown that remains, between `0.0` and `1.0`.
+    ///
+    /// This is `1.0` immediately after the cooldown is triggered, and `0.0` once it is ready again.
+    /// Instant cooldowns, whose max time is zero, are always `0.0`.
+    /// Useful for drawing radial cooldown sweeps.
+    #[inline]
+    #[must_use]
+    pub fn fraction_remaining(&self) -> f32 {
+        if self.max_time == Duration::ZERO {
+            return 0.0;
+        }
+
+        (self.remaining().as_secs_f32() / self.max_time.as_secs_f32()).clamp(0.0, 1.0)
+    }
+}
+
+/// A cooldown that can be used several times in a row, where each use recovers on its own independent timer.
+///
+/// Each of the `max_stacks` stacks is started by a use, and becomes available again exactly `max_time` later.
+/// Unlike [`Charges`](crate::charges::Charges), which recover one at a time on a single shared timer,
+/// rapid uses of a [`StackedCooldown`] queue up several staggered timers that all recover in parallel.
+///
+/// ```rust
+/// use bevy::utils::Duration;
+/// use leafwing_abilities::cooldown::StackedCooldown;
+/// use leafwing_abilities::CannotUseAbility;
+///
+/// let mut cooldown = StackedCooldown::new(Duration::from_secs(3), 2);
+/// cooldown.trigger().unwrap();
+///
+/// cooldown.tick(Duration::from_secs(1));
+/// cooldown.trigger().unwrap();
+/// assert_eq!(
+///     cooldown.ready(),
+///     Err(CannotUseAbility::OnCooldown { remaining: Duration::from_secs(2) })
+/// );
+///
+/// // The first stack recovers three seconds after it was used
+/// cooldown.tick(Duration::from_secs(2));
+/// assert_eq!(cooldown.available(), 1);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct StackedCooldown {
+    /// The independent timer of each stack.
+    ///
+    /// Never empty, which is what allows [`StackedCooldown::max_time`] to read the first stack.
+    #[serde(deserialize_with = "deserialize_stacks")]
+    stacks: Vec<Cooldown>,
+}
+
+/// Deserializes the [`StackedCooldown::stacks`], rejecting an empty list as [`StackedCooldown::new`] does.
+fn deserialize_stacks<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Cooldown>, D::Error> {
+    let stacks = Vec::<Cooldown>::deserialize(deserializer)?;
+    if stacks.is_empty() {
+        return Err(serde::de::Error::invalid_length(0, &"at least one stack"));
+    }
+    Ok(stacks)
+}
+
+impl StackedCooldown {
+    /// Creates a new [`StackedCooldown`] with `max_stacks` stacks, each of which takes `max_time` to recover after it is used.
+    ///
+    /// When initialized, all stacks are available.
+    ///
+    /// # Panics
+    ///
+    /// The provided max time cannot be [`Duration::ZERO`], and there must be at least one stack.
+    pub fn new(max_time: Duration, max_stacks: usize) -> StackedCooldown {
+        assert!(max_stacks > 0);
+
+        StackedCooldown {
+            stacks: vec![Cooldown::new(max_time); max_stacks],
+        }
+    }
+
+    /// Advances the timer of each recovering stack by `delta_time`.
+    pub fn tick(&mut self, delta_time: Duration) {
+        for stack in self.stacks.iter_mut() {
+            stack.tick(delta_time, &mut None);
+        }
+    }
+
+    /// Is this action ready to be used?
+    ///
+    /// This will be true if and only if at least one stack has fully recovered.
+    pub fn ready(&self) -> Result<(), CannotUseAbility> {
+        match self.available() > 0 {
+            true => Ok(()),
+            false => Err(CannotUseAbility::OnCooldown {
+                remaining: self.time_until_ready(),
+            }),
+        }
+    }
+
+    /// Returns the time until the next stack becomes available, including any active phase.
+    ///
+    /// This is [`Duration::ZERO`] when at least one stack is available.
+    #[must_use]
+    pub fn time_until_ready(&self) -> Duration {
+        self.stacks
+            .iter()
+            .map(Cooldown::time_until_ready)
+            .min()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Uses one of the available stacks, starting its timer.
+    ///
+    /// Returns a result indicating whether a stack was available.
+    /// If no stack was available, [`CannotUseAbility::OnCooldown`] is returned and this call has no effect.
+    pub fn trigger(&mut self) -> Result<(), CannotUseAbility> {
+        match self.stacks.iter_mut().find(|stack| stack.ready().is_ok()) {
+            Some(stack) => stack.trigger(),
+            None => Err(CannotUseAbility::OnCooldown {
+                remaining: self.time_until_ready(),
+            }),
+        }
+    }
+
+    /// Refreshes every stack, making all of them available immediately.
+    #[inline]
+    pub fn refresh(&mut self) {
+        for stack in self.stacks.iter_mut() {
+            stack.refresh();
+        }
+    }
+
+    /// Returns the number of stacks that are available to be used.
+    #[inline]
+    #[must_use]
+    pub fn available(&self) -> usize {
+        self.stacks
+            .iter()
+            .filter(|stack| stack.ready().is_ok())
+            .count()
+    }
+
+    /// Returns the total number of stacks.
+    #[inline]
+    #[must_use]
+    pub fn max_stacks(&self) -> usize {
+        self.stacks.len()
+    }
+
+    /// Returns the time that it takes for each stack to recover after being used.
+    #[inline]
+    #[must_use]
+    pub fn max_time(&self) -> Duration {
+        self.stacks[0].max_time()
+    }
+
+    /// Returns the time remaining until the next stack becomes available.
+    ///
+    /// When at least one stack is available, this will return [`Duration::ZERO`].
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> Duration {
+        self.stacks
+            .iter()
+            .map(Cooldown::remaining)
+            .min()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Returns an iterator over the timer of each stack.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Cooldown> {
+        self.stacks.iter()
+    }
+}
+
+/// A single use of an ability, which refills on one recovering timer and is displayed as a filling pip.
+///
+/// This is equivalent to a [`Cooldown`] paired with a single [`Charges`](crate::charges::Charges),
+/// but reconciles both models into one type: the pip is full (and the ability ready) once the timer has elapsed,
+/// and triggering the ability empties it again.
+///
+/// ```rust
+/// use bevy::utils::Duration;
+/// use leafwing_abilities::cooldown::SingleChargeCooldown;
+///
+/// let mut pip = SingleChargeCooldown::new(Duration::from_secs(4));
+/// assert_eq!(pip.fraction_elapsed(), 1.0);
+///
+/// pip.trigger().unwrap();
+/// assert_eq!(pip.fraction_elapsed(), 0.0);
+///
+/// pip.tick(Duration::from_secs(1));
+/// assert_eq!(pip.fraction_elapsed(), 0.25);
+/// assert!(pip.ready().is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SingleChargeCooldown {
+    cooldown: Cooldown,
+}
+
+impl SingleChargeCooldown {
+    /// Creates a new [`SingleChargeCooldown`], which takes `max_time` to refill after it is used.
+    ///
+    /// When initialized, the charge is available.
     ///
     /// # Panics
     ///
     /// The provided max time cannot be [`Duration::ZERO`].
-    /// Instead, use [`None`] in the [`Cooldowns`] struct for an action without a cooldown.
-    #[inline]
-    pub fn set_max_time(&mut self, max_time: Duration) {
-        assert!(max_time != Duration::ZERO);
+    pub fn new(max_time: Duration) -> SingleChargeCooldown {
+        SingleChargeCooldown {
+            cooldown: Cooldown::new(max_time),
+        }
+    }
 
-        self.max_time = max_time;
-        self.elapsed_time = self.elapsed_time.min(max_time);
+    /// Advances the refill timer by `delta_time`.
+    pub fn tick(&mut self, delta_time: Duration) {
+        self.cooldown.tick(delta_time, &mut None);
     }
 
-    /// Returns the time that has passed since the cooldown was triggered.
-    #[inline]
-    pub fn elapsed(&self) -> Duration {
-        self.elapsed_time
+    /// Is the charge available?
+    pub fn ready(&self) -> Result<(), CannotUseAbility> {
+        self.cooldown.ready()
     }
 
-    /// Sets the time that has passed since the cooldown was triggered.
+    /// Spends the charge if it is available, emptying the pip.
     ///
-    /// This will always be clamped between [`Duration::ZERO`] and the `max_time` of this cooldown.
+    /// If the charge was not available, [`CannotUseAbility::OnCooldown`] is returned and this call has no effect.
+    pub fn trigger(&mut self) -> Result<(), CannotUseAbility> {
+        self.cooldown.trigger()
+    }
+
+    /// Refills the charge immediately.
     #[inline]
-    pub fn set_elapsed(&mut self, elapsed_time: Duration) {
-        self.elapsed_time = elapsed_time.clamp(Duration::ZERO, self.max_time);
+    pub fn refresh(&mut self) {
+        self.cooldown.refresh();
     }
 
-    /// Returns the time remaining until the next charge is ready.
+    /// How full is the pip?
     ///
-    /// When a cooldown is fully charged, this will return [`Duration::ZERO`].
+    /// This is `0.0` immediately after the charge is spent, and `1.0` once it is available again.
     #[inline]
+    #[must_use]
+    pub fn fraction_elapsed(&self) -> f32 {
+        self.cooldown.elapsed().as_secs_f32() / self.cooldown.max_time().as_secs_f32()
+    }
+
+    /// Returns the time remaining until the charge is available.
+    #[inline]
+    #[must_use]
     pub fn remaining(&self) -> Duration {
-        self.max_time.saturating_sub(self.elapsed_time)
+        self.cooldown.remaining()
     }
 
-    /// Sets the time remaining until the next charge is ready.
-    ///
-    /// This will always be clamped between [`Duration::ZERO`] and the `max_time` of this cooldown.
+    /// Returns the underlying [`Cooldown`].
     #[inline]
-    pub fn set_remaining(&mut self, time_remaining: Duration) {
-        self.elapsed_time = self
-            .max_time
-            .saturating_sub(time_remaining.clamp(Duration::ZERO, self.max_time));
+    #[must_use]
+    pub fn cooldown(&self) -> &Cooldown {
+        &self.cooldown
     }
 }
 
@@ -425,14 +1934,138 @@ mod tick_tests {
         Cooldown::new(Duration::ZERO);
     }
 
+    #[test]
+    fn zero_duration_cooldowns_are_instant() {
+        use crate as leafwing_abilities;
+        use leafwing_input_manager::Actionlike;
+
+        let instant = Cooldown {
+            max_time: Duration::ZERO,
+            elapsed_time: Duration::ZERO,
+            ..Cooldown::from_secs(1.)
+        };
+        assert!(instant.is_instant());
+        assert!(instant.ready().is_ok());
+        assert!(!Cooldown::from_secs(1.).is_instant());
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy)]
+        enum Action {
+            Blink,
+            Dash,
+            Walk,
+        }
+
+        let cooldowns = CooldownState::new([
+            (Action::Blink, instant),
+            (Action::Dash, Cooldown::from_secs(1.)),
+        ]);
+        assert!(cooldowns.is_instant(Action::Blink));
+        assert!(!cooldowns.is_instant(Action::Dash));
+        assert!(!cooldowns.is_instant(Action::Walk));
+    }
+
     #[test]
     fn tick_has_no_effect_on_fresh_cooldown() {
         let cooldown = Cooldown::from_secs(1.);
-        let mut cloned_cooldown = cooldown.clone();
+        let mut cloned_cooldown = cooldown;
         cloned_cooldown.tick(Duration::from_secs_f32(1.234), &mut None);
         assert_eq!(cooldown, cloned_cooldown);
     }
 
+    #[test]
+    fn day_long_cooldown_is_ready_on_time() {
+        let mut cooldown = Cooldown::new(Duration::from_secs(24 * 60 * 60));
+        cooldown.trigger().unwrap();
+
+        // 5.4 million frames of 16 milliseconds each make up exactly one day
+        let frame = Duration::from_millis(16);
+        for _ in 0..5_399_999 {
+            cooldown.tick(frame, &mut None);
+        }
+        assert_eq!(
+            cooldown.ready(),
+            Err(CannotUseAbility::OnCooldown { remaining: frame })
+        );
+
+        cooldown.tick(frame, &mut None);
+        assert!(cooldown.ready().is_ok());
+    }
+
+    #[test]
+    fn advance_adds_raw_progress() {
+        let mut cooldown = Cooldown::from_secs(3.);
+        cooldown.trigger().unwrap();
+
+        cooldown.advance(1.5);
+        assert_eq!(cooldown.remaining(), Duration::from_secs_f32(1.5));
+
+        // Invalid amounts are ignored
+        cooldown.advance(-1.);
+        cooldown.advance(f32::NAN);
+        assert_eq!(cooldown.remaining(), Duration::from_secs_f32(1.5));
+
+        cooldown.advance(100.);
+        assert!(cooldown.ready().is_ok());
+        assert_eq!(cooldown.elapsed(), cooldown.max_time());
+
+        // Amounts too large for a Duration saturate instead of panicking
+        for amount in [f32::MAX, f32::INFINITY] {
+            cooldown.trigger().unwrap();
+            cooldown.advance(amount);
+            assert!(cooldown.ready().is_ok());
+        }
+    }
+
+    #[test]
+    fn fraction_remaining_tracks_progress() {
+        let mut cooldown = Cooldown::from_secs(4.);
+        assert_eq!(cooldown.fraction_remaining(), 0.0);
+
+        cooldown.trigger().unwrap();
+        assert_eq!(cooldown.fraction_remaining(), 1.0);
+
+        cooldown.tick(Duration::from_secs(1), &mut None);
+        assert_eq!(cooldown.fraction_remaining(), 0.75);
+        assert_eq!(cooldown.elapsed(), Duration::from_secs(1));
+        assert_eq!(cooldown.remaining(), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn instant_cooldowns_have_nothing_remaining() {
+        // Only reachable through deserialization, as the constructors reject zero durations
+        let mut cooldown = Cooldown::from_secs(1.);
+        cooldown.max_time = Duration::ZERO;
+        assert_eq!(cooldown.fraction_remaining(), 0.0);
+
+        cooldown.elapsed_time = Duration::ZERO;
+        assert_eq!(cooldown.fraction_remaining(), 0.0);
+    }
+
+    #[test]
+    fn rate_scales_recovery() {
+        let mut cooldown = Cooldown::from_secs(4.);
+        cooldown.trigger().unwrap();
+
+        cooldown.set_rate(2.0);
+        cooldown.tick(Duration::from_secs(1), &mut None);
+        assert_eq!(cooldown.fraction_remaining(), 0.5);
+
+        // Changing the rate mid-cooldown keeps the progress made so far
+        cooldown.set_rate(0.0);
+        assert_eq!(cooldown.fraction_remaining(), 0.5);
+        cooldown.tick(Duration::from_secs(100), &mut None);
+        assert_eq!(cooldown.fraction_remaining(), 0.5);
+
+        cooldown.set_rate(-1.0);
+        assert_eq!(cooldown.rate(), 0.0);
+        cooldown.set_rate(f32::NAN);
+        assert_eq!(cooldown.rate(), 0.0);
+
+        cooldown.set_rate(0.5);
+        cooldown.tick(Duration::from_secs(2), &mut None);
+        assert_eq!(cooldown.fraction_remaining(), 0.25);
+    }
+
     #[test]
     fn cooldowns_start_ready() {
         let cooldown = Cooldown::from_secs(1.);
@@ -444,7 +2077,12 @@ mod tick_tests {
         let mut cooldown = Cooldown::from_secs(1.);
         assert!(cooldown.ready().is_ok());
         let _ = cooldown.trigger();
-        assert_eq!(cooldown.ready(), Err(CannotUseAbility::OnCooldown));
+        assert_eq!(
+            cooldown.ready(),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(1)
+            })
+        );
         cooldown.refresh();
         assert!(cooldown.ready().is_ok());
     }
@@ -452,7 +2090,7 @@ mod tick_tests {
     #[test]
     fn ticking_changes_cooldown() {
         let cooldown = Cooldown::new(Duration::from_millis(1000));
-        let mut cloned_cooldown = cooldown.clone();
+        let mut cloned_cooldown = cooldown;
         let _ = cloned_cooldown.trigger();
         assert!(cooldown != cloned_cooldown);
 
@@ -464,15 +2102,534 @@ mod tick_tests {
     fn cooldowns_reset_after_being_ticked() {
         let mut cooldown = Cooldown::from_secs(1.);
         let _ = cooldown.trigger();
-        assert_eq!(cooldown.ready(), Err(CannotUseAbility::OnCooldown));
+        assert_eq!(
+            cooldown.ready(),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(1)
+            })
+        );
 
         cooldown.tick(Duration::from_secs(3), &mut None);
         assert!(cooldown.ready().is_ok());
     }
 
+    #[test]
+    fn cooldown_reduction_halves_cooldown_time() {
+        let reduction = CooldownReduction::new(0.5);
+        let mut cooldown = Cooldown::from_secs(2.);
+        cooldown.trigger().unwrap();
+
+        cooldown.tick(reduction.scale(Duration::from_millis(900)), &mut None);
+        assert_eq!(
+            cooldown.ready(),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_millis(200)
+            })
+        );
+
+        cooldown.tick(reduction.scale(Duration::from_millis(100)), &mut None);
+        assert!(cooldown.ready().is_ok());
+    }
+
+    #[test]
+    fn active_duration_delays_cooldown() {
+        let mut cooldown = Cooldown::from_secs(2.).with_active_duration(Duration::from_secs(1));
+        cooldown.trigger().unwrap();
+
+        // Active
+        cooldown.tick(Duration::from_millis(500), &mut None);
+        assert!(cooldown.is_active());
+        assert_eq!(cooldown.active_remaining(), Duration::from_millis(500));
+        assert_eq!(cooldown.remaining(), Duration::from_secs(2));
+        assert_eq!(
+            cooldown.ready(),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_millis(2500)
+            })
+        );
+
+        // Cooling
+        cooldown.tick(Duration::from_millis(1000), &mut None);
+        assert!(!cooldown.is_active());
+        assert_eq!(cooldown.remaining(), Duration::from_millis(1500));
+        assert_eq!(
+            cooldown.ready(),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_millis(1500)
+            })
+        );
+
+        // Ready
+        cooldown.tick(Duration::from_millis(1500), &mut None);
+        assert!(!cooldown.is_active());
+        assert!(cooldown.ready().is_ok());
+    }
+
+    #[test]
+    fn cooldown_constructors_agree() {
+        let cooldown = Cooldown::from_millis(1500);
+        assert_eq!(cooldown, Cooldown::new(Duration::from_millis(1500)));
+        assert_eq!(cooldown, Cooldown::from_secs(1.5));
+        assert_eq!(cooldown.max_time(), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn cooldowns_can_start_ready_or_on_cooldown() {
+        assert!(Cooldown::from_secs(1.).ready().is_ok());
+
+        let mut cooldown = Cooldown::from_secs(1.).starting_on_cooldown();
+        assert_eq!(
+            cooldown.ready(),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(1)
+            })
+        );
+
+        cooldown.tick(Duration::from_secs(1), &mut None);
+        assert!(cooldown.ready().is_ok());
+    }
+
     #[test]
     fn time_remaining_on_fresh_cooldown_is_zero() {
         let cooldown = Cooldown::from_secs(1.);
         assert_eq!(cooldown.remaining(), Duration::ZERO);
     }
+
+    #[test]
+    fn stacked_cooldowns_recover_independently() {
+        let mut cooldown = StackedCooldown::new(Duration::from_secs(3), 3);
+        assert_eq!(cooldown.available(), 3);
+
+        // Three staggered uses, half a second apart
+        cooldown.trigger().unwrap();
+        cooldown.tick(Duration::from_millis(500));
+        cooldown.trigger().unwrap();
+        cooldown.tick(Duration::from_millis(500));
+        cooldown.trigger().unwrap();
+        assert_eq!(
+            cooldown.ready(),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(2)
+            })
+        );
+        assert_eq!(
+            cooldown.trigger(),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(2)
+            })
+        );
+
+        // t = 3s: only the first use has recovered
+        cooldown.tick(Duration::from_secs(2));
+        assert_eq!(cooldown.available(), 1);
+        assert_eq!(cooldown.remaining(), Duration::ZERO);
+
+        // t = 3.5s: the second use recovers on its own timer
+        cooldown.tick(Duration::from_millis(500));
+        assert_eq!(cooldown.available(), 2);
+
+        // t = 4s: all uses have recovered
+        cooldown.tick(Duration::from_millis(500));
+        assert_eq!(cooldown.available(), 3);
+    }
+
+    #[test]
+    fn single_charge_cooldown_fills_like_a_pip() {
+        let mut pip = SingleChargeCooldown::new(Duration::from_secs(2));
+        assert!(pip.ready().is_ok());
+        assert_eq!(pip.fraction_elapsed(), 1.0);
+
+        pip.trigger().unwrap();
+        assert_eq!(pip.fraction_elapsed(), 0.0);
+        assert_eq!(
+            pip.trigger(),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(2)
+            })
+        );
+
+        pip.tick(Duration::from_millis(500));
+        assert_eq!(pip.fraction_elapsed(), 0.25);
+        assert_eq!(
+            pip.ready(),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_millis(1500)
+            })
+        );
+
+        pip.tick(Duration::from_millis(1500));
+        assert_eq!(pip.fraction_elapsed(), 1.0);
+        assert!(pip.ready().is_ok());
+
+        // Triggering resets the fill
+        pip.trigger().unwrap();
+        assert_eq!(pip.fraction_elapsed(), 0.0);
+    }
+
+    #[test]
+    fn uptime_accumulates_during_active_phase() {
+        use crate as leafwing_abilities;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy)]
+        enum Action {
+            Shield,
+        }
+
+        let mut cooldowns = CooldownState::new([(
+            Action::Shield,
+            Cooldown::from_secs(1.).with_active_duration(Duration::from_secs(3)),
+        )]);
+        cooldowns.trigger(Action::Shield).unwrap();
+
+        for _ in 0..10 {
+            cooldowns.tick(Duration::from_secs(1), None);
+        }
+
+        assert_eq!(cooldowns.uptime(Action::Shield), Duration::from_secs(3));
+        assert_eq!(
+            cooldowns.uptime_fraction(Action::Shield, Duration::from_secs(10)),
+            0.3
+        );
+
+        cooldowns
+            .get_mut(Action::Shield)
+            .as_mut()
+            .unwrap()
+            .reset_uptime();
+        assert_eq!(cooldowns.uptime(Action::Shield), Duration::ZERO);
+    }
+
+    #[test]
+    fn migration_preserves_surviving_actions() {
+        use crate as leafwing_abilities;
+        use bevy::reflect::Reflect;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Reflect, Clone, Copy)]
+        enum OldAction {
+            Jump,
+            Dash,
+        }
+
+        #[derive(Actionlike, Abilitylike, Reflect, Clone, Copy)]
+        enum NewAction {
+            Dash,
+            Fireball,
+        }
+
+        let mut old = CooldownState::new([
+            (OldAction::Jump, Cooldown::from_secs(1.)),
+            (OldAction::Dash, Cooldown::from_secs(2.)),
+        ]);
+        old.trigger(OldAction::Dash).unwrap();
+
+        let defaults = CooldownState::new([
+            (NewAction::Dash, Cooldown::from_secs(10.)),
+            (NewAction::Fireball, Cooldown::from_secs(3.)),
+        ]);
+
+        let migrated = CooldownState::migrate_from(&old, &defaults);
+
+        // Dash survived, so its in-progress cooldown is kept
+        assert_eq!(migrated.get(NewAction::Dash), old.get(OldAction::Dash));
+        assert_eq!(
+            migrated.ready(NewAction::Dash),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(2)
+            })
+        );
+        // Fireball was added, and uses the default
+        assert_eq!(
+            migrated.get(NewAction::Fireball),
+            &Some(Cooldown::from_secs(3.))
+        );
+        // Jump was removed
+        assert_eq!(migrated.iter().count(), 2);
+    }
+
+    #[test]
+    fn tick_one_only_affects_one_action() {
+        use crate as leafwing_abilities;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy)]
+        enum Action {
+            Jump,
+            Dash,
+        }
+
+        let mut cooldowns = CooldownState::new([
+            (Action::Jump, Cooldown::from_secs(2.)),
+            (Action::Dash, Cooldown::from_secs(2.)),
+        ]);
+        cooldowns.trigger(Action::Jump).unwrap();
+        cooldowns.trigger(Action::Dash).unwrap();
+        let mut global_cooldown = Cooldown::from_secs(2.);
+        global_cooldown.trigger().unwrap();
+        cooldowns.global_cooldown = Some(global_cooldown);
+
+        cooldowns.tick_one(Action::Jump, Duration::from_secs(1), None);
+
+        let jump = cooldowns.get(Action::Jump).unwrap();
+        assert_eq!(jump.remaining(), Duration::from_secs(1));
+        let dash = cooldowns.get(Action::Dash).unwrap();
+        assert_eq!(dash.remaining(), Duration::from_secs(2));
+        let gcd = cooldowns.global_cooldown.unwrap();
+        assert_eq!(gcd.remaining(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn actions_without_cooldowns_can_always_be_triggered() {
+        use crate as leafwing_abilities;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy)]
+        enum Action {
+            Walk,
+        }
+
+        let mut cooldowns = CooldownState::<Action>::default();
+        for _ in 0..3 {
+            assert_eq!(cooldowns.trigger(Action::Walk), Ok(()));
+        }
+
+        // Only the global cooldown can block them
+        cooldowns.set_global_cooldown(Cooldown::from_secs(1.));
+        assert_eq!(cooldowns.trigger(Action::Walk), Ok(()));
+        assert_eq!(
+            cooldowns.trigger(Action::Walk),
+            Err(CannotUseAbility::OnGlobalCooldown {
+                remaining: Duration::from_secs(1)
+            })
+        );
+    }
+
+    #[test]
+    fn is_ready_only_checks_the_actions_own_cooldown() {
+        use crate as leafwing_abilities;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy)]
+        enum Action {
+            Walk,
+            Shoot,
+        }
+
+        let mut cooldowns = CooldownState::new([(Action::Shoot, Cooldown::from_secs(2.))]);
+        cooldowns.set_global_cooldown(Cooldown::from_secs(1.));
+        cooldowns.trigger(Action::Shoot).unwrap();
+
+        assert!(!cooldowns.is_ready(Action::Shoot));
+        // Walk is blocked by the global cooldown, but its own cooldown is ready
+        assert!(cooldowns.ready(Action::Walk).is_err());
+        assert!(cooldowns.is_ready(Action::Walk));
+
+        cooldowns.tick(Duration::from_secs(2), None);
+        assert!(cooldowns.is_ready(Action::Shoot));
+    }
+
+    #[test]
+    fn cooldown_states_can_be_collected() {
+        use crate as leafwing_abilities;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy, PartialEq, Debug)]
+        enum Action {
+            Jump,
+            Dash,
+        }
+
+        let collected: CooldownState<Action> = [(Action::Dash, Cooldown::from_secs(2.))]
+            .into_iter()
+            .collect();
+        let built = CooldownState::new_with(|action| match action {
+            Action::Jump => None,
+            Action::Dash => Some(Cooldown::from_secs(2.)),
+        });
+
+        assert_eq!(collected, built);
+        assert!(built.get(Action::Jump).is_none());
+    }
+
+    #[test]
+    fn reset_and_refresh_all_preserve_durations() {
+        use crate as leafwing_abilities;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy)]
+        enum Action {
+            Jump,
+            Dash,
+        }
+
+        let mut cooldowns = CooldownState::new([
+            (Action::Jump, Cooldown::from_secs(2.)),
+            (Action::Dash, Cooldown::from_secs(3.)),
+        ]);
+
+        cooldowns.refresh_all();
+        assert_eq!(
+            cooldowns.remaining(Action::Jump),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            cooldowns.remaining(Action::Dash),
+            Some(Duration::from_secs(3))
+        );
+
+        cooldowns.reset(Action::Jump);
+        assert!(cooldowns.get(Action::Jump).unwrap().ready().is_ok());
+        assert!(cooldowns.get(Action::Dash).unwrap().ready().is_err());
+        assert_eq!(
+            cooldowns.get(Action::Jump).unwrap().max_time(),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn restoring_a_snapshot_resimulates_identically() {
+        use crate as leafwing_abilities;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy, PartialEq, Debug)]
+        enum Action {
+            Jump,
+            Dash,
+        }
+
+        let mut cooldowns = CooldownState::new([
+            (Action::Jump, Cooldown::from_secs(2.)),
+            (Action::Dash, Cooldown::from_secs(3.)),
+        ]);
+        cooldowns.set_global_cooldown(Cooldown::from_secs(0.5));
+        cooldowns.set_global_rate(1.3);
+        cooldowns.trigger(Action::Jump).unwrap();
+        cooldowns.tick(Duration::from_millis(16), None);
+
+        let snapshot = cooldowns.snapshot();
+        let simulate = |cooldowns: &mut CooldownState<Action>| {
+            cooldowns.tick(Duration::from_millis(16), None);
+            let _ = cooldowns.trigger(Action::Dash);
+            cooldowns.tick(Duration::from_millis(600), None);
+        };
+
+        simulate(&mut cooldowns);
+        let first_run = cooldowns.clone();
+        assert_ne!(first_run, snapshot);
+
+        cooldowns.restore(&snapshot);
+        assert_eq!(cooldowns, snapshot);
+        simulate(&mut cooldowns);
+        assert_eq!(cooldowns, first_run);
+    }
+
+    #[test]
+    fn cooldown_groups_block_every_member() {
+        use crate as leafwing_abilities;
+        use crate::charges::ChargeState;
+        use crate::NullPool;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy, PartialEq, Debug)]
+        enum Item {
+            HealthPotion,
+            ManaPotion,
+            Bomb,
+        }
+
+        let mut cooldowns = CooldownState::new([(Item::HealthPotion, Cooldown::from_secs(1.))]);
+        cooldowns.set_global_cooldown(Cooldown::from_secs(0.5));
+        cooldowns.set_ignores_global_cooldown(Item::ManaPotion, true);
+        let potions = cooldowns.set_group(
+            [Item::HealthPotion, Item::ManaPotion],
+            Cooldown::from_secs(30.),
+        );
+        assert_eq!(cooldowns.group(Item::ManaPotion), Some(potions));
+        assert_eq!(cooldowns.group(Item::Bomb), None);
+
+        let mut charges = ChargeState::default();
+        Item::HealthPotion
+            .trigger::<NullPool>(&mut charges, &mut cooldowns, None, None)
+            .unwrap();
+
+        // The group blocks its members independently of their own cooldowns and the global cooldown
+        cooldowns.tick(Duration::from_secs(10), None);
+        assert_eq!(
+            cooldowns.ready(Item::ManaPotion),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(20)
+            })
+        );
+        assert_eq!(
+            Item::HealthPotion.ready::<NullPool>(&charges, &cooldowns, None, None),
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(20)
+            })
+        );
+        assert!(cooldowns.ready(Item::Bomb).is_ok());
+
+        cooldowns.tick(Duration::from_secs(20), None);
+        cooldowns.trigger(Item::ManaPotion).unwrap();
+        assert!(cooldowns.ready(Item::HealthPotion).is_err());
+
+        cooldowns.reset_all();
+        assert!(cooldowns.ready(Item::HealthPotion).is_ok());
+
+        // Refunds and refreshes reach the group cooldown too
+        cooldowns.refresh_all();
+        assert_eq!(
+            cooldowns.group_cooldown(potions).remaining(),
+            Duration::from_secs(30)
+        );
+        assert!(cooldowns.ready(Item::ManaPotion).is_err());
+
+        cooldowns.refund(Item::HealthPotion, Duration::from_secs(30));
+        assert!(cooldowns.group_cooldown(potions).ready().is_ok());
+        assert!(cooldowns.ready(Item::ManaPotion).is_ok());
+    }
+
+    #[test]
+    fn duration_override_lasts_one_cycle() {
+        use crate as leafwing_abilities;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy, PartialEq, Debug)]
+        enum Action {
+            ChargedShot,
+        }
+
+        let mut cooldowns = CooldownState::new([(Action::ChargedShot, Cooldown::from_secs(1.))]);
+        cooldowns
+            .trigger_with_duration(Action::ChargedShot, Duration::from_secs(4))
+            .unwrap();
+        assert_eq!(
+            cooldowns.remaining(Action::ChargedShot),
+            Some(Duration::from_secs(4))
+        );
+
+        cooldowns.tick(Duration::from_secs(3), None);
+        assert!(cooldowns.ready(Action::ChargedShot).is_err());
+        cooldowns.tick(Duration::from_secs(1), None);
+        assert!(cooldowns.ready(Action::ChargedShot).is_ok());
+
+        // The next trigger uses the configured duration again
+        cooldowns.trigger(Action::ChargedShot).unwrap();
+        assert_eq!(
+            cooldowns.remaining(Action::ChargedShot),
+            Some(Duration::from_secs(1))
+        );
+
+        // Recharging charges also reverts to the configured duration after the overridden cycle
+        let mut charges = Some(Charges::replenish_one(2));
+        let mut cooldown = Cooldown::from_secs(1.);
+        charges.as_mut().unwrap().expend().unwrap();
+        charges.as_mut().unwrap().expend().unwrap();
+        cooldown
+            .trigger_with_duration(Duration::from_secs(2))
+            .unwrap();
+        cooldown.tick(Duration::from_secs(2), &mut charges);
+        assert_eq!(charges.unwrap().charges(), 1);
+        assert_eq!(cooldown.remaining(), Duration::from_secs(1));
+    }
 }
@@ -0,0 +1,124 @@
+//! Reports metrics about the abilities of type `A` through Bevy's [`Diagnostics`].
+//!
+//! This is useful when profiling ability-heavy scenes: add the [`AbilityDiagnosticsPlugin`]
+//! alongside a diagnostics consumer, such as [`LogDiagnosticsPlugin`](bevy::diagnostic::LogDiagnosticsPlugin).
+
+use crate::recast::{CastKind, RecastExpired};
+use crate::triggers::AbilityTriggered;
+use crate::Abilitylike;
+
+use bevy::app::{App, CoreSet, Plugin};
+use bevy::diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy::ecs::prelude::*;
+use core::marker::PhantomData;
+
+/// A [`Plugin`] that records diagnostics about the abilities of type `A` each frame.
+///
+/// The following measurements are recorded during [`CoreSet::Last`]:
+/// - [`ABILITIES_TICKED`](Self::ABILITIES_TICKED): the number of [`CooldownState`](crate::cooldown::CooldownState) and [`ChargeState`](crate::charges::ChargeState) components
+///   (and resources) that were ticked by the [`tick_cooldowns`](crate::systems::tick_cooldowns) system, as counted in [`AbilityCounters`].
+///   Entities whose cooldowns are [paused](crate::cooldown::CooldownsPaused) are not ticked, and so are not counted.
+/// - [`ABILITIES_TRIGGERED`](Self::ABILITIES_TRIGGERED): the number of [`AbilityTriggered`] events sent this frame for initial casts,
+///   including uses of abilities that only spend [`Charges`](crate::charges::Charges).
+///   Only entities with a [`TriggeredAbilities`](crate::triggers::TriggeredAbilities) component send these events.
+/// - [`RECASTS_EXPIRED`](Self::RECASTS_EXPIRED): the number of [`RecastExpired`] events sent this frame.
+///
+/// The diagnostic ids are shared between all ability types, so this plugin should only be added for a single type `A`.
+pub struct AbilityDiagnosticsPlugin<A: Abilitylike> {
+    _phantom: PhantomData<A>,
+}
+
+// Deriving default induces an undesired bound on the generic
+impl<A: Abilitylike> Default for AbilityDiagnosticsPlugin<A> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Abilitylike> Plugin for AbilityDiagnosticsPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Diagnostics>()
+            .init_resource::<AbilityCounters<A>>()
+            .add_event::<RecastExpired<A>>()
+            .add_event::<AbilityTriggered<A>>()
+            .add_startup_system(Self::setup_system)
+            .add_system(
+                Self::diagnostic_system
+                    .in_base_set(CoreSet::Last)
+                    .after(crate::systems::send_ability_triggered::<A>),
+            );
+    }
+}
+
+impl<A: Abilitylike> AbilityDiagnosticsPlugin<A> {
+    /// The number of ability states ticked each frame.
+    pub const ABILITIES_TICKED: DiagnosticId =
+        DiagnosticId::from_u128(232204620659440351930335768480626898971);
+    /// The number of abilities triggered each frame.
+    pub const ABILITIES_TRIGGERED: DiagnosticId =
+        DiagnosticId::from_u128(84226706622798127624217310288460822411);
+    /// The number of recast windows that expired each frame.
+    pub const RECASTS_EXPIRED: DiagnosticId =
+        DiagnosticId::from_u128(316802054136695598158914815904385615502);
+
+    /// Registers the diagnostics recorded by this plugin.
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(
+            Self::ABILITIES_TICKED,
+            "abilities_ticked",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::ABILITIES_TRIGGERED,
+            "abilities_triggered",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::RECASTS_EXPIRED,
+            "recasts_expired",
+            20,
+        ));
+    }
+
+    /// Records the diagnostics for this frame, and resets the [`AbilityCounters`].
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        mut counters: ResMut<AbilityCounters<A>>,
+        mut ability_triggered: EventReader<AbilityTriggered<A>>,
+        mut recast_expired: EventReader<RecastExpired<A>>,
+    ) {
+        let abilities_ticked = std::mem::take(&mut counters.abilities_ticked);
+        let abilities_triggered = ability_triggered
+            .iter()
+            .filter(|event| event.cast_kind == CastKind::Initial)
+            .count();
+
+        diagnostics.add_measurement(Self::ABILITIES_TICKED, || abilities_ticked as f64);
+        diagnostics.add_measurement(Self::ABILITIES_TRIGGERED, || abilities_triggered as f64);
+        diagnostics.add_measurement(Self::RECASTS_EXPIRED, || {
+            recast_expired.iter().count() as f64
+        });
+    }
+}
+
+/// Counts of ability work performed by the systems of this crate, since they were last recorded by the [`AbilityDiagnosticsPlugin`].
+///
+/// Inserted by the [`AbilityDiagnosticsPlugin`]. If this resource is missing, nothing is counted.
+#[derive(Resource, Debug)]
+pub struct AbilityCounters<A: Abilitylike> {
+    /// The number of ability states ticked by the [`tick_cooldowns`](crate::systems::tick_cooldowns) system.
+    pub abilities_ticked: usize,
+    _phantom: PhantomData<A>,
+}
+
+// Deriving default induces an undesired bound on the generic
+impl<A: Abilitylike> Default for AbilityCounters<A> {
+    fn default() -> Self {
+        Self {
+            abilities_ticked: 0,
+            _phantom: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Crowd-control effects, such as silences and disarms, that stop abilities from being used at all.
+//!
+//! Add a [`DisabledAbilities`] component to an entity to block specific abilities,
+//! without touching their cooldowns, charges or costs.
+//! Once an ability is enabled again, it resumes exactly where it left off.
+
+use bevy::ecs::prelude::{Component, Resource};
+use std::marker::PhantomData;
+
+use crate::{Abilitylike, CannotUseAbility};
+
+/// The abilities of type `A` that are currently disabled for this entity, such as by a silence.
+///
+/// The methods of [`AbilityState`](crate::AbilityState) return [`CannotUseAbility::Disabled`] for disabled abilities,
+/// before any cooldown, charge or cost is checked. Their cooldowns and charges keep ticking as usual.
+///
+/// ```rust
+/// use leafwing_abilities::prelude::*;
+/// use leafwing_input_manager::Actionlike;
+///
+/// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+/// enum Action {
+///     Fireball,
+///     Slash,
+/// }
+///
+/// // Silences block spells, but not attacks
+/// let mut disabled = DisabledAbilities::default();
+/// disabled.disable(Action::Fireball);
+/// assert_eq!(disabled.check(Action::Fireball), Err(CannotUseAbility::Disabled));
+/// assert!(disabled.check(Action::Slash).is_ok());
+///
+/// // The silence wears off
+/// disabled.enable(Action::Fireball);
+/// assert!(disabled.check(Action::Fireball).is_ok());
+/// ```
+#[derive(Resource, Component, Clone, Debug, PartialEq, Eq)]
+pub struct DisabledAbilities<A: Abilitylike> {
+    /// Whether each action is disabled, stored in [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) order.
+    disabled_vec: Vec<bool>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Abilitylike> Default for DisabledAbilities<A> {
+    fn default() -> Self {
+        DisabledAbilities {
+            disabled_vec: A::variants().map(|_| false).collect(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Abilitylike> DisabledAbilities<A> {
+    /// Creates a new [`DisabledAbilities`] where each of the `actions` is disabled.
+    #[must_use]
+    pub fn new(actions: impl IntoIterator<Item = A>) -> Self {
+        let mut disabled = DisabledAbilities::default();
+        for action in actions {
+            disabled.disable(action);
+        }
+
+        disabled
+    }
+
+    /// Disables `action`, so that it cannot be used until it is [enabled](Self::enable) again.
+    #[inline]
+    pub fn disable(&mut self, action: A) {
+        self.disabled_vec[action.index()] = true;
+    }
+
+    /// Enables `action` again, so that it can be used as normal.
+    ///
+    /// If `action` was not disabled, this has no effect.
+    #[inline]
+    pub fn enable(&mut self, action: A) {
+        self.disabled_vec[action.index()] = false;
+    }
+
+    /// Enables every action again.
+    #[inline]
+    pub fn enable_all(&mut self) {
+        self.disabled_vec.fill(false);
+    }
+
+    /// Is `action` currently disabled?
+    #[inline]
+    #[must_use]
+    pub fn is_disabled(&self, action: A) -> bool {
+        self.disabled_vec[action.index()]
+    }
+
+    /// Returns [`CannotUseAbility::Disabled`] if `action` is currently disabled.
+    #[inline]
+    pub fn check(&self, action: A) -> Result<(), CannotUseAbility> {
+        match self.is_disabled(action) {
+            true => Err(CannotUseAbility::Disabled),
+            false => Ok(()),
+        }
+    }
+
+    /// Returns an iterator over the actions that are currently disabled, in [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) order.
+    pub fn iter(&self) -> impl Iterator<Item = A> + '_ {
+        A::variants().filter(|action| self.is_disabled(action.clone()))
+    }
+}
@@ -5,20 +5,27 @@
 
 use crate::cooldown::CooldownState;
 use bevy::ecs::prelude::*;
-use charges::{ChargeState, Charges};
+use bevy::utils::Duration;
+use charges::{ChargeState, Charges, CooldownStrategy};
 use cooldown::Cooldown;
 use leafwing_input_manager::Actionlike;
 use pool::{AbilityCosts, Pool};
 use thiserror::Error;
 
 mod ability_state;
+pub mod cast_time;
+pub mod channel;
 pub mod charges;
 pub mod cooldown;
+pub mod diagnostics;
+pub mod disabled;
 pub mod plugin;
 pub mod pool;
 #[cfg(feature = "premade_pools")]
 pub mod premade_pools;
+pub mod recast;
 pub mod systems;
+pub mod triggers;
 pub use ability_state::*;
 
 // Importing the derive macro
@@ -26,11 +33,17 @@ pub use leafwing_abilities_macros::Abilitylike;
 
 /// Everything you need to get started
 pub mod prelude {
+    pub use crate::channel::{Channel, ChannelState};
     pub use crate::charges::{ChargeState, Charges};
-    pub use crate::cooldown::{Cooldown, CooldownState};
-    pub use crate::pool::{AbilityCosts, Pool, PoolBundle};
-
-    pub use crate::plugin::AbilityPlugin;
+    pub use crate::cooldown::{Cooldown, CooldownGroup, CooldownState, CooldownsPaused};
+    pub use crate::disabled::DisabledAbilities;
+    pub use crate::pool::{
+        AbilityCosts, CooldownResetOnPoolFull, OverflowPolicy, Pool, PoolBundle, PoolModifiers,
+        RegenGate, RegenMultiplier, UndamagedRequirements,
+    };
+    pub use crate::recast::{Recast, RecastState};
+
+    pub use crate::plugin::{AbilityPlugin, PoolPlugin};
     pub use crate::CannotUseAbility;
     pub use crate::{AbilitiesBundle, AbilityState, Abilitylike};
 }
@@ -67,12 +80,52 @@ pub mod prelude {
 /// }
 /// ```
 pub trait Abilitylike: Actionlike {
+    /// The [`Cooldown`] that this ability starts with, before any runtime modifiers are applied.
+    ///
+    /// Returns [`None`] by default, meaning that the ability has no cooldown.
+    /// To define per-ability cooldowns in one place, implement this trait by hand rather than deriving it,
+    /// and build the matching [`CooldownState`] with [`CooldownState::from_abilitylike`].
+    ///
+    /// ```rust
+    /// use leafwing_abilities::prelude::*;
+    /// use leafwing_input_manager::Actionlike;
+    ///
+    /// #[derive(Actionlike, Clone, Copy)]
+    /// enum Action {
+    ///     Jump,
+    ///     Fireball,
+    /// }
+    ///
+    /// impl Abilitylike for Action {
+    ///     fn base_cooldown(&self) -> Option<Cooldown> {
+    ///         match self {
+    ///             Action::Jump => None,
+    ///             Action::Fireball => Some(Cooldown::from_secs(3.)),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let cooldowns = CooldownState::<Action>::from_abilitylike();
+    /// assert!(cooldowns.get(Action::Jump).is_none());
+    /// assert!(cooldowns.get(Action::Fireball).is_some());
+    /// ```
+    #[inline]
+    #[must_use]
+    fn base_cooldown(&self) -> Option<Cooldown> {
+        None
+    }
+
     /// Is this ability ready?
     ///
     /// If this ability has charges, at least one charge must be available.
     /// If this ability has a cooldown but no charges, the cooldown must be ready.
+    /// If it has both, the cooldown only needs to be ready when the charges use [`CooldownStrategy::RateLimit`].
     /// Otherwise, returns [`Ok(())`].
     ///
+    /// If this ability belongs to a [cooldown group](CooldownState::set_group), the group's cooldown must be ready.
+    /// Unless this ability [ignores the global cooldown](CooldownState::ignores_global_cooldown),
+    /// the global cooldown must also be ready.
+    ///
     /// Calls [`ability_ready`], which can be used manually if you already know the [`Charges`] and [`Cooldown`] of interest.
     fn ready<P: Pool>(
         &self,
@@ -88,7 +141,14 @@ pub trait Abilitylike: Actionlike {
             None => &None,
         };
 
-        ability_ready(charges, cooldown, maybe_pool, maybe_cost)
+        ability_ready(charges, cooldown, maybe_pool, maybe_cost)?;
+        cooldowns.group_ready(self.clone())?;
+
+        if cooldowns.ignores_global_cooldown(self.clone()) {
+            Ok(())
+        } else {
+            cooldowns.gcd_ready()
+        }
     }
 
     /// Triggers this ability, depleting a charge if available.
@@ -96,6 +156,10 @@ pub trait Abilitylike: Actionlike {
     /// Returns `true` if the ability could be used, and `false` if it could not be.
     /// Abilities can only be used if they are ready.
     ///     
+    /// Unless this ability [ignores the global cooldown](CooldownState::ignores_global_cooldown),
+    /// it cannot be used while the global cooldown is running, and triggers the global cooldown on success.
+    /// The same applies to the cooldown of its [cooldown group](CooldownState::set_group), if any.
+    ///
     /// Calls [`trigger_ability`], which can be used manually if you already know the [`Charges`] and [`Cooldown`] of interest.
     fn trigger<P: Pool>(
         &self,
@@ -104,14 +168,88 @@ pub trait Abilitylike: Actionlike {
         maybe_pool: Option<&mut P>,
         maybe_costs: Option<&AbilityCosts<Self, P>>,
     ) -> Result<(), CannotUseAbility> {
-        let charges = charges.get_mut(self.clone());
+        // Check everything up front, so that failed triggers have no effect
+        self.ready(&*charges, &*cooldowns, maybe_pool.as_deref(), maybe_costs)?;
+
+        let action_charges = charges.get_mut(self.clone());
         let cooldown = cooldowns.get_mut(self.clone());
         let &maybe_cost = match maybe_costs {
             Some(costs) => costs.get(self.clone()),
             None => &None,
         };
 
-        trigger_ability(charges, cooldown, maybe_pool, maybe_cost)
+        trigger_ability(action_charges, cooldown, maybe_pool, maybe_cost)?;
+        cooldowns.trigger_group_cooldown(self.clone())?;
+        cooldowns.trigger_global_cooldown(self.clone())
+    }
+
+    /// Triggers this ability like [`Abilitylike::trigger`], and pays its cost from `maybe_pool`.
+    ///
+    /// If the cost cannot be paid, [`CannotUseAbility::PoolInsufficient`] is returned and the ability is not triggered.
+    ///
+    /// This works directly on the components of an entity, without needing an [`AbilityState`] query.
+    /// As a result, it is a convenient way to unit test ability logic without creating an `App`.
+    /// Unlike [`AbilityStateItem::trigger_and_pay_cost`], optional components such as recasts and [`UndamagedRequirements`](crate::pool::UndamagedRequirements) are not considered.
+    ///
+    /// ```rust
+    /// use leafwing_abilities::prelude::*;
+    /// use leafwing_abilities::premade_pools::mana::{Mana, ManaPool};
+    /// use leafwing_input_manager::Actionlike;
+    ///
+    /// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+    /// enum Spell {
+    ///     Fireball,
+    /// }
+    ///
+    /// let mut charges = ChargeState::default();
+    /// let mut cooldowns = CooldownState::new([(Spell::Fireball, Cooldown::from_secs(1.))]);
+    /// let mut mana = ManaPool::new_full(Mana(15.), Mana(0.));
+    /// let costs = AbilityCosts::new([(Spell::Fireball, Mana(10.))]);
+    ///
+    /// Spell::Fireball
+    ///     .trigger_and_pay_cost(&mut charges, &mut cooldowns, Some(&mut mana), Some(&costs))
+    ///     .unwrap();
+    /// assert_eq!(mana.current(), Mana(5.));
+    ///
+    /// // Not enough mana is left, so nothing is spent
+    /// cooldowns.reset_all();
+    /// assert_eq!(
+    ///     Spell::Fireball.trigger_and_pay_cost(&mut charges, &mut cooldowns, Some(&mut mana), Some(&costs)),
+    ///     Err(CannotUseAbility::PoolInsufficient { deficit: 5. })
+    /// );
+    /// assert!(cooldowns.ready(Spell::Fireball).is_ok());
+    /// ```
+    fn trigger_and_pay_cost<P: Pool>(
+        &self,
+        charges: &mut ChargeState<Self>,
+        cooldowns: &mut CooldownState<Self>,
+        mut maybe_pool: Option<&mut P>,
+        maybe_costs: Option<&AbilityCosts<Self, P>>,
+    ) -> Result<(), CannotUseAbility> {
+        let &maybe_cost = match maybe_costs {
+            Some(costs) => costs.get(self.clone()),
+            None => &None,
+        };
+
+        if let Some(cost) = maybe_cost {
+            match maybe_pool.as_deref() {
+                Some(pool) => pool.available(cost)?,
+                // The pool does not exist, but the cost does
+                None if cost > P::ZERO => {
+                    return Err(CannotUseAbility::PoolInsufficient {
+                        deficit: cost.into(),
+                    })
+                }
+                None => (),
+            }
+        }
+
+        self.trigger(charges, cooldowns, maybe_pool.as_deref_mut(), maybe_costs)?;
+
+        match (maybe_pool, maybe_cost) {
+            (Some(pool), Some(cost)) => pool.expend(cost),
+            _ => Ok(()),
+        }
     }
 }
 
@@ -121,20 +259,53 @@ pub trait Abilitylike: Actionlike {
 /// For example, if an ability is out of charges and also not pressed,
 /// [`ready_and_pressed`](crate::ability_state::AbilityStateItem) will return `Err(CannotUseAbility::NotPressed)`,
 /// rather than `Err(CannotUseAbility::NoCharges)`, even though both are true.
-#[derive(Error, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Error, Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub enum CannotUseAbility {
     /// The corresponding [`ActionState`](leafwing_input_manager::action_state::ActionState) was not pressed
     #[error("The ability was not pressed.")]
     NotPressed,
+    /// The ability was disabled by a [`DisabledAbilities`](crate::disabled::DisabledAbilities) component, such as by a silence
+    #[error("The ability is disabled.")]
+    Disabled,
     /// There were no [`Charges`] available for this ability
     #[error("No charges available.")]
     NoCharges,
     /// The [`Cooldown`] of this ability was not ready
-    #[error("Cooldown not ready.")]
-    OnCooldown,
+    #[error("Cooldown not ready for another {remaining:?}.")]
+    OnCooldown {
+        /// The time until the cooldown is ready, as reported by [`Cooldown::time_until_ready`]
+        remaining: Duration,
+    },
+    /// The [global cooldown](CooldownState::global_cooldown) was not ready
+    ///
+    /// This is only returned if the ability's own [`Cooldown`] is ready.
+    #[error("Global cooldown not ready for another {remaining:?}.")]
+    OnGlobalCooldown {
+        /// The time until the global cooldown is ready, as reported by [`Cooldown::time_until_ready`]
+        remaining: Duration,
+    },
     /// Not enough resources from the corresponding [`Pool`]s are available
-    #[error("Not enough resources.")]
-    PoolInsufficient,
+    #[error("Not enough resources: {deficit} more needed.")]
+    PoolInsufficient {
+        /// How much more of the resource is needed, converted from the [`Pool::Quantity`]
+        deficit: f32,
+    },
+    /// The corresponding [`Pool`] was damaged too recently, according to its [`UndamagedRequirements`](crate::pool::UndamagedRequirements)
+    #[error("Damaged too recently.")]
+    RecentlyDamaged,
+    /// The target's [`Pool`] was not below the required threshold
+    #[error("Target is above the required threshold.")]
+    TargetAboveThreshold,
+    /// Activating this ability would exceed the [`ConcurrencyLimit`](crate::cooldown::ConcurrencyLimit) of this entity
+    #[error("Too many abilities are active.")]
+    TooManyActive,
+    /// An ability is being channeled, and its [`ChannelInterruptPolicy`](crate::channel::ChannelInterruptPolicy) ignores this ability
+    #[error("Another ability is being channeled.")]
+    Channeling,
+    /// The cost of this ability is [paid when its cast finishes](crate::cast_time::CostTiming::OnCompletion),
+    /// so extra costs cannot be paid alongside it by [`trigger_and_pay_costs`](crate::ability_state::AbilityStateItem::trigger_and_pay_costs)
+    #[error("The cost of the ability is deferred until its cast finishes.")]
+    CostDeferred,
 }
 
 /// Checks if a [`Charges`], [`Cooldown`] pair associated with an ability is ready to use.
@@ -143,6 +314,11 @@ pub enum CannotUseAbility {
 /// If this ability has a cooldown but no charges, the cooldown must be ready.
 /// Otherwise, returns `true`.
 ///
+/// When both are present, the [`CooldownStrategy`] of the charges decides whether the cooldown gates uses.
+/// Usually the cooldown is the timer that recharges the charges, so only the charges are checked.
+/// With [`CooldownStrategy::RateLimit`], the cooldown limits the rate of use instead, and both must be ready:
+/// [`CannotUseAbility::NoCharges`] is returned if no charges are left, and [`CannotUseAbility::OnCooldown`] if charges are left but the cooldown is not ready.
+///
 /// If you don't have an associated resource pool to check, pass in [`NullPool`] as `P`.
 #[inline]
 pub fn ability_ready<P: Pool>(
@@ -152,10 +328,14 @@ pub fn ability_ready<P: Pool>(
     cost: Option<P::Quantity>,
 ) -> Result<(), CannotUseAbility> {
     if let Some(charges) = charges {
-        if charges.charges() > 0 {
-            Ok(())
-        } else {
+        if charges.charges() == 0 {
             Err(CannotUseAbility::NoCharges)
+        } else if let (CooldownStrategy::RateLimit, Some(cooldown)) =
+            (charges.cooldown_strat, cooldown)
+        {
+            cooldown.ready()
+        } else {
+            Ok(())
         }
     } else if let Some(cooldown) = cooldown {
         cooldown.ready()
@@ -168,7 +348,9 @@ pub fn ability_ready<P: Pool>(
     // The pool does not exist, but the cost does
     } else if let Some(cost) = cost {
         if cost > P::ZERO {
-            Err(CannotUseAbility::PoolInsufficient)
+            Err(CannotUseAbility::PoolInsufficient {
+                deficit: cost.into(),
+            })
         } else {
             Ok(())
         }
@@ -180,6 +362,7 @@ pub fn ability_ready<P: Pool>(
 /// Triggers an implicit ability, depleting a charge if available.
 ///
 /// If no `charges` is [`None`], this will be based off the [`Cooldown`] alone, triggering it if possible.
+/// If the charges use [`CooldownStrategy::RateLimit`], a charge is spent and the cooldown is triggered on every use.
 /// If you don't have an associated resource pool to check, pass in [`NullPool`] as `P`.
 #[inline]
 pub fn trigger_ability<P: Pool>(
@@ -192,6 +375,15 @@ pub fn trigger_ability<P: Pool>(
 
     if let Some(ref mut charges) = charges {
         charges.expend()?;
+
+        if let Some(ref mut cooldown) = cooldown {
+            if charges.cooldown_strat == CooldownStrategy::RateLimit {
+                cooldown.trigger()?;
+            // Start recovering charges, unless the cooldown is already doing so
+            } else if charges.recharging() && cooldown.ready().is_ok() {
+                cooldown.trigger()?;
+            }
+        }
     } else if let Some(ref mut cooldown) = cooldown {
         cooldown.trigger()?;
     }
@@ -231,6 +423,7 @@ mod tests {
     use crate::cooldown::Cooldown;
     use crate::NullPool;
     use crate::{ability_ready, trigger_ability, CannotUseAbility};
+    use bevy::utils::Duration;
 
     #[test]
     fn ability_ready_no_cooldown_no_charges() {
@@ -245,7 +438,9 @@ mod tests {
         cooldown.as_mut().map(|c| c.trigger());
         assert_eq!(
             ability_ready::<NullPool>(&None, &cooldown, None, None),
-            Err(CannotUseAbility::OnCooldown)
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(1)
+            })
         );
     }
 
@@ -303,11 +498,15 @@ mod tests {
         cooldown.as_mut().map(|c| c.trigger());
         assert_eq!(
             trigger_ability::<NullPool>(&mut None, &mut cooldown, None, None),
-            Err(CannotUseAbility::OnCooldown)
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(1)
+            })
         );
         assert_eq!(
             ability_ready::<NullPool>(&None, &cooldown, None, None),
-            Err(CannotUseAbility::OnCooldown)
+            Err(CannotUseAbility::OnCooldown {
+                remaining: Duration::from_secs(1)
+            })
         );
     }
 
@@ -357,4 +556,64 @@ mod tests {
             Err(CannotUseAbility::NoCharges)
         );
     }
+
+    #[test]
+    fn cloned_ability_state_is_independent() {
+        use crate as leafwing_abilities;
+        use crate::charges::ChargeState;
+        use crate::cooldown::CooldownState;
+        use crate::pool::{AbilityCosts, Pool};
+        use crate::premade_pools::mana::{Mana, ManaPool};
+        use crate::{AbilitiesBundle, Abilitylike};
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy)]
+        enum Action {
+            Fireball,
+        }
+
+        let bundle = AbilitiesBundle {
+            cooldowns: CooldownState::new([(Action::Fireball, Cooldown::from_secs(1.))]),
+            charges: ChargeState::new([(Action::Fireball, Charges::replenish_one(2))]),
+        };
+        let pool = ManaPool::new_full(Mana(10.), Mana(0.));
+        let costs = AbilityCosts::<Action, ManaPool>::new([(Action::Fireball, Mana(4.))]);
+
+        // Simulate a cast on a preview copy
+        let mut preview_bundle = bundle.clone();
+        let mut preview_pool = pool.clone();
+        let preview_costs = costs.clone();
+        Action::Fireball
+            .trigger_and_pay_cost(
+                &mut preview_bundle.charges,
+                &mut preview_bundle.cooldowns,
+                Some(&mut preview_pool),
+                Some(&preview_costs),
+            )
+            .unwrap();
+
+        // Small types can be copied or cloned directly
+        let cooldown: Cooldown = preview_bundle.cooldowns.get(Action::Fireball).unwrap();
+        let charges: Charges = preview_bundle
+            .charges
+            .get(Action::Fireball)
+            .clone()
+            .unwrap();
+        assert!(cooldown.ready().is_err());
+        assert_eq!(charges.charges(), 1);
+        assert_eq!(preview_pool.current(), Mana(6.));
+
+        // The originals are untouched
+        assert!(bundle.cooldowns.ready(Action::Fireball).is_ok());
+        assert_eq!(
+            bundle
+                .charges
+                .get(Action::Fireball)
+                .as_ref()
+                .unwrap()
+                .charges(),
+            2
+        );
+        assert_eq!(pool.current(), Mana(10.));
+    }
 }
@@ -1,10 +1,19 @@
 //! Contains main plugin exported by this crate.
 
+use crate::cast_time::CastFinished;
+use crate::channel::ChannelCompleted;
+use crate::charges::ChargeState;
+use crate::cooldown::{CooldownReady, CooldownState, DefaultCooldowns};
+use crate::pool::{Pool, PoolDepleted, PoolFull};
+use crate::recast::RecastExpired;
+use crate::triggers::{AbilityTriggered, TriggerSpawners};
 use crate::Abilitylike;
 use bevy::ecs::prelude::*;
+use bevy::time::{fixed_timestep::FixedTime, Time};
+use bevy::utils::Duration;
 use core::marker::PhantomData;
 
-use bevy::app::{App, CoreSet, Plugin};
+use bevy::app::{App, CoreSchedule, CoreSet, IntoSystemAppConfigs, Plugin};
 use leafwing_input_manager::plugin::{InputManagerSystem, ToggleActions};
 
 /// A [`Plugin`] that collects [`Input`](bevy::input::Input) from disparate sources, producing an [`ActionState`](crate::action_state::ActionState) that can be conveniently checked
@@ -39,7 +48,16 @@ use leafwing_input_manager::plugin::{InputManagerSystem, ToggleActions};
 ///    - powers the [`ActionStateDriver`](crate::action_state::ActionStateDriver) component baseod on an [`Interaction`](bevy::ui::Interaction) component
 ///    - labeled [`InputManagerSystem::Update`]
 /// - [`release_on_disable`](crate::systems::release_on_disable), which resets action states when [`ToggleActions`] is flipped, to avoid persistent presses.
+///
+/// ## Per-frame state
+///
+/// Some ability state, such as the [`TriggeredAbilities`](crate::triggers::TriggeredAbilities) log, only describes the current frame.
+/// This state is cleared in [`AbilitySystem::ClearFrameState`], during [`CoreSet::Last`].
+/// Systems that read it should run during [`CoreSet::Update`] or [`CoreSet::PostUpdate`],
+/// or be ordered before [`AbilitySystem::ClearFrameState`].
 pub struct AbilityPlugin<A: Abilitylike> {
+    default_cooldowns: Option<CooldownState<A>>,
+    time_source: TimeSource,
     _phantom: PhantomData<A>,
 }
 
@@ -47,6 +65,8 @@ pub struct AbilityPlugin<A: Abilitylike> {
 impl<A: Abilitylike> Default for AbilityPlugin<A> {
     fn default() -> Self {
         Self {
+            default_cooldowns: None,
+            time_source: TimeSource::Virtual,
             _phantom: PhantomData::default(),
         }
     }
@@ -61,9 +81,242 @@ impl<A: Abilitylike> AbilityPlugin<A> {
     #[must_use]
     pub fn server() -> Self {
         Self {
+            default_cooldowns: None,
+            time_source: TimeSource::Virtual,
             _phantom: PhantomData::default(),
         }
     }
+
+    /// Ticks abilities in [`CoreSchedule::FixedUpdate`], advancing them by the [`FixedTime::period`](bevy::time::fixed_timestep::FixedTime::period) each time.
+    ///
+    /// Cooldowns, charges, recast windows and the time since each ability was last used
+    /// then advance in identical steps regardless of the frame rate,
+    /// which is needed for deterministic simulations such as lockstep multiplayer.
+    ///
+    /// The tick systems remain in [`AbilitySystem::Tick`], which can still be used for ordering.
+    /// Resource pools are unaffected: they are regenerated by the [`PoolPlugin`].
+    /// This is equivalent to [`with_time_source(TimeSource::Fixed)`](Self::with_time_source).
+    #[must_use]
+    pub fn in_fixed_update(self) -> Self {
+        self.with_time_source(TimeSource::Fixed)
+    }
+
+    /// Selects the clock that abilities are ticked against.
+    ///
+    /// By default, [`TimeSource::Virtual`] is used, so cooldowns stop while [`Time`] is paused or slowed.
+    /// Use [`TimeSource::Real`] for cooldowns that should keep progressing regardless, such as in idle games.
+    ///
+    /// The elapsed time is read through [`AbilityDeltaTime`](crate::systems::AbilityDeltaTime),
+    /// according to the [`AbilityTimeSource<A>`] resource inserted by this plugin.
+    /// With [`TimeSource::Manual`], the tick systems are not added at all.
+    #[must_use]
+    pub fn with_time_source(mut self, time_source: TimeSource) -> Self {
+        self.time_source = time_source;
+        self
+    }
+
+    /// Registers the [`CooldownState`] that newly spawned entities should use by default.
+    ///
+    /// Any [`CooldownState`] component added without cooldowns of its own (for example, via [`AbilitiesBundle::default`](crate::AbilitiesBundle))
+    /// is replaced by a copy of `cooldowns` before it is first ticked.
+    /// These defaults are stored in the [`DefaultCooldowns<A>`] resource.
+    #[must_use]
+    pub fn with_default_cooldowns(mut self, cooldowns: CooldownState<A>) -> Self {
+        self.default_cooldowns = Some(cooldowns);
+        self
+    }
+
+    /// Runs a single complete update of this plugin's systems on the `world`, as if `delta_time` had elapsed.
+    ///
+    /// Cooldowns and charges are ticked (respecting any [`CooldownReduction`](crate::cooldown::CooldownReduction)),
+    /// sending a [`CooldownReady`] event for each cooldown that finishes,
+    /// recast windows are advanced, sending a [`RecastExpired`] event for each window that expires,
+    /// channels are advanced, sending a [`ChannelCompleted`] event for each channel that completes,
+    /// and the time since each ability was last used is advanced.
+    /// [`ToggleActions<A>`] is ignored: the update is always performed.
+    ///
+    /// Every [`Pool`] of type `P` is regenerated, as in [`PoolPlugin::regenerate_world`],
+    /// and then casts are advanced by [`tick_cast_times`](crate::systems::tick_cast_times), sending a [`CastFinished`] event for each cast that finishes.
+    /// If abilities of type `A` do not use a resource pool, pass in [`NullPool`](crate::NullPool) as `P`.
+    ///
+    /// This is intended for deterministic tests, and does not require a [`Time`] resource or a full [`App`].
+    /// Every system is advanced by exactly `delta_time`, regardless of the configured [`TimeSource`].
+    /// Any existing [`Time`] and [`FixedTime`] resources are left untouched.
+    pub fn tick_world<P: Pool + Component + Resource>(world: &mut World, delta_time: Duration) {
+        use crate::systems::*;
+
+        // Only inserted if missing, so events are not lost when used alongside this plugin
+        world.init_resource::<Events<CooldownReady<A>>>();
+        world.init_resource::<Events<RecastExpired<A>>>();
+        world.init_resource::<Events<ChannelCompleted<A>>>();
+        world.init_resource::<Events<CastFinished<A>>>();
+
+        let mut schedule = Schedule::new();
+        schedule.add_systems((
+            tick_cooldowns::<A>,
+            tick_recasts::<A>,
+            tick_channels::<A>,
+            tick_last_used::<A>,
+            regenerate_resource_pool::<P>,
+            tick_cast_times::<A, P>.after(regenerate_resource_pool::<P>),
+        ));
+        run_with_delta_time(world, &mut schedule, delta_time);
+    }
+}
+
+/// Runs the `schedule` on the `world` with a [`Time`] resource whose delta is exactly `delta_time`.
+///
+/// The [`FixedTime`] resource is removed while the `schedule` runs, so that [`TimeSource::Fixed`] also reads this delta.
+/// Any existing [`Time`] and [`FixedTime`] resources are restored afterwards.
+fn run_with_delta_time(world: &mut World, schedule: &mut Schedule, delta_time: Duration) {
+    let previous_time = world.remove_resource::<Time>();
+    let previous_fixed_time = world.remove_resource::<FixedTime>();
+    let mut time = Time::default();
+    let start = time.startup();
+    time.update_with_instant(start);
+    time.update_with_instant(start + delta_time);
+    world.insert_resource(time);
+
+    schedule.run(world);
+
+    match previous_time {
+        Some(previous_time) => world.insert_resource(previous_time),
+        None => {
+            world.remove_resource::<Time>();
+        }
+    }
+    if let Some(previous_fixed_time) = previous_fixed_time {
+        world.insert_resource(previous_fixed_time);
+    }
+}
+
+/// The clocks that abilities can be ticked against, selected with [`AbilityPlugin::with_time_source`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TimeSource {
+    /// The [`Time::delta`], which stops while [`Time`] is paused and is scaled by its relative speed.
+    #[default]
+    Virtual,
+    /// The [`Time::raw_delta`], which measures wall-clock time, ignoring any pausing or relative speed.
+    Real,
+    /// The [`FixedTime::period`](bevy::time::fixed_timestep::FixedTime::period), with abilities ticked in [`CoreSchedule::FixedUpdate`].
+    Fixed,
+    /// No clock: abilities are never ticked automatically, and only advance when you tick them yourself.
+    ///
+    /// This is useful for turn-based games, where cooldowns are measured in turns rather than seconds.
+    /// Call [`AbilityPlugin::tick_world`] at the end of each turn,
+    /// or tick individual [`CooldownState`] and [`ChargeState`] components with [`CooldownState::tick`].
+    Manual,
+}
+
+/// The [`TimeSource`] that abilities of type `A` are ticked against.
+///
+/// This is inserted by the [`AbilityPlugin`].
+/// If it is missing, [`TimeSource::Virtual`] is used.
+#[derive(Resource, Debug)]
+pub struct AbilityTimeSource<A: Abilitylike> {
+    source: TimeSource,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Abilitylike> AbilityTimeSource<A> {
+    /// Creates a new [`AbilityTimeSource`] for the given `source`.
+    #[must_use]
+    pub fn new(source: TimeSource) -> Self {
+        Self {
+            source,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The clock that abilities of type `A` are ticked against.
+    #[inline]
+    #[must_use]
+    pub fn source(&self) -> TimeSource {
+        self.source
+    }
+}
+
+/// The system sets added by the [`AbilityPlugin`], which your own systems can be ordered relative to.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AbilitySystem {
+    /// Advances cooldowns, charges, recast windows, channels and the time since each ability was last used.
+    ///
+    /// Runs during [`CoreSet::PreUpdate`], or in [`CoreSchedule::FixedUpdate`] if configured with [`AbilityPlugin::in_fixed_update`].
+    /// Add a run condition to this set to stop these systems from running, such as while the game is paused:
+    ///
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use leafwing_abilities::prelude::*;
+    /// use leafwing_abilities::plugin::AbilitySystem;
+    /// use leafwing_input_manager::Actionlike;
+    ///
+    /// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+    /// enum Action {
+    ///     Fireball,
+    /// }
+    ///
+    /// #[derive(States, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+    /// enum GameState {
+    ///     #[default]
+    ///     Playing,
+    ///     Cutscene,
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.add_state::<GameState>()
+    ///     .add_plugin(AbilityPlugin::<Action>::default())
+    ///     .configure_set(AbilitySystem::Tick.run_if(in_state(GameState::Playing)));
+    /// ```
+    ///
+    /// To pause the cooldowns of a single entity, add a [`CooldownsPaused`](crate::cooldown::CooldownsPaused) component to it.
+    Tick,
+    /// Advances cooldowns and charges, via the [`tick_cooldowns`](crate::systems::tick_cooldowns) system.
+    ///
+    /// This is a subset of [`AbilitySystem::Tick`].
+    /// Order systems that modify cooldowns relative to this set, such as refunds from hit events,
+    /// so that their effects are applied consistently each frame:
+    ///
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use bevy::utils::Duration;
+    /// use leafwing_abilities::prelude::*;
+    /// use leafwing_abilities::plugin::AbilitySystem;
+    /// use leafwing_input_manager::Actionlike;
+    ///
+    /// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+    /// enum Action {
+    ///     Strike,
+    /// }
+    ///
+    /// struct CriticalHit {
+    ///     attacker: Entity,
+    /// }
+    ///
+    /// fn refund_on_crit(
+    ///     mut crits: EventReader<CriticalHit>,
+    ///     mut query: Query<&mut CooldownState<Action>>,
+    /// ) {
+    ///     for crit in crits.iter() {
+    ///         if let Ok(mut cooldowns) = query.get_mut(crit.attacker) {
+    ///             cooldowns.refund(Action::Strike, Duration::from_secs(2));
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugin(AbilityPlugin::<Action>::default())
+    ///     .add_event::<CriticalHit>()
+    ///     .add_system(
+    ///         refund_on_crit
+    ///             .in_base_set(CoreSet::PreUpdate)
+    ///             .before(AbilitySystem::TickCooldowns),
+    ///     );
+    /// ```
+    TickCooldowns,
+    /// Clears ability state that only describes the current frame, such as the [`TriggeredAbilities`](crate::triggers::TriggeredAbilities) log.
+    ///
+    /// Runs during [`CoreSet::Last`].
+    ClearFrameState,
 }
 
 impl<A: Abilitylike> Plugin for AbilityPlugin<A> {
@@ -71,15 +324,171 @@ impl<A: Abilitylike> Plugin for AbilityPlugin<A> {
         use crate::systems::*;
 
         // Systems
+        app.insert_resource(AbilityTimeSource::<A>::new(self.time_source));
+        if self.time_source == TimeSource::Fixed {
+            app.add_systems(
+                (
+                    tick_cooldowns::<A>
+                        .run_if(run_if_enabled::<A>)
+                        .in_set(AbilitySystem::TickCooldowns),
+                    tick_recasts::<A>.run_if(run_if_enabled::<A>),
+                    tick_channels::<A>.run_if(run_if_enabled::<A>),
+                    tick_last_used::<A>.run_if(run_if_enabled::<A>),
+                )
+                    .in_set(AbilitySystem::Tick)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            );
+        } else if self.time_source != TimeSource::Manual {
+            app.add_system(
+                tick_cooldowns::<A>
+                    .run_if(run_if_enabled::<A>)
+                    .in_set(AbilitySystem::Tick)
+                    .in_set(AbilitySystem::TickCooldowns)
+                    .in_set(InputManagerSystem::Tick)
+                    .in_base_set(CoreSet::PreUpdate)
+                    .before(InputManagerSystem::Update),
+            );
+            app.add_system(
+                tick_recasts::<A>
+                    .run_if(run_if_enabled::<A>)
+                    .in_set(AbilitySystem::Tick)
+                    .in_set(InputManagerSystem::Tick)
+                    .in_base_set(CoreSet::PreUpdate)
+                    .before(InputManagerSystem::Update),
+            );
+            app.add_system(
+                tick_channels::<A>
+                    .run_if(run_if_enabled::<A>)
+                    .in_set(AbilitySystem::Tick)
+                    .in_set(InputManagerSystem::Tick)
+                    .in_base_set(CoreSet::PreUpdate)
+                    .before(InputManagerSystem::Update),
+            );
+            app.add_system(
+                tick_last_used::<A>
+                    .run_if(run_if_enabled::<A>)
+                    .in_set(AbilitySystem::Tick)
+                    .in_set(InputManagerSystem::Tick)
+                    .in_base_set(CoreSet::PreUpdate)
+                    .before(InputManagerSystem::Update),
+            );
+        }
+
+        if let Some(default_cooldowns) = &self.default_cooldowns {
+            app.insert_resource(DefaultCooldowns(default_cooldowns.clone()));
+            app.add_system(
+                apply_default_cooldowns::<A>
+                    .in_base_set(CoreSet::PreUpdate)
+                    .before(InputManagerSystem::Tick),
+            );
+        }
+
+        app.configure_set(AbilitySystem::ClearFrameState.in_base_set(CoreSet::Last));
         app.add_system(
-            tick_cooldowns::<A>
-                .run_if(run_if_enabled::<A>)
-                .in_set(InputManagerSystem::Tick)
-                .in_base_set(CoreSet::PreUpdate)
-                .before(InputManagerSystem::Update),
+            run_trigger_spawners::<A>
+                .in_base_set(CoreSet::Last)
+                .before(AbilitySystem::ClearFrameState),
         );
+        app.add_system(
+            send_ability_triggered::<A>
+                .in_base_set(CoreSet::Last)
+                .before(AbilitySystem::ClearFrameState),
+        );
+        app.add_system(clear_triggered_abilities::<A>.in_set(AbilitySystem::ClearFrameState));
+
+        // Reflection
+        app.register_type::<CooldownState<A>>();
+        app.register_type::<ChargeState<A>>();
+        #[cfg(feature = "premade_pools")]
+        {
+            use crate::premade_pools::{
+                balance::BalancedPool, life::LifePool, mana::ManaPool, stamina::StaminaPool,
+            };
+            app.register_type::<LifePool>();
+            app.register_type::<ManaPool>();
+            app.register_type::<StaminaPool>();
+            app.register_type::<BalancedPool>();
+        }
 
         // Resources
         app.init_resource::<ToggleActions<A>>();
+        app.init_resource::<TriggerSpawners<A>>();
+
+        // Events
+        app.add_event::<CooldownReady<A>>();
+        app.add_event::<RecastExpired<A>>();
+        app.add_event::<ChannelCompleted<A>>();
+        app.add_event::<CastFinished<A>>();
+        app.add_event::<AbilityTriggered<A>>();
+    }
+}
+
+/// A [`Plugin`] that manages the resource [`Pool`] type `P`, for both components and resources.
+///
+/// Pools are regenerated according to their [`regen_per_second`](Pool::regen_per_second) and the elapsed [`Time`],
+/// via the [`regenerate_resource_pool`](crate::systems::regenerate_resource_pool) system during [`CoreSet::PreUpdate`].
+/// Any [`RegenMultiplier`](crate::pool::RegenMultiplier) stored alongside a pool scales its regeneration.
+/// Beforehand, any changed [`PoolModifiers`](crate::pool::PoolModifiers) are applied to the max of their pool,
+/// via the [`apply_pool_modifiers`](crate::systems::apply_pool_modifiers) system.
+///
+/// Sends a [`PoolDepleted`] event when a pool becomes empty, and a [`PoolFull`] event when it becomes full,
+/// via the [`send_pool_events`](crate::systems::send_pool_events) system during [`CoreSet::PostUpdate`].
+///
+/// Add one copy of this plugin for each pool type that you want to track.
+pub struct PoolPlugin<P: Pool + Component + Resource> {
+    manual_regeneration: bool,
+    _phantom: PhantomData<P>,
+}
+
+// Deriving default induces an undesired bound on the generic
+impl<P: Pool + Component + Resource> Default for PoolPlugin<P> {
+    fn default() -> Self {
+        Self {
+            manual_regeneration: false,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<P: Pool + Component + Resource> PoolPlugin<P> {
+    /// Stops pools from being regenerated automatically each frame, so that they only regenerate when you ask them to.
+    ///
+    /// This is useful for turn-based games: call [`PoolPlugin::regenerate_world`] at the end of each turn,
+    /// or [`Pool::regenerate`] on individual pools.
+    /// Modifiers are still applied and events are still sent.
+    #[must_use]
+    pub fn with_manual_regeneration(mut self) -> Self {
+        self.manual_regeneration = true;
+        self
+    }
+
+    /// Regenerates every pool of type `P` on the `world`, as if `delta_time` had elapsed.
+    ///
+    /// This runs the [`regenerate_resource_pool`](crate::systems::regenerate_resource_pool) system once,
+    /// so any [`RegenMultiplier`](crate::pool::RegenMultiplier) or [`RegenGate`](crate::pool::RegenGate) is respected.
+    /// Any existing [`Time`] resource is left untouched.
+    pub fn regenerate_world(world: &mut World, delta_time: Duration) {
+        let mut schedule = Schedule::new();
+        schedule.add_system(crate::systems::regenerate_resource_pool::<P>);
+        run_with_delta_time(world, &mut schedule, delta_time);
+    }
+}
+
+impl<P: Pool + Component + Resource> Plugin for PoolPlugin<P> {
+    fn build(&self, app: &mut App) {
+        use crate::systems::*;
+
+        app.add_system(
+            apply_pool_modifiers::<P>
+                .in_base_set(CoreSet::PreUpdate)
+                .before(regenerate_resource_pool::<P>),
+        );
+        if !self.manual_regeneration {
+            app.add_system(regenerate_resource_pool::<P>.in_base_set(CoreSet::PreUpdate));
+        }
+        app.add_system(send_pool_events::<P>.in_base_set(CoreSet::PostUpdate));
+
+        app.add_event::<PoolDepleted<P>>();
+        app.add_event::<PoolFull<P>>();
     }
 }
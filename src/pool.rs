@@ -5,9 +5,11 @@
 //! Life, mana, energy and rage might all be modelled effectively as pools.
 //! Pools have a maximum value, have a minimum value of zero, can regenerate over time, and can be spent to pay for abilities.
 //!
-//! The [`regenerate_resource_pool`](crate::systems::regenerate_resource_pool) system will regenerate resource pools of a given type if manually added.
+//! Add a [`PoolPlugin`](crate::plugin::PoolPlugin) for each pool type to regenerate pools of that type automatically.
+//! Alternatively, the [`regenerate_resource_pool`](crate::systems::regenerate_resource_pool) system can be added to your schedule manually.
 
 use bevy::ecs::prelude::*;
+use bevy::reflect::{FromReflect, Reflect};
 use bevy::utils::Duration;
 use core::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
 use std::marker::PhantomData;
@@ -30,6 +32,8 @@ pub trait Pool: Sized {
         + SubAssign
         + Mul<f32, Output = Self::Quantity>
         + Div<f32, Output = Self::Quantity>
+        + Div<Self::Quantity, Output = f32>
+        + Into<f32>
         + PartialEq
         + PartialOrd
         + Clone
@@ -75,12 +79,72 @@ pub trait Pool: Sized {
     /// Panics if `max` is less than [`Pool::ZERO`].
     fn current(&self) -> Self::Quantity;
 
+    /// The current quantity as a fraction of the [`max`](Pool::max), between `0.0` and `1.0`.
+    ///
+    /// Returns `0.0` if the max is [`Pool::ZERO`].
+    fn fraction(&self) -> f32 {
+        if self.max() <= Self::ZERO {
+            return 0.0;
+        }
+
+        (self.current() / self.max()).clamp(0.0, 1.0)
+    }
+
+    /// Sets the current quantity to a `fraction` of the [`max`](Pool::max).
+    ///
+    /// The fraction is clamped between `0.0` and `1.0`, and non-finite fractions are ignored.
+    /// The value that was actually set is returned.
+    fn set_fraction(&mut self, fraction: f32) -> Self::Quantity {
+        if !fraction.is_finite() {
+            return self.current();
+        }
+
+        self.set_current(self.max() * fraction.clamp(0.0, 1.0))
+    }
+
+    /// Is the current quantity at the [`max`](Pool::max)?
+    fn is_full(&self) -> bool {
+        self.current() >= self.max()
+    }
+
+    /// Is the current quantity [`Pool::ZERO`]?
+    fn is_empty(&self) -> bool {
+        self.current() <= Self::ZERO
+    }
+
+    /// Captures the complete state of this pool, so that it can later be [restored](Pool::restore).
+    ///
+    /// This mirrors [`CooldownState::snapshot`](crate::cooldown::CooldownState::snapshot), which describes how snapshots are used.
+    /// Restoring a snapshot makes the pool bit-identical to when it was taken,
+    /// including any internal timers and accumulators, such as the time since a [`LifePool`](crate::premade_pools::life::LifePool) was damaged.
+    /// Note that quantities such as [`Life`](crate::premade_pools::life::Life) and [`Mana`](crate::premade_pools::mana::Mana) are floating point:
+    /// they must be restored rather than recomputed for re-simulation to be deterministic.
+    #[must_use]
+    fn snapshot(&self) -> Self
+    where
+        Self: Clone,
+    {
+        self.clone()
+    }
+
+    /// Restores the state captured by [`snapshot`](Pool::snapshot).
+    fn restore(&mut self, snapshot: &Self)
+    where
+        Self: Clone,
+    {
+        self.clone_from(snapshot);
+    }
+
     /// Check if the given cost can be paid by this pool.
+    ///
+    /// If not, the returned [`CannotUseAbility::PoolInsufficient`] reports how much more is needed.
     fn available(&self, amount: Self::Quantity) -> Result<(), CannotUseAbility> {
         if self.current() >= amount {
             Ok(())
         } else {
-            Err(CannotUseAbility::PoolInsufficient)
+            Err(CannotUseAbility::PoolInsufficient {
+                deficit: (amount - self.current()).into(),
+            })
         }
     }
 
@@ -90,6 +154,24 @@ pub trait Pool: Sized {
     /// The value that was actually set is returned.
     fn set_current(&mut self, new_quantity: Self::Quantity) -> Self::Quantity;
 
+    /// Sets the current quantity of resources in the pool, if the new quantity is finite.
+    ///
+    /// This will be bounded by the minimum and maximum values of this pool.
+    /// The value that was actually set is returned.
+    ///
+    /// Has no effect if `new_quantity` is not finite (for example, NaN),
+    /// returning [`PoolError::NonFinite`] instead.
+    fn try_set_current(
+        &mut self,
+        new_quantity: Self::Quantity,
+    ) -> Result<Self::Quantity, PoolError> {
+        if quantity_is_finite(new_quantity) {
+            Ok(self.set_current(new_quantity))
+        } else {
+            Err(PoolError::NonFinite)
+        }
+    }
+
     /// The maximum quantity of resources that this pool can store.
     fn max(&self) -> Self::Quantity;
 
@@ -97,9 +179,9 @@ pub trait Pool: Sized {
     ///
     /// The current value will be reduced to the new max if necessary.
     ///
-    /// Has no effect if `new_max < Pool::ZERO`.
-    /// Returns a [`PoolMaxLessThanZero`] error if this occurs.
-    fn set_max(&mut self, new_max: Self::Quantity) -> Result<(), MaxPoolLessThanZero>;
+    /// Has no effect if `new_max < Pool::ZERO` or `new_max` is not finite,
+    /// returning [`PoolError::MaxLessThanZero`] or [`PoolError::NonFinite`] respectively.
+    fn set_max(&mut self, new_max: Self::Quantity) -> Result<(), PoolError>;
 
     /// Spend the specified amount from the pool, if there is that much available.
     ///
@@ -120,6 +202,38 @@ pub trait Pool: Sized {
         self.set_current(new_current);
     }
 
+    /// Adds `amount` to the current value of the pool, up to its [`max`](Pool::max).
+    ///
+    /// Returns the quantity that was actually added, which is less than `amount` if the pool overflowed:
+    /// useful for displaying overhealing.
+    fn add(&mut self, amount: Self::Quantity) -> Self::Quantity {
+        let previous = self.current();
+        self.set_current(previous + amount) - previous
+    }
+
+    /// Subtracts `amount` from the current value of the pool, down to [`Pool::ZERO`].
+    ///
+    /// Unlike [`Pool::expend`], this succeeds even if less than `amount` is available.
+    /// Returns the quantity that was actually removed, which is less than `amount` if the pool ran dry:
+    /// useful for displaying overkill.
+    fn subtract(&mut self, amount: Self::Quantity) -> Self::Quantity {
+        let previous = self.current();
+        previous - self.set_current(previous - amount)
+    }
+
+    /// Adds the signed `delta` to the current value of the pool, such as for an effect that can either heal or damage.
+    ///
+    /// Positive deltas are applied with [`Pool::add`], and negative deltas with [`Pool::subtract`],
+    /// so the pool stays between [`Pool::ZERO`] and its [`max`](Pool::max).
+    /// Returns the signed quantity that was actually applied.
+    fn apply_delta(&mut self, delta: Self::Quantity) -> Self::Quantity {
+        if delta >= Self::ZERO {
+            self.add(delta)
+        } else {
+            Self::ZERO - self.subtract(Self::ZERO - delta)
+        }
+    }
+
     /// The quantity recovered by the pool in one second.
     ///
     /// This value may be negative, in the case of automatically decaying pools (like rage).
@@ -127,23 +241,103 @@ pub trait Pool: Sized {
 
     /// Set the quantity recovered by the pool in one second.
     ///
-    /// This value may be negative, in the case of automatically decaying pools (like rage).
+    /// This value may be negative, in the case of automatically decaying pools (like rage) or drains (like bleeding).
+    /// Negative regeneration drains the pool towards [`Pool::ZERO`], and never below it.
     fn set_regen_per_second(&mut self, new_regen_per_second: Self::Quantity);
 
     /// Regenerates this pool according to the elapsed `delta_time`.
     ///
-    /// Called in the [`regenerate_resource_pool`](crate::systems::regenerate_resource_pool) system.
-    /// Can also be called in your own regeneration systems.
+    /// Can be called in your own regeneration systems.
+    #[inline]
     fn regenerate(&mut self, delta_time: Duration) {
-        let pool_regained = self.regen_per_second() * delta_time.as_secs_f32();
+        self.regenerate_scaled(delta_time, 1.0);
+    }
+
+    /// Regenerates this pool according to the elapsed `delta_time`, with the amount regained scaled by `multiplier`.
+    ///
+    /// Called in the [`regenerate_resource_pool`](crate::systems::regenerate_resource_pool) system,
+    /// using the [`RegenMultiplier`] stored alongside this pool (if any).
+    /// The amount regained before scaling is given by [`Pool::regen_amount`].
+    /// Pools that need to track the passage of time while regenerating should override this method rather than [`Pool::regenerate`].
+    fn regenerate_scaled(&mut self, delta_time: Duration, multiplier: f32) {
+        let pool_regained = self.regen_amount(delta_time, self.time_since_damaged()) * multiplier;
         self.replenish(pool_regained)
     }
+
+    /// The quantity regenerated over the elapsed `delta_time`, before any [`RegenMultiplier`] is applied.
+    ///
+    /// `time_since_damaged` is the [time since this pool was last damaged](Pool::time_since_damaged) at the start of the tick.
+    /// By default, regeneration is linear: [`regen_per_second`](Pool::regen_per_second) is regained each second,
+    /// however long ago the pool was damaged.
+    ///
+    /// Override this method for regeneration that follows a curve,
+    /// such as health that regenerates faster the longer a unit stays out of combat.
+    /// Note that the [`regenerate_resource_pool`](crate::systems::regenerate_resource_pool) system skips pools
+    /// whose [`regen_per_second`](Pool::regen_per_second) is zero, unless they track the time since they were damaged.
+    fn regen_amount(
+        &self,
+        delta_time: Duration,
+        _time_since_damaged: Option<Duration>,
+    ) -> Self::Quantity {
+        self.regen_per_second() * delta_time.as_secs_f32()
+    }
+
+    /// Drains this pool at `rate` per second for the elapsed `delta_time`, such as for channeled abilities or damage over time.
+    ///
+    /// The pool never drops below [`Pool::ZERO`].
+    /// Returns the quantity that was actually spent, which is less than requested once the pool runs dry:
+    /// a channel can be stopped as soon as this falls short.
+    fn expend_per_second(&mut self, rate: Self::Quantity, delta_time: Duration) -> Self::Quantity {
+        let requested = rate * delta_time.as_secs_f32();
+        if requested <= Self::ZERO {
+            return Self::ZERO;
+        }
+
+        let previous = self.current();
+        let target = if previous > requested {
+            previous - requested
+        } else {
+            Self::ZERO
+        };
+
+        previous - self.set_current(target)
+    }
+
+    /// The time that has elapsed since the current value of this pool was last reduced.
+    ///
+    /// Returns [`None`] if this pool has never been reduced, or if this pool type does not track damage.
+    /// Used by [`UndamagedRequirements`] to gate abilities that cannot be used while recently damaged.
+    fn time_since_damaged(&self) -> Option<Duration> {
+        None
+    }
 }
 
-/// The maximum value for a [`Pool`] was set to be less than [`Pool::ZERO`].
+/// Is this quantity finite?
+///
+/// Multiplying by zero maps every finite quantity to zero, but NaN and infinities to NaN,
+/// which is the only value that cannot be compared to itself.
+fn quantity_is_finite<Q: Mul<f32, Output = Q> + PartialOrd + Copy>(quantity: Q) -> bool {
+    let zeroed = quantity * 0.;
+    zeroed.partial_cmp(&zeroed).is_some()
+}
+
+/// An error returned by the fallible setters of a [`Pool`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
-#[error("The maximum quantity that can be stored in a pool must be greater than zero.")]
-pub struct MaxPoolLessThanZero;
+pub enum PoolError {
+    /// The maximum value for a [`Pool`] was set to be less than [`Pool::ZERO`].
+    #[error("The maximum quantity that can be stored in a pool must be greater than zero.")]
+    MaxLessThanZero,
+    /// The minimum value for a [`Pool`] was set to be greater than its maximum value.
+    ///
+    /// This can only occur for pools whose minimum can be changed.
+    #[error(
+        "The minimum quantity that can be stored in a pool cannot be greater than its maximum."
+    )]
+    MinGreaterThanMax,
+    /// A quantity that was not finite (such as NaN or infinity) was provided.
+    #[error("Pool quantities must be finite.")]
+    NonFinite,
+}
 
 /// Stores the cost (in terms of the [`Pool::Quantity`] of ability) associated with each ability of type `A`.
 #[derive(Component, Debug)]
@@ -216,6 +410,18 @@ impl<A: Abilitylike, P: Pool> AbilityCosts<A, P> {
         }
     }
 
+    /// Returns how much more of the resource the `pool` needs before it can pay for `action`.
+    ///
+    /// Returns [`None`] if the cost can already be paid.
+    #[inline]
+    #[must_use]
+    pub fn deficit(&self, action: A, pool: &P) -> Option<P::Quantity> {
+        match self.get(action) {
+            Some(cost) if *cost > pool.current() => Some(*cost - pool.current()),
+            _ => None,
+        }
+    }
+
     /// Returns a reference to the underlying [`Pool::Quantity`] cost for `action`, if set.
     #[inline]
     #[must_use]
@@ -263,6 +469,571 @@ impl<A: Abilitylike, P: Pool> AbilityCosts<A, P> {
     }
 }
 
+/// Stores how long each ability of type `A` requires its [`Pool`] to have gone without being damaged before it can be used.
+///
+/// This is useful for stealth or channeled abilities, which are interrupted by taking damage.
+/// Abilities without a requirement can always be used, regardless of when the pool was last damaged.
+///
+/// The time since the pool was last damaged is read from [`Pool::time_since_damaged`].
+///
+/// ```rust
+/// use bevy::utils::Duration;
+/// use leafwing_abilities::prelude::*;
+/// use leafwing_abilities::premade_pools::life::{Life, LifePool};
+/// use leafwing_input_manager::Actionlike;
+///
+/// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+/// enum Action {
+///     Stealth,
+///     Attack,
+/// }
+///
+/// let requirements = UndamagedRequirements::new([(Action::Stealth, Duration::from_secs(3))]);
+/// let mut life_pool = LifePool::new_full(Life(100.), Life(0.));
+/// assert!(requirements.check(Action::Stealth, Some(&life_pool)).is_ok());
+///
+/// life_pool.expend(Life(10.)).unwrap();
+/// assert_eq!(requirements.check(Action::Stealth, Some(&life_pool)), Err(CannotUseAbility::RecentlyDamaged));
+/// assert!(requirements.check(Action::Attack, Some(&life_pool)).is_ok());
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Eq)]
+pub struct UndamagedRequirements<A: Abilitylike> {
+    /// The required time without damage for each ability, stored in [`Actionlike::variants`] order.
+    requirement_vec: Vec<Option<Duration>>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Abilitylike> Default for UndamagedRequirements<A> {
+    fn default() -> Self {
+        UndamagedRequirements {
+            requirement_vec: A::variants().map(|_| None).collect(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Abilitylike> UndamagedRequirements<A> {
+    /// Creates a new [`UndamagedRequirements`] from an iterator of `(action, duration)` pairs
+    ///
+    /// If a [`Duration`] is not provided for an action, that action can be used no matter when the pool was last damaged.
+    ///
+    /// To create an empty [`UndamagedRequirements`] struct, use the [`Default::default`] method instead.
+    #[must_use]
+    pub fn new(action_duration_pairs: impl IntoIterator<Item = (A, Duration)>) -> Self {
+        let mut requirements = UndamagedRequirements::default();
+        for (action, duration) in action_duration_pairs.into_iter() {
+            requirements.require_undamaged_for(action, duration);
+        }
+        requirements
+    }
+
+    /// Requires that the pool has not been damaged for at least `duration` before `action` can be used.
+    ///
+    /// If a requirement already existed for this action, it will be replaced.
+    #[inline]
+    pub fn require_undamaged_for(&mut self, action: A, duration: Duration) -> &mut Self {
+        *self.get_mut(action) = Some(duration);
+        self
+    }
+
+    /// Checks whether the `pool` has gone long enough without being damaged to use `action`.
+    ///
+    /// Returns [`CannotUseAbility::RecentlyDamaged`] if the pool was damaged too recently.
+    /// If no requirement is set for this action, or no pool is provided, this always returns `Ok(())`.
+    #[inline]
+    pub fn check<P: Pool>(&self, action: A, pool: Option<&P>) -> Result<(), CannotUseAbility> {
+        if let (Some(required), Some(pool)) = (self.get(action), pool) {
+            if let Some(time_since_damaged) = pool.time_since_damaged() {
+                if time_since_damaged < *required {
+                    return Err(CannotUseAbility::RecentlyDamaged);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns a reference to the required time without damage for `action`, if set.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, action: A) -> &Option<Duration> {
+        &self.requirement_vec[action.index()]
+    }
+
+    /// Returns a mutable reference to the required time without damage for `action`, if set.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, action: A) -> &mut Option<Duration> {
+        &mut self.requirement_vec[action.index()]
+    }
+
+    /// Collects a `&mut Self` into a `Self`.
+    ///
+    /// Used to conclude the builder pattern. Actually just calls `self.clone()`.
+    #[inline]
+    #[must_use]
+    pub fn build(&mut self) -> Self {
+        self.clone()
+    }
+}
+
+/// Refreshes the [`Cooldown`](crate::cooldown::Cooldown) of `action` whenever the [`Pool`] `P` on the same entity becomes full.
+///
+/// This is edge-triggered: the cooldown is refreshed once each time the pool fills up,
+/// rather than every frame that the pool stays full.
+/// This is useful for mechanics like "this ability is ready whenever your energy is full".
+///
+/// Requires the [`reset_cooldowns_on_pool_full`](crate::systems::reset_cooldowns_on_pool_full) system to be added to your schedule.
+#[derive(Component, Clone, Debug)]
+pub struct CooldownResetOnPoolFull<A: Abilitylike, P: Pool + Component> {
+    /// The ability whose cooldown is refreshed.
+    pub action: A,
+    /// Was the pool full the last time it was checked?
+    was_full: bool,
+    _phantom: PhantomData<P>,
+}
+
+impl<A: Abilitylike, P: Pool + Component> CooldownResetOnPoolFull<A, P> {
+    /// Creates a new [`CooldownResetOnPoolFull`], which refreshes the cooldown of `action`.
+    #[must_use]
+    pub fn new(action: A) -> Self {
+        CooldownResetOnPoolFull {
+            action,
+            was_full: false,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Checks whether the `pool` has just become full since the last time this method was called.
+    pub fn just_filled(&mut self, pool: &P) -> bool {
+        let is_full = pool.is_full();
+        let just_filled = is_full && !self.was_full;
+        self.was_full = is_full;
+
+        just_filled
+    }
+}
+
+/// An event sent when the current value of the [`Pool`] `P` drops to [`Pool::ZERO`].
+///
+/// Sent by the [`send_pool_events`](crate::systems::send_pool_events) system, added by the [`PoolPlugin`](crate::plugin::PoolPlugin).
+/// This is sent once each time the pool becomes empty, rather than every frame that it stays empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolDepleted<P: Pool> {
+    /// The entity whose pool was depleted, or [`None`] if the pool is stored as a resource.
+    pub entity: Option<Entity>,
+    _phantom: PhantomData<P>,
+}
+
+impl<P: Pool> PoolDepleted<P> {
+    /// Creates a new [`PoolDepleted`] event for the pool of `entity`.
+    #[must_use]
+    pub fn new(entity: Option<Entity>) -> Self {
+        PoolDepleted {
+            entity,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// An event sent when the current value of the [`Pool`] `P` rises to its [`max`](Pool::max).
+///
+/// Sent by the [`send_pool_events`](crate::systems::send_pool_events) system, added by the [`PoolPlugin`](crate::plugin::PoolPlugin).
+/// This is sent once each time the pool becomes full, rather than every frame that it stays full.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolFull<P: Pool> {
+    /// The entity whose pool became full, or [`None`] if the pool is stored as a resource.
+    pub entity: Option<Entity>,
+    _phantom: PhantomData<P>,
+}
+
+impl<P: Pool> PoolFull<P> {
+    /// Creates a new [`PoolFull`] event for the pool of `entity`.
+    #[must_use]
+    pub fn new(entity: Option<Entity>) -> Self {
+        PoolFull {
+            entity,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// A stat that scales the regeneration of every [`Pool`] on the entity (or among the resources) that it is stored alongside.
+///
+/// A multiplier of `2.0` doubles the amount regenerated each second, while `0.0` stops regeneration entirely,
+/// without modifying the [`regen_per_second`](Pool::regen_per_second) of any pool.
+///
+/// This is read by the [`regenerate_resource_pool`](crate::systems::regenerate_resource_pool) system.
+///
+/// ```rust
+/// use leafwing_abilities::pool::RegenMultiplier;
+///
+/// assert_eq!(RegenMultiplier::default().multiplier(), 1.0);
+///
+/// // Multipliers are clamped to a sensible range
+/// assert_eq!(RegenMultiplier::new(-1.0).multiplier(), 0.0);
+/// assert_eq!(RegenMultiplier::new(1000.0).multiplier(), RegenMultiplier::MAX);
+/// assert_eq!(RegenMultiplier::new(f32::NAN).multiplier(), 1.0);
+/// ```
+#[derive(Resource, Component, Debug, Clone, Copy, PartialEq)]
+pub struct RegenMultiplier(f32);
+
+impl Default for RegenMultiplier {
+    fn default() -> Self {
+        RegenMultiplier(1.0)
+    }
+}
+
+impl RegenMultiplier {
+    /// The largest permitted regeneration multiplier.
+    ///
+    /// Higher values would refill pools almost instantly.
+    pub const MAX: f32 = 100.0;
+
+    /// Creates a new [`RegenMultiplier`].
+    ///
+    /// The provided `multiplier` is clamped between `0.0` and [`RegenMultiplier::MAX`].
+    /// Non-finite values are treated as the default multiplier of `1.0`.
+    #[inline]
+    #[must_use]
+    pub fn new(multiplier: f32) -> RegenMultiplier {
+        if multiplier.is_finite() {
+            RegenMultiplier(multiplier.clamp(0.0, Self::MAX))
+        } else {
+            RegenMultiplier::default()
+        }
+    }
+
+    /// The factor by which regeneration is scaled.
+    #[inline]
+    #[must_use]
+    pub fn multiplier(&self) -> f32 {
+        self.0
+    }
+}
+
+/// A switch that can pause the regeneration of the entity (or resources) that it is stored alongside, without removing any components.
+///
+/// While the gate is closed:
+/// - [`Charges`](crate::charges::Charges) that are [recovered over time](crate::charges::Charges::recharges_over_time) are held in place,
+///   along with their cooldowns and independent recharge timers, such as while a weapon is being fired
+/// - every [`Pool`] does not regenerate, such as while in combat
+///
+/// Charges with [`CooldownStrategy::Ignore`](crate::charges::CooldownStrategy::Ignore) or [`CooldownStrategy::RateLimit`](crate::charges::CooldownStrategy::RateLimit)
+/// are unaffected, as are the cooldowns of abilities without charges: use a [`CooldownsPaused`](crate::cooldown::CooldownsPaused) component to freeze them too.
+/// Regeneration resumes from the same point once the gate is opened again.
+///
+/// This is read by the [`tick_cooldowns`](crate::systems::tick_cooldowns) and [`regenerate_resource_pool`](crate::systems::regenerate_resource_pool) systems.
+/// To gate the regeneration of every entity at once, add a run condition to [`AbilitySystem::Tick`](crate::plugin::AbilitySystem::Tick).
+///
+/// ```rust
+/// use leafwing_abilities::pool::RegenGate;
+///
+/// let mut gate = RegenGate::default();
+/// assert!(gate.is_open());
+///
+/// gate.close();
+/// assert!(!gate.is_open());
+/// ```
+#[derive(Resource, Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegenGate {
+    open: bool,
+}
+
+impl Default for RegenGate {
+    fn default() -> Self {
+        RegenGate { open: true }
+    }
+}
+
+impl RegenGate {
+    /// Is regeneration currently allowed?
+    #[inline]
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Allows regeneration to continue.
+    #[inline]
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// Stops regeneration until the gate is [opened](Self::open) again.
+    #[inline]
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Opens the gate if `open` is true, and closes it otherwise.
+    ///
+    /// This is convenient to update every frame from a condition, such as whether the fire button is held.
+    #[inline]
+    pub fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+}
+
+/// Controls whether the current value of a pool can exceed its maximum, such as for overhealing.
+///
+/// This is respected by the premade [`LifePool`](crate::premade_pools::life::LifePool)
+/// and [`ManaPool`](crate::premade_pools::mana::ManaPool) types.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Reflect, FromReflect)]
+pub enum OverflowPolicy<Q: Reflect + FromReflect> {
+    /// The current value is clamped to the maximum.
+    ///
+    /// This is the default.
+    #[default]
+    Clamp,
+    /// The current value may exceed the maximum, and any excess decays back towards the maximum over time.
+    ///
+    /// The pool does not regenerate while it is overflowing.
+    Decay {
+        /// The quantity of excess lost per second.
+        decay_per_second: Q,
+    },
+}
+
+/// Stacking bonuses to the [`max`](Pool::max) of the [`Pool`] type `P` that it is stored alongside.
+///
+/// The effective max is `(base_max + bonus) * (1.0 + percent_bonus)`, where each bonus is the sum of every active buff.
+/// Buffs are applied and removed symmetrically, so they can stack and expire in any order without drifting.
+///
+/// Whenever this component changes, the [`apply_pool_modifiers`](crate::systems::apply_pool_modifiers) system
+/// (added by the [`PoolPlugin`](crate::plugin::PoolPlugin)) [applies](PoolModifiers::apply) the effective max to the pool.
+/// When the max decreases, the current value is clamped to it, so expiring buffs never leave the pool over its max.
+///
+/// ```rust
+/// use leafwing_abilities::pool::{Pool, PoolModifiers};
+/// use leafwing_abilities::premade_pools::life::{Life, LifePool};
+///
+/// let mut life_pool = LifePool::new(Life(50.), Life(100.), Life(0.));
+/// let mut modifiers = PoolModifiers::<LifePool>::new(Life(100.));
+///
+/// modifiers.add_bonus(Life(50.));
+/// modifiers.add_percent_bonus(0.2);
+/// modifiers.apply(&mut life_pool);
+/// assert_eq!(life_pool.max(), Life(180.));
+/// // The current value scales up with the max
+/// assert_eq!(life_pool.current(), Life(90.));
+///
+/// modifiers.remove_bonus(Life(50.));
+/// modifiers.remove_percent_bonus(0.2);
+/// modifiers.apply(&mut life_pool);
+/// assert_eq!(life_pool.max(), Life(100.));
+/// assert_eq!(life_pool.current(), Life(90.));
+/// ```
+#[derive(Resource, Component, Debug, Clone, PartialEq)]
+pub struct PoolModifiers<P: Pool> {
+    base_max: P::Quantity,
+    bonus: P::Quantity,
+    percent_bonus: f32,
+    /// Should the current value be scaled up when the max increases, preserving the [fraction](Pool::fraction) that is full?
+    ///
+    /// If `false`, the current value is unchanged when the max increases.
+    /// Either way, the current value is clamped when the max decreases.
+    pub scale_current: bool,
+}
+
+impl<P: Pool> PoolModifiers<P> {
+    /// Creates a new [`PoolModifiers`] with no bonuses, which scales the current value when the max increases.
+    #[must_use]
+    pub fn new(base_max: P::Quantity) -> Self {
+        PoolModifiers {
+            base_max,
+            bonus: P::ZERO,
+            percent_bonus: 0.0,
+            scale_current: true,
+        }
+    }
+
+    /// The max of the pool before any bonuses are applied.
+    #[inline]
+    #[must_use]
+    pub fn base_max(&self) -> P::Quantity {
+        self.base_max
+    }
+
+    /// Sets the max of the pool before any bonuses are applied.
+    #[inline]
+    pub fn set_base_max(&mut self, base_max: P::Quantity) {
+        self.base_max = base_max;
+    }
+
+    /// Adds a flat bonus to the max of the pool.
+    #[inline]
+    pub fn add_bonus(&mut self, bonus: P::Quantity) {
+        self.bonus += bonus;
+    }
+
+    /// Removes a flat bonus previously added with [`add_bonus`](Self::add_bonus).
+    #[inline]
+    pub fn remove_bonus(&mut self, bonus: P::Quantity) {
+        self.bonus -= bonus;
+    }
+
+    /// Adds a percentage bonus to the max of the pool, where `0.2` increases it by 20%.
+    ///
+    /// Percentage bonuses are summed rather than multiplied together, and negative bonuses reduce the max.
+    #[inline]
+    pub fn add_percent_bonus(&mut self, percent_bonus: f32) {
+        self.percent_bonus += percent_bonus;
+    }
+
+    /// Removes a percentage bonus previously added with [`add_percent_bonus`](Self::add_percent_bonus).
+    #[inline]
+    pub fn remove_percent_bonus(&mut self, percent_bonus: f32) {
+        self.percent_bonus -= percent_bonus;
+    }
+
+    /// The max of the pool after all bonuses are applied.
+    ///
+    /// This is never less than [`Pool::ZERO`].
+    #[must_use]
+    pub fn effective_max(&self) -> P::Quantity {
+        let effective_max = (self.base_max + self.bonus) * (1.0 + self.percent_bonus).max(0.0);
+
+        if effective_max > P::ZERO {
+            effective_max
+        } else {
+            P::ZERO
+        }
+    }
+
+    /// Sets the max of the `pool` to the [`effective_max`](Self::effective_max).
+    ///
+    /// If the max increases and [`scale_current`](Self::scale_current) is set, the current value is scaled by the same fraction.
+    /// If the max decreases, the current value is clamped to it.
+    pub fn apply(&self, pool: &mut P) {
+        let old_max = pool.max();
+        let new_max = self.effective_max();
+        if new_max == old_max {
+            return;
+        }
+
+        let fraction = pool.fraction();
+        if pool.set_max(new_max).is_ok() && self.scale_current && new_max > old_max {
+            pool.set_fraction(fraction);
+        }
+    }
+}
+
+/// A cost paid from one or more resource pools when an ability of type `A` is used.
+///
+/// This is implemented for a [`PoolCost`], which pairs a [`Pool`] with its [`AbilityCosts`],
+/// and for tuples of up to three costs, so that an ability can spend several resources at once.
+/// Costs made of multiple parts are atomic: if any part cannot be afforded, nothing is paid.
+///
+/// Pass a [`ResourceCost`] to [`AbilityStateItem::trigger_and_pay_costs`](crate::AbilityStateItem::trigger_and_pay_costs)
+/// to pay it alongside the [`Pool`] of the [`AbilityState`](crate::AbilityState).
+///
+/// ```rust
+/// use leafwing_abilities::pool::{PoolCost, ResourceCost};
+/// use leafwing_abilities::prelude::*;
+/// use leafwing_abilities::premade_pools::life::{Life, LifePool};
+/// use leafwing_abilities::premade_pools::mana::{Mana, ManaPool};
+/// use leafwing_input_manager::Actionlike;
+///
+/// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+/// enum Action {
+///     BloodMagic,
+/// }
+///
+/// let mut mana = ManaPool::new_full(Mana(10.), Mana(0.));
+/// let mana_costs = AbilityCosts::new([(Action::BloodMagic, Mana(5.))]);
+/// let mut life = LifePool::new_full(Life(10.), Life(0.));
+/// let life_costs = AbilityCosts::new([(Action::BloodMagic, Life(20.))]);
+///
+/// let mut costs = (
+///     PoolCost::new(&mut mana, &mana_costs),
+///     PoolCost::new(&mut life, &life_costs),
+/// );
+/// assert_eq!(
+///     costs.pay(Action::BloodMagic),
+///     Err(CannotUseAbility::PoolInsufficient { deficit: 10. })
+/// );
+/// // Neither resource was spent
+/// assert_eq!(mana.current(), Mana(10.));
+/// assert_eq!(life.current(), Life(10.));
+/// ```
+pub trait ResourceCost<A: Abilitylike> {
+    /// Can every part of the cost of `action` be paid?
+    ///
+    /// Returns [`CannotUseAbility::PoolInsufficient`] if not.
+    fn can_afford(&self, action: A) -> Result<(), CannotUseAbility>;
+
+    /// Pays every part of the cost of `action`, if and only if they can all be afforded.
+    ///
+    /// If any part cannot be paid, [`CannotUseAbility::PoolInsufficient`] is returned and nothing is spent.
+    fn pay(&mut self, action: A) -> Result<(), CannotUseAbility>;
+}
+
+/// A [`Pool`] paired with the [`AbilityCosts`] that are paid from it: the simplest [`ResourceCost`].
+pub struct PoolCost<'a, A: Abilitylike, P: Pool> {
+    /// The pool that costs are paid from.
+    pub pool: &'a mut P,
+    /// The cost of each ability in terms of this pool.
+    pub ability_costs: &'a AbilityCosts<A, P>,
+}
+
+impl<'a, A: Abilitylike, P: Pool> PoolCost<'a, A, P> {
+    /// Creates a new [`PoolCost`], paying `ability_costs` from `pool`.
+    #[must_use]
+    pub fn new(pool: &'a mut P, ability_costs: &'a AbilityCosts<A, P>) -> Self {
+        PoolCost {
+            pool,
+            ability_costs,
+        }
+    }
+}
+
+impl<A: Abilitylike, P: Pool> ResourceCost<A> for PoolCost<'_, A, P> {
+    fn can_afford(&self, action: A) -> Result<(), CannotUseAbility> {
+        match self.ability_costs.get(action) {
+            Some(cost) => self.pool.available(*cost),
+            None => Ok(()),
+        }
+    }
+
+    fn pay(&mut self, action: A) -> Result<(), CannotUseAbility> {
+        match self.ability_costs.get(action) {
+            Some(cost) => self.pool.expend(*cost),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<A: Abilitylike, C0: ResourceCost<A>, C1: ResourceCost<A>> ResourceCost<A> for (C0, C1) {
+    fn can_afford(&self, action: A) -> Result<(), CannotUseAbility> {
+        self.0.can_afford(action.clone())?;
+        self.1.can_afford(action)
+    }
+
+    fn pay(&mut self, action: A) -> Result<(), CannotUseAbility> {
+        self.can_afford(action.clone())?;
+        self.0.pay(action.clone())?;
+        self.1.pay(action)
+    }
+}
+
+impl<A: Abilitylike, C0: ResourceCost<A>, C1: ResourceCost<A>, C2: ResourceCost<A>> ResourceCost<A>
+    for (C0, C1, C2)
+{
+    fn can_afford(&self, action: A) -> Result<(), CannotUseAbility> {
+        self.0.can_afford(action.clone())?;
+        self.1.can_afford(action.clone())?;
+        self.2.can_afford(action)
+    }
+
+    fn pay(&mut self, action: A) -> Result<(), CannotUseAbility> {
+        self.can_afford(action.clone())?;
+        self.0.pay(action.clone())?;
+        self.1.pay(action.clone())?;
+        self.2.pay(action)
+    }
+}
+
 /// Stores a resource pool and the associated costs for each ability.
 ///
 /// Note that if your abilities do not cost the given resource,
@@ -275,8 +1046,34 @@ impl<A: Abilitylike, P: Pool> AbilityCosts<A, P> {
 /// # Important Note
 ///
 /// Note that resource pools are not controlled by [`AbilityPlugin`](crate::plugin::AbilityPlugin).
-/// If you want regeneration to occur automatically, add [`regenerate_resource_pool`](crate::systems::regenerate_resource_pool)
-/// to your schedule.
+/// If you want regeneration to occur automatically, add a [`PoolPlugin`](crate::plugin::PoolPlugin) for your pool type,
+/// or add [`regenerate_resource_pool`](crate::systems::regenerate_resource_pool) to your schedule.
+///
+/// # Example
+///
+/// A [`PoolBundle`] composes with the other bundles needed by a caster:
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_abilities::prelude::*;
+/// use leafwing_abilities::premade_pools::mana::{Mana, ManaPool};
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+/// enum Action {
+///     Fireball,
+/// }
+///
+/// let mut world = World::new();
+/// world.spawn((
+///     InputManagerBundle::<Action>::default(),
+///     AbilitiesBundle::<Action>::default(),
+///     PoolBundle::new(
+///         ManaPool::new_full(Mana(100.), Mana(1.)),
+///         AbilityCosts::new([(Action::Fireball, Mana(10.))]),
+///     ),
+/// ));
+/// ```
 #[derive(Bundle)]
 pub struct PoolBundle<A: Abilitylike, P: Pool + Component> {
     /// The resource pool used to pay for abilities
@@ -285,6 +1082,27 @@ pub struct PoolBundle<A: Abilitylike, P: Pool + Component> {
     pub ability_costs: AbilityCosts<A, P>,
 }
 
+impl<A: Abilitylike, P: Pool + Component> PoolBundle<A, P> {
+    /// Creates a new [`PoolBundle`] from a `pool` and the `ability_costs` paid from it.
+    #[must_use]
+    pub fn new(pool: P, ability_costs: AbilityCosts<A, P>) -> Self {
+        PoolBundle {
+            pool,
+            ability_costs,
+        }
+    }
+}
+
+// Cannot use derive(Default), as it forces an undesirable bound on our generics
+impl<A: Abilitylike, P: Pool + Component + Default> Default for PoolBundle<A, P> {
+    fn default() -> Self {
+        PoolBundle {
+            pool: P::default(),
+            ability_costs: AbilityCosts::default(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -297,6 +1115,20 @@ mod tests {
         assert_eq!(mana_pool.current(), ManaPool::ZERO);
     }
 
+    #[test]
+    fn expend_per_second_stops_at_zero() {
+        let mut mana_pool = ManaPool::new_full(Mana(10.), Mana(0.));
+
+        let spent = mana_pool.expend_per_second(Mana(4.), Duration::from_secs(2));
+        assert_eq!(spent, Mana(8.));
+        assert_eq!(mana_pool.current(), Mana(2.));
+
+        // Only the remaining mana can be spent
+        let spent = mana_pool.expend_per_second(Mana(4.), Duration::from_secs(2));
+        assert_eq!(spent, Mana(2.));
+        assert_eq!(mana_pool.current(), ManaPool::ZERO);
+    }
+
     #[test]
     fn set_pool_cannot_exceed_max() {
         let max_mana = Mana(10.);
@@ -305,6 +1137,47 @@ mod tests {
         assert_eq!(mana_pool.current(), max_mana);
     }
 
+    #[test]
+    fn pool_fractions() {
+        let mut mana_pool = ManaPool::new(Mana(3.), Mana(12.), Mana(0.));
+        assert_eq!(mana_pool.fraction(), 0.25);
+        assert!(!mana_pool.is_full());
+        assert!(!mana_pool.is_empty());
+
+        assert_eq!(mana_pool.set_fraction(0.5), Mana(6.));
+        assert_eq!(mana_pool.set_fraction(2.0), Mana(12.));
+        assert!(mana_pool.is_full());
+        assert_eq!(mana_pool.set_fraction(f32::NAN), Mana(12.));
+        assert_eq!(mana_pool.set_fraction(-1.0), Mana(0.));
+        assert!(mana_pool.is_empty());
+
+        let empty_pool = ManaPool::new_empty(Mana(0.), Mana(0.));
+        assert_eq!(empty_pool.fraction(), 0.0);
+    }
+
+    #[test]
+    fn deficit_reports_missing_resources() {
+        use crate as leafwing_abilities;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy)]
+        enum Action {
+            Fireball,
+            Blink,
+            Walk,
+        }
+
+        let costs = AbilityCosts::<Action, ManaPool>::new([
+            (Action::Fireball, Mana(8.)),
+            (Action::Blink, Mana(2.)),
+        ]);
+        let mana_pool = ManaPool::new(Mana(5.), Mana(10.), Mana(0.));
+
+        assert_eq!(costs.deficit(Action::Fireball, &mana_pool), Some(Mana(3.)));
+        assert_eq!(costs.deficit(Action::Blink, &mana_pool), None);
+        assert_eq!(costs.deficit(Action::Walk, &mana_pool), None);
+    }
+
     #[test]
     fn reducing_max_decreases_current() {
         let mut mana_pool = ManaPool::new_full(Mana(10.), Mana(0.));
@@ -318,7 +1191,31 @@ mod tests {
         let mut mana_pool = ManaPool::new_full(Mana(10.), Mana(0.));
         let result = mana_pool.set_max(Mana(-7.));
         assert_eq!(mana_pool.max(), Mana(10.));
-        assert_eq!(result, Err(MaxPoolLessThanZero))
+        assert_eq!(result, Err(PoolError::MaxLessThanZero))
+    }
+
+    #[test]
+    fn non_finite_quantities_are_rejected() {
+        let mut mana_pool = ManaPool::new(Mana(5.), Mana(10.), Mana(0.));
+
+        assert_eq!(mana_pool.set_max(Mana(f32::NAN)), Err(PoolError::NonFinite));
+        assert_eq!(
+            mana_pool.set_max(Mana(f32::INFINITY)),
+            Err(PoolError::NonFinite)
+        );
+        assert_eq!(mana_pool.max(), Mana(10.));
+
+        assert_eq!(
+            mana_pool.try_set_current(Mana(f32::NAN)),
+            Err(PoolError::NonFinite)
+        );
+        assert_eq!(
+            mana_pool.try_set_current(Mana(f32::NEG_INFINITY)),
+            Err(PoolError::NonFinite)
+        );
+        assert_eq!(mana_pool.current(), Mana(5.));
+
+        assert_eq!(mana_pool.try_set_current(Mana(20.)), Ok(Mana(10.)));
     }
 
     #[test]
@@ -330,7 +1227,7 @@ mod tests {
         assert_eq!(mana_pool.current(), Mana(1.));
         assert_eq!(
             mana_pool.expend(Mana(5.)),
-            Err(CannotUseAbility::PoolInsufficient)
+            Err(CannotUseAbility::PoolInsufficient { deficit: 4. })
         );
     }
 
@@ -342,4 +1239,207 @@ mod tests {
 
         assert!((mana_pool.current() - expected).0.abs() < f32::EPSILON);
     }
+
+    #[test]
+    fn deltas_can_heal_or_damage() {
+        use crate::premade_pools::life::{Life, LifePool};
+
+        let mut life_pool = LifePool::new_full(Life(10.), Life(0.));
+        let lifesteal = Life(4.);
+        assert_eq!(life_pool.apply_delta(-lifesteal), Life(-4.));
+        assert_eq!(life_pool.current(), Life(6.));
+
+        // The pool is clamped in both directions
+        assert_eq!(life_pool.apply_delta(Life(10.)), Life(4.));
+        assert_eq!(life_pool.current(), Life(10.));
+        assert_eq!(life_pool.apply_delta(Life(-15.)), Life(-10.));
+        assert_eq!(life_pool.current(), Life(0.));
+
+        let mut mana_pool = ManaPool::new_full(Mana(10.), Mana(0.));
+        assert_eq!(mana_pool.apply_delta(-Mana(2.5)), Mana(-2.5));
+        assert_eq!(mana_pool.current(), Mana(7.5));
+    }
+
+    #[test]
+    fn regeneration_can_follow_a_curve() {
+        /// Regenerates faster the longer it has gone undamaged, up to three times its base rate.
+        struct RampingPool {
+            current: Mana,
+            max: Mana,
+            regen_per_second: Mana,
+            time_since_damaged: Option<Duration>,
+        }
+
+        impl Pool for RampingPool {
+            type Quantity = Mana;
+            const ZERO: Mana = Mana(0.);
+
+            fn new(current: Mana, max: Mana, regen_per_second: Mana) -> Self {
+                RampingPool {
+                    current,
+                    max,
+                    regen_per_second,
+                    time_since_damaged: None,
+                }
+            }
+
+            fn current(&self) -> Mana {
+                self.current
+            }
+
+            fn set_current(&mut self, new_quantity: Mana) -> Mana {
+                self.current = Mana(new_quantity.0.clamp(0., self.max.0));
+                self.current
+            }
+
+            fn max(&self) -> Mana {
+                self.max
+            }
+
+            fn set_max(&mut self, new_max: Mana) -> Result<(), PoolError> {
+                self.max = new_max;
+                Ok(())
+            }
+
+            fn regen_per_second(&self) -> Mana {
+                self.regen_per_second
+            }
+
+            fn set_regen_per_second(&mut self, new_regen_per_second: Mana) {
+                self.regen_per_second = new_regen_per_second;
+            }
+
+            fn regen_amount(
+                &self,
+                delta_time: Duration,
+                time_since_damaged: Option<Duration>,
+            ) -> Mana {
+                let ramp = match time_since_damaged {
+                    Some(elapsed) => (1. + elapsed.as_secs_f32()).min(3.),
+                    None => 3.,
+                };
+                self.regen_per_second * (delta_time.as_secs_f32() * ramp)
+            }
+
+            fn time_since_damaged(&self) -> Option<Duration> {
+                self.time_since_damaged
+            }
+        }
+
+        let mut pool = RampingPool::new_empty(Mana(100.), Mana(1.));
+        pool.time_since_damaged = Some(Duration::ZERO);
+        pool.regenerate(Duration::from_secs(1));
+        assert_eq!(pool.current(), Mana(1.));
+
+        pool.time_since_damaged = Some(Duration::from_secs(1));
+        pool.regenerate(Duration::from_secs(1));
+        assert_eq!(pool.current(), Mana(3.));
+
+        // The multiplier still applies on top of the curve
+        pool.time_since_damaged = Some(Duration::from_secs(10));
+        pool.regenerate_scaled(Duration::from_secs(1), 0.5);
+        assert_eq!(pool.current(), Mana(4.5));
+    }
+
+    #[test]
+    fn damage_blocks_undamaged_abilities_until_window_elapses() {
+        use crate as leafwing_abilities;
+        use crate::premade_pools::life::{Life, LifePool};
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy)]
+        enum Action {
+            Stealth,
+        }
+
+        let requirements = UndamagedRequirements::new([(Action::Stealth, Duration::from_secs(2))]);
+        let mut life_pool = LifePool::new_full(Life(10.), Life(0.));
+        assert_eq!(life_pool.time_since_damaged(), None);
+        assert!(requirements
+            .check(Action::Stealth, Some(&life_pool))
+            .is_ok());
+
+        life_pool.expend(Life(3.)).unwrap();
+        assert_eq!(
+            requirements.check(Action::Stealth, Some(&life_pool)),
+            Err(CannotUseAbility::RecentlyDamaged)
+        );
+
+        life_pool.regenerate(Duration::from_secs(1));
+        assert_eq!(
+            requirements.check(Action::Stealth, Some(&life_pool)),
+            Err(CannotUseAbility::RecentlyDamaged)
+        );
+
+        life_pool.regenerate(Duration::from_secs(1));
+        assert!(requirements
+            .check(Action::Stealth, Some(&life_pool))
+            .is_ok());
+    }
+
+    #[test]
+    fn cooldown_resets_once_per_fill() {
+        use crate as leafwing_abilities;
+        use crate::cooldown::{Cooldown, CooldownState};
+        use crate::systems::reset_cooldowns_on_pool_full;
+        use leafwing_input_manager::Actionlike;
+
+        #[derive(Actionlike, Abilitylike, Clone, Copy)]
+        enum Action {
+            Overcharge,
+        }
+
+        let mut world = World::new();
+        let entity = world
+            .spawn((
+                ManaPool::new_empty(Mana(10.), Mana(0.)),
+                CooldownState::new([(Action::Overcharge, Cooldown::from_secs(10.))]),
+                CooldownResetOnPoolFull::<Action, ManaPool>::new(Action::Overcharge),
+            ))
+            .id();
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(reset_cooldowns_on_pool_full::<Action, ManaPool>);
+
+        let trigger = |world: &mut World| {
+            let mut cooldowns = world.get_mut::<CooldownState<Action>>(entity).unwrap();
+            cooldowns.trigger(Action::Overcharge).unwrap();
+        };
+        let ready = |world: &World| {
+            let cooldowns = world.get::<CooldownState<Action>>(entity).unwrap();
+            cooldowns.ready(Action::Overcharge).is_ok()
+        };
+
+        trigger(&mut world);
+        schedule.run(&mut world);
+        assert!(!ready(&world));
+
+        // Filling the pool resets the cooldown
+        world
+            .get_mut::<ManaPool>(entity)
+            .unwrap()
+            .replenish(Mana(10.));
+        schedule.run(&mut world);
+        assert!(ready(&world));
+
+        // Staying full does not reset it again
+        trigger(&mut world);
+        schedule.run(&mut world);
+        assert!(!ready(&world));
+
+        // But draining and refilling the pool does
+        world
+            .get_mut::<ManaPool>(entity)
+            .unwrap()
+            .expend(Mana(1.))
+            .unwrap();
+        schedule.run(&mut world);
+        assert!(!ready(&world));
+        world
+            .get_mut::<ManaPool>(entity)
+            .unwrap()
+            .replenish(Mana(1.));
+        schedule.run(&mut world);
+        assert!(ready(&world));
+    }
 }
@@ -3,11 +3,37 @@
 //! These can be annoying due to orphan rules that prevent you from implementing your own methods,
 //! so feel free to copy-paste them (without attribution) into your own source to make new variants.
 
-use crate::pool::{MaxPoolLessThanZero, Pool};
-use bevy::prelude::{Component, Resource};
-use core::ops::{Div, Mul};
+use crate::pool::{OverflowPolicy, Pool, PoolError};
+use bevy::prelude::{Component, ReflectComponent, ReflectResource, Resource};
+use bevy::reflect::{FromReflect, Reflect};
+use bevy::utils::Duration;
+use core::ops::{Div, Mul, Neg};
 use derive_more::{Add, AddAssign, Sub, SubAssign};
 
+/// Returns how much of `delta_time` is spent regenerating, once `regen_delay` has passed since the pool was last reduced.
+///
+/// `time_since_reduced` is measured at the start of the tick, and is [`None`] if the pool has never been reduced.
+fn time_regenerating(
+    time_since_reduced: Option<Duration>,
+    delta_time: Duration,
+    regen_delay: Duration,
+) -> Duration {
+    match time_since_reduced {
+        Some(elapsed) => elapsed
+            .saturating_add(delta_time)
+            .saturating_sub(regen_delay.max(elapsed)),
+        None => delta_time,
+    }
+}
+
+/// Returns the time remaining until a pool with the given `regen_delay` starts regenerating again.
+fn time_until_regen(time_since_reduced: Option<Duration>, regen_delay: Duration) -> Duration {
+    match time_since_reduced {
+        Some(elapsed) => regen_delay.saturating_sub(elapsed),
+        None => Duration::ZERO,
+    }
+}
+
 /// A premade resource pool for life (aka health, hit points or HP).
 pub mod life {
     use super::*;
@@ -15,22 +41,137 @@ pub mod life {
     /// The amount of life available to a unit.
     /// If they lose it all, they die or pass out.
     ///
+    /// Life can be protected by a temporary shield, added with [`LifePool::add_shield`].
+    /// Any reduction to the current life is absorbed by the shield first.
+    /// The shield does not regenerate, and cannot exceed its own maximum, set by [`LifePool::set_max_shield`].
+    ///
     /// This is intended to be stored as a component on each entity.
-    #[derive(Debug, Clone, PartialEq, Component, Resource)]
+    ///
+    /// The [`Default`] pool is empty, with a maximum of zero: it is only intended for use with reflection.
+    #[derive(Debug, Clone, PartialEq, Default, Component, Resource, Reflect)]
+    #[reflect(Component, Resource)]
     pub struct LifePool {
         /// The current life.
         current: Life,
         /// The maximum life that can be stored.
         max: Life,
         /// The amount of life regenerated per second.
+        ///
+        /// If negative, life is drained instead, down to zero.
         pub regen_per_second: Life,
+        /// How long after life is lost before it starts regenerating again.
+        pub regen_delay: Duration,
+        /// The time since life was last lost, if ever.
+        time_since_damaged: Option<Duration>,
+        /// The current shield, which absorbs damage before life is lost.
+        shield: Life,
+        /// The maximum shield that can be stored.
+        max_shield: Life,
+        /// Can the current life exceed the max life, such as for overhealing?
+        ///
+        /// Defaults to [`OverflowPolicy::Clamp`].
+        pub overflow: OverflowPolicy<Life>,
+    }
+
+    impl LifePool {
+        /// The time remaining until life starts regenerating again, according to the [`regen_delay`](Self::regen_delay).
+        ///
+        /// This is [`Duration::ZERO`] if life is currently regenerating.
+        #[inline]
+        #[must_use]
+        pub fn time_until_regen(&self) -> Duration {
+            time_until_regen(self.time_since_damaged, self.regen_delay)
+        }
+
+        /// The current shield, which absorbs damage before life is lost.
+        #[inline]
+        #[must_use]
+        pub fn current_shield(&self) -> Life {
+            self.shield
+        }
+
+        /// The maximum shield that can be stored.
+        ///
+        /// This is zero by default, preventing any shield from being added.
+        #[inline]
+        #[must_use]
+        pub fn max_shield(&self) -> Life {
+            self.max_shield
+        }
+
+        /// The current life plus the current shield, for display purposes.
+        ///
+        /// Use [`Pool::current`] to exclude the shield.
+        #[inline]
+        #[must_use]
+        pub fn current_with_shield(&self) -> Life {
+            self.current + self.shield
+        }
+
+        /// Deals `amount` of damage to this pool, which is absorbed by the shield before life is lost.
+        ///
+        /// Life never drops below zero.
+        /// Returns the damage that was actually dealt to the shield and life combined,
+        /// which is less than `amount` in the case of overkill.
+        pub fn damage(&mut self, amount: Life) -> Life {
+            let previous = self.current_with_shield();
+            self.subtract(amount);
+            previous - self.current_with_shield()
+        }
+
+        /// Heals this pool by `amount`, up to its max life.
+        ///
+        /// Returns the life that was actually restored, which is less than `amount` in the case of overhealing.
+        pub fn heal(&mut self, amount: Life) -> Life {
+            self.add(amount)
+        }
+
+        /// Adds `amount` to the current shield, up to the [`max_shield`](Self::max_shield).
+        ///
+        /// Non-finite amounts are ignored. The shield that was actually set is returned.
+        pub fn add_shield(&mut self, amount: Life) -> Life {
+            if amount.0.is_finite() {
+                self.shield = Life((self.shield + amount).0.clamp(0., self.max_shield.0));
+            }
+
+            self.shield
+        }
+
+        /// Sets the maximum shield that can be stored.
+        ///
+        /// The current shield will be reduced to the new max if necessary.
+        ///
+        /// Has no effect if `new_max_shield` is negative or not finite,
+        /// returning [`PoolError::MaxLessThanZero`] or [`PoolError::NonFinite`] respectively.
+        pub fn set_max_shield(&mut self, new_max_shield: Life) -> Result<(), PoolError> {
+            if !new_max_shield.0.is_finite() {
+                Err(PoolError::NonFinite)
+            } else if new_max_shield < Self::ZERO {
+                Err(PoolError::MaxLessThanZero)
+            } else {
+                self.max_shield = new_max_shield;
+                self.shield = Life(self.shield.0.min(new_max_shield.0));
+                Ok(())
+            }
+        }
     }
 
     /// A quantity of life, used to modify a [`LifePool`].
     ///
     /// This can be used for damage computations, life regeneration, healing and so on.
     #[derive(
-        Debug, Clone, Copy, PartialEq, PartialOrd, Default, Add, Sub, AddAssign, SubAssign,
+        Debug,
+        Clone,
+        Copy,
+        PartialEq,
+        PartialOrd,
+        Default,
+        Add,
+        Sub,
+        AddAssign,
+        SubAssign,
+        Reflect,
+        FromReflect,
     )]
     pub struct Life(pub f32);
 
@@ -50,6 +191,14 @@ pub mod life {
         }
     }
 
+    impl Neg for Life {
+        type Output = Life;
+
+        fn neg(self) -> Life {
+            Life(-self.0)
+        }
+    }
+
     impl Div<f32> for Life {
         type Output = Life;
 
@@ -58,6 +207,20 @@ pub mod life {
         }
     }
 
+    impl Div<Life> for Life {
+        type Output = f32;
+
+        fn div(self, rhs: Life) -> f32 {
+            self.0 / rhs.0
+        }
+    }
+
+    impl From<Life> for f32 {
+        fn from(quantity: Life) -> f32 {
+            quantity.0
+        }
+    }
+
     impl Pool for LifePool {
         type Quantity = Life;
         const ZERO: Life = Life(0.);
@@ -71,6 +234,11 @@ pub mod life {
                 current,
                 max,
                 regen_per_second,
+                regen_delay: Duration::ZERO,
+                time_since_damaged: None,
+                shield: Life(0.),
+                max_shield: Life(0.),
+                overflow: OverflowPolicy::Clamp,
             }
         }
 
@@ -78,8 +246,35 @@ pub mod life {
             self.current
         }
 
+        /// Sets the current life, clamped between zero and the max life.
+        ///
+        /// If the [`overflow`](LifePool::overflow) policy is [`OverflowPolicy::Decay`], life may exceed the max instead.
+        /// Any reduction to the current life is absorbed by the shield first.
+        /// If this reduces the current life or the shield, the time since this pool was last damaged is reset.
+        ///
+        /// NaN is ignored, and infinite quantities are clamped, so the current life always remains finite.
         fn set_current(&mut self, new_quantity: Self::Quantity) -> Self::Quantity {
-            let actual_value = Life(new_quantity.0.clamp(0., self.max.0));
+            if new_quantity.0.is_nan() {
+                return self.current;
+            }
+
+            let mut new_quantity = new_quantity;
+            let mut absorbed = Life(0.);
+            if new_quantity < self.current {
+                absorbed = Life((self.current - new_quantity).0.min(self.shield.0));
+                self.shield -= absorbed;
+                new_quantity += absorbed;
+            }
+
+            let upper_bound = match self.overflow {
+                OverflowPolicy::Clamp => self.max.0,
+                OverflowPolicy::Decay { .. } => f32::MAX,
+            };
+            let actual_value = Life(new_quantity.0.clamp(0., upper_bound));
+            if actual_value < self.current || absorbed > Life(0.) {
+                self.time_since_damaged = Some(Duration::ZERO);
+            }
+
             self.current = actual_value;
             self.current
         }
@@ -88,9 +283,11 @@ pub mod life {
             self.max
         }
 
-        fn set_max(&mut self, new_max: Self::Quantity) -> Result<(), MaxPoolLessThanZero> {
-            if new_max < Self::ZERO {
-                Err(MaxPoolLessThanZero)
+        fn set_max(&mut self, new_max: Self::Quantity) -> Result<(), PoolError> {
+            if !new_max.0.is_finite() {
+                Err(PoolError::NonFinite)
+            } else if new_max < Self::ZERO {
+                Err(PoolError::MaxLessThanZero)
             } else {
                 self.max = new_max;
                 self.set_current(self.current);
@@ -105,6 +302,48 @@ pub mod life {
         fn set_regen_per_second(&mut self, new_regen_per_second: Self::Quantity) {
             self.regen_per_second = new_regen_per_second;
         }
+
+        /// Regenerates life according to the elapsed `delta_time` and `multiplier`,
+        /// and advances the time since this pool was last damaged.
+        ///
+        /// No life is regenerated until the [`regen_delay`](LifePool::regen_delay) has passed since life was last lost.
+        ///
+        /// A negative [`regen_per_second`](LifePool::regen_per_second) drains life continuously, such as for a bleed effect,
+        /// until it reaches zero.
+        /// Draining is not delayed, bypasses the shield and does not reset the time since this pool was last damaged.
+        ///
+        /// Any life in excess of the max decays according to the [`overflow`](LifePool::overflow) policy,
+        /// and life does not regenerate beyond the max.
+        fn regenerate_scaled(&mut self, delta_time: Duration, multiplier: f32) {
+            self.current = match self.overflow {
+                OverflowPolicy::Clamp => Life(self.current.0.min(self.max.0)),
+                OverflowPolicy::Decay { decay_per_second } => {
+                    let decayed = self.current - decay_per_second * delta_time.as_secs_f32();
+                    Life(decayed.0.max(self.max.0.min(self.current.0)))
+                }
+            };
+
+            let draining = self.regen_per_second < Self::ZERO;
+            let regen_time = match draining {
+                true => delta_time,
+                false => time_regenerating(self.time_since_damaged, delta_time, self.regen_delay),
+            };
+            let life_regained = self.regen_amount(regen_time, self.time_since_damaged) * multiplier;
+            if let Some(time_since_damaged) = self.time_since_damaged.as_mut() {
+                *time_since_damaged = time_since_damaged.saturating_add(delta_time);
+            }
+
+            if draining {
+                let upper_bound = self.max.0.max(self.current.0);
+                self.current = Life((self.current + life_regained).0.clamp(0., upper_bound));
+            } else if self.current < self.max {
+                self.set_current(Life((self.current + life_regained).0.min(self.max.0)));
+            }
+        }
+
+        fn time_since_damaged(&self) -> Option<Duration> {
+            self.time_since_damaged
+        }
     }
 }
 
@@ -116,21 +355,56 @@ pub mod mana {
     /// Units must spend mana to cast spells according to their [`AbilityCosts<A, Mana>`](crate::pool::AbilityCosts) component.
     ///
     /// This is intended to be stored as a component on each entity.
-    #[derive(Debug, Clone, PartialEq, Component, Resource)]
+    ///
+    /// The [`Default`] pool is empty, with a maximum of zero: it is only intended for use with reflection.
+    #[derive(Debug, Clone, PartialEq, Default, Component, Resource, Reflect)]
+    #[reflect(Component, Resource)]
     pub struct ManaPool {
         /// The current mana.
         current: Mana,
         /// The maximum mana that can be stored.
         max: Mana,
         /// The amount of mana regenerated per second.
+        ///
+        /// If negative, mana is drained instead, down to zero.
         pub regen_per_second: Mana,
+        /// How long after mana is spent before it starts regenerating again.
+        pub regen_delay: Duration,
+        /// The time since mana was last spent, if ever.
+        time_since_spent: Option<Duration>,
+        /// Can the current mana exceed the max mana?
+        ///
+        /// Defaults to [`OverflowPolicy::Clamp`].
+        pub overflow: OverflowPolicy<Mana>,
+    }
+
+    impl ManaPool {
+        /// The time remaining until mana starts regenerating again, according to the [`regen_delay`](Self::regen_delay).
+        ///
+        /// This is [`Duration::ZERO`] if mana is currently regenerating.
+        #[inline]
+        #[must_use]
+        pub fn time_until_regen(&self) -> Duration {
+            time_until_regen(self.time_since_spent, self.regen_delay)
+        }
     }
 
     /// A quantity of mana, used to modify a [`ManaPool`].
     ///
     /// This can be used for ability costs, mana regeneration and so on.
     #[derive(
-        Debug, Clone, Copy, PartialEq, PartialOrd, Default, Add, Sub, AddAssign, SubAssign,
+        Debug,
+        Clone,
+        Copy,
+        PartialEq,
+        PartialOrd,
+        Default,
+        Add,
+        Sub,
+        AddAssign,
+        SubAssign,
+        Reflect,
+        FromReflect,
     )]
     pub struct Mana(pub f32);
 
@@ -150,6 +424,14 @@ pub mod mana {
         }
     }
 
+    impl Neg for Mana {
+        type Output = Mana;
+
+        fn neg(self) -> Mana {
+            Mana(-self.0)
+        }
+    }
+
     impl Div<f32> for Mana {
         type Output = Mana;
 
@@ -158,6 +440,20 @@ pub mod mana {
         }
     }
 
+    impl Div<Mana> for Mana {
+        type Output = f32;
+
+        fn div(self, rhs: Mana) -> f32 {
+            self.0 / rhs.0
+        }
+    }
+
+    impl From<Mana> for f32 {
+        fn from(quantity: Mana) -> f32 {
+            quantity.0
+        }
+    }
+
     impl Pool for ManaPool {
         type Quantity = Mana;
         const ZERO: Mana = Mana(0.);
@@ -171,6 +467,9 @@ pub mod mana {
                 current,
                 max,
                 regen_per_second,
+                regen_delay: Duration::ZERO,
+                time_since_spent: None,
+                overflow: OverflowPolicy::Clamp,
             }
         }
 
@@ -178,8 +477,26 @@ pub mod mana {
             self.current
         }
 
+        /// Sets the current mana, clamped between zero and the max mana.
+        ///
+        /// If the [`overflow`](ManaPool::overflow) policy is [`OverflowPolicy::Decay`], mana may exceed the max instead.
+        /// If this reduces the current mana, the time since mana was last spent is reset.
+        ///
+        /// NaN is ignored, and infinite quantities are clamped, so the current mana always remains finite.
         fn set_current(&mut self, new_quantity: Self::Quantity) -> Self::Quantity {
-            let actual_value = Mana(new_quantity.0.clamp(0., self.max.0));
+            if new_quantity.0.is_nan() {
+                return self.current;
+            }
+
+            let upper_bound = match self.overflow {
+                OverflowPolicy::Clamp => self.max.0,
+                OverflowPolicy::Decay { .. } => f32::MAX,
+            };
+            let actual_value = Mana(new_quantity.0.clamp(0., upper_bound));
+            if actual_value < self.current {
+                self.time_since_spent = Some(Duration::ZERO);
+            }
+
             self.current = actual_value;
             self.current
         }
@@ -188,9 +505,495 @@ pub mod mana {
             self.max
         }
 
-        fn set_max(&mut self, new_max: Self::Quantity) -> Result<(), MaxPoolLessThanZero> {
-            if new_max < Self::ZERO {
-                Err(MaxPoolLessThanZero)
+        fn set_max(&mut self, new_max: Self::Quantity) -> Result<(), PoolError> {
+            if !new_max.0.is_finite() {
+                Err(PoolError::NonFinite)
+            } else if new_max < Self::ZERO {
+                Err(PoolError::MaxLessThanZero)
+            } else {
+                self.max = new_max;
+                self.set_current(self.current);
+                Ok(())
+            }
+        }
+
+        fn regen_per_second(&self) -> Self::Quantity {
+            self.regen_per_second
+        }
+
+        fn set_regen_per_second(&mut self, new_regen_per_second: Self::Quantity) {
+            self.regen_per_second = new_regen_per_second;
+        }
+
+        /// Regenerates mana according to the elapsed `delta_time` and `multiplier`,
+        /// and advances the time since mana was last spent.
+        ///
+        /// No mana is regenerated until the [`regen_delay`](ManaPool::regen_delay) has passed since mana was last spent.
+        ///
+        /// A negative [`regen_per_second`](ManaPool::regen_per_second) drains mana continuously until it reaches zero.
+        /// Draining is not delayed, and does not reset the time since mana was last spent.
+        ///
+        /// Any mana in excess of the max decays according to the [`overflow`](ManaPool::overflow) policy,
+        /// and mana does not regenerate beyond the max.
+        fn regenerate_scaled(&mut self, delta_time: Duration, multiplier: f32) {
+            self.current = match self.overflow {
+                OverflowPolicy::Clamp => Mana(self.current.0.min(self.max.0)),
+                OverflowPolicy::Decay { decay_per_second } => {
+                    let decayed = self.current - decay_per_second * delta_time.as_secs_f32();
+                    Mana(decayed.0.max(self.max.0.min(self.current.0)))
+                }
+            };
+
+            let draining = self.regen_per_second < Self::ZERO;
+            let regen_time = match draining {
+                true => delta_time,
+                false => time_regenerating(self.time_since_spent, delta_time, self.regen_delay),
+            };
+            let mana_regained =
+                self.regen_amount(regen_time, self.time_since_damaged()) * multiplier;
+            if let Some(time_since_spent) = self.time_since_spent.as_mut() {
+                *time_since_spent = time_since_spent.saturating_add(delta_time);
+            }
+
+            if draining {
+                let upper_bound = self.max.0.max(self.current.0);
+                self.current = Mana((self.current + mana_regained).0.clamp(0., upper_bound));
+            } else if self.current < self.max {
+                self.set_current(Mana((self.current + mana_regained).0.min(self.max.0)));
+            }
+        }
+    }
+}
+
+/// A premade resource pool for stamina, measured in whole points.
+pub mod stamina {
+    use super::*;
+    use core::ops::{Add, AddAssign, Sub, SubAssign};
+
+    /// The amount of stamina available to a unit.
+    /// Units spend discrete points of stamina on actions like dodging or sprinting.
+    ///
+    /// Stamina regenerates and [drains over time](Pool::expend_per_second) in whole points: fractional progress towards the next point is stored between ticks.
+    ///
+    /// This is intended to be stored as a component on each entity.
+    ///
+    /// The [`Default`] pool is empty, with a maximum of zero: it is only intended for use with reflection.
+    #[derive(Debug, Clone, PartialEq, Default, Component, Resource, Reflect)]
+    #[reflect(Component, Resource)]
+    pub struct StaminaPool {
+        /// The current stamina.
+        current: Stamina,
+        /// The maximum stamina that can be stored.
+        max: Stamina,
+        /// The amount of stamina regenerated per second.
+        pub regen_per_second: Stamina,
+        /// The progress towards regenerating the next point of stamina, between `0.0` and `1.0`.
+        regen_progress: f32,
+        /// The progress towards spending the next point of stamina with [`Pool::expend_per_second`], between `0.0` and `1.0`.
+        expend_progress: f32,
+    }
+
+    impl StaminaPool {
+        /// The progress towards regenerating the next point of stamina, between `0.0` and `1.0`.
+        #[inline]
+        #[must_use]
+        pub fn regen_progress(&self) -> f32 {
+            self.regen_progress
+        }
+    }
+
+    /// A quantity of stamina, used to modify a [`StaminaPool`].
+    ///
+    /// Arithmetic on stamina saturates: it can never be negative, or overflow.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Reflect, FromReflect,
+    )]
+    pub struct Stamina(pub u32);
+
+    impl Add for Stamina {
+        type Output = Stamina;
+
+        fn add(self, rhs: Stamina) -> Stamina {
+            Stamina(self.0.saturating_add(rhs.0))
+        }
+    }
+
+    impl Sub for Stamina {
+        type Output = Stamina;
+
+        fn sub(self, rhs: Stamina) -> Stamina {
+            Stamina(self.0.saturating_sub(rhs.0))
+        }
+    }
+
+    impl AddAssign for Stamina {
+        fn add_assign(&mut self, rhs: Stamina) {
+            *self = *self + rhs;
+        }
+    }
+
+    impl SubAssign for Stamina {
+        fn sub_assign(&mut self, rhs: Stamina) {
+            *self = *self - rhs;
+        }
+    }
+
+    /// Multiplies by a fractional factor, rounding down.
+    impl Mul<f32> for Stamina {
+        type Output = Stamina;
+
+        fn mul(self, rhs: f32) -> Stamina {
+            // Float to integer casts saturate, and map NaN to zero
+            Stamina((self.0 as f32 * rhs) as u32)
+        }
+    }
+
+    impl Mul<u32> for Stamina {
+        type Output = Stamina;
+
+        fn mul(self, rhs: u32) -> Stamina {
+            Stamina(self.0.saturating_mul(rhs))
+        }
+    }
+
+    impl Mul<Stamina> for u32 {
+        type Output = Stamina;
+
+        fn mul(self, rhs: Stamina) -> Stamina {
+            Stamina(self.saturating_mul(rhs.0))
+        }
+    }
+
+    /// Divides by a fractional factor, rounding down.
+    impl Div<f32> for Stamina {
+        type Output = Stamina;
+
+        fn div(self, rhs: f32) -> Stamina {
+            Stamina((self.0 as f32 / rhs) as u32)
+        }
+    }
+
+    impl Div<Stamina> for Stamina {
+        type Output = f32;
+
+        fn div(self, rhs: Stamina) -> f32 {
+            self.0 as f32 / rhs.0 as f32
+        }
+    }
+
+    impl From<Stamina> for f32 {
+        fn from(quantity: Stamina) -> f32 {
+            quantity.0 as f32
+        }
+    }
+
+    impl Pool for StaminaPool {
+        type Quantity = Stamina;
+        const ZERO: Stamina = Stamina(0);
+
+        fn new(
+            current: Self::Quantity,
+            max: Self::Quantity,
+            regen_per_second: Self::Quantity,
+        ) -> Self {
+            StaminaPool {
+                current,
+                max,
+                regen_per_second,
+                regen_progress: 0.,
+                expend_progress: 0.,
+            }
+        }
+
+        fn current(&self) -> Self::Quantity {
+            self.current
+        }
+
+        fn set_current(&mut self, new_quantity: Self::Quantity) -> Self::Quantity {
+            self.current = new_quantity.min(self.max);
+            self.current
+        }
+
+        fn max(&self) -> Self::Quantity {
+            self.max
+        }
+
+        /// Sets the max stamina. Integer quantities are always valid, so this never fails.
+        fn set_max(&mut self, new_max: Self::Quantity) -> Result<(), PoolError> {
+            self.max = new_max;
+            self.set_current(self.current);
+            Ok(())
+        }
+
+        fn regen_per_second(&self) -> Self::Quantity {
+            self.regen_per_second
+        }
+
+        fn set_regen_per_second(&mut self, new_regen_per_second: Self::Quantity) {
+            self.regen_per_second = new_regen_per_second;
+        }
+
+        /// Regenerates stamina according to the elapsed `delta_time` and `multiplier`.
+        ///
+        /// Only whole points of stamina are regenerated: any remainder is carried over to the next tick.
+        /// No progress is stored while the pool is full.
+        fn regenerate_scaled(&mut self, delta_time: Duration, multiplier: f32) {
+            if self.current >= self.max {
+                self.regen_progress = 0.;
+                return;
+            }
+
+            let progress = self.regen_progress
+                + self.regen_per_second.0 as f32 * delta_time.as_secs_f32() * multiplier;
+            if !progress.is_finite() {
+                return;
+            }
+
+            let points_regained = progress.trunc();
+            self.replenish(Stamina(points_regained as u32));
+            self.regen_progress = match self.current >= self.max {
+                true => 0.,
+                false => progress - points_regained,
+            };
+        }
+
+        /// Drains stamina at `rate` per second for the elapsed `delta_time`.
+        ///
+        /// Only whole points of stamina are spent: any remainder is carried over to the next call,
+        /// so that slow drains still spend stamina when called every frame.
+        /// No progress is stored once the pool is empty.
+        fn expend_per_second(&mut self, rate: Stamina, delta_time: Duration) -> Stamina {
+            let progress = self.expend_progress + rate.0 as f32 * delta_time.as_secs_f32();
+            if !progress.is_finite() || progress <= 0. {
+                return Stamina(0);
+            }
+
+            let points_spent = progress.trunc();
+            let previous = self.current;
+            self.set_current(previous - Stamina(points_spent as u32));
+            self.expend_progress = match self.current == Stamina(0) {
+                true => 0.,
+                false => progress - points_spent,
+            };
+
+            previous - self.current
+        }
+    }
+}
+
+/// A premade resource pool for meters that float around a neutral midpoint, such as balance or poise.
+pub mod balance {
+    use super::*;
+    use crate::CannotUseAbility;
+
+    /// A meter that ranges between a configurable minimum and maximum, which may be negative.
+    ///
+    /// Unlike the other premade pools, the current value is clamped to `[min, max]` rather than `[0, max]`.
+    /// The minimum starts at [`Pool::ZERO`]: lower it with [`BalancedPool::set_min`].
+    /// Spending from this pool can take it below zero, down to its minimum.
+    ///
+    /// This is intended to be stored as a component on each entity.
+    ///
+    /// The [`Default`] pool has a minimum and maximum of zero: it is only intended for use with reflection.
+    ///
+    /// ```rust
+    /// use leafwing_abilities::pool::Pool;
+    /// use leafwing_abilities::premade_pools::balance::{Balance, BalancedPool};
+    ///
+    /// let mut poise = BalancedPool::new(Balance(0.), Balance(100.), Balance(0.));
+    /// poise.set_min(Balance(-100.)).unwrap();
+    ///
+    /// poise.subtract(Balance(150.));
+    /// assert_eq!(poise.current(), Balance(-100.));
+    /// assert!(poise.is_empty());
+    /// ```
+    #[derive(Debug, Clone, PartialEq, Default, Component, Resource, Reflect)]
+    #[reflect(Component, Resource)]
+    pub struct BalancedPool {
+        /// The current balance.
+        current: Balance,
+        /// The minimum balance that can be stored.
+        min: Balance,
+        /// The maximum balance that can be stored.
+        max: Balance,
+        /// The amount of balance regenerated per second.
+        ///
+        /// If negative, balance is drained instead, down to the minimum.
+        pub regen_per_second: Balance,
+    }
+
+    impl BalancedPool {
+        /// The minimum balance that can be stored.
+        #[inline]
+        #[must_use]
+        pub fn min(&self) -> Balance {
+            self.min
+        }
+
+        /// Sets the minimum balance that can be stored.
+        ///
+        /// The current value will be raised to the new min if necessary.
+        ///
+        /// Has no effect if `new_min` is greater than the [`max`](Pool::max) or is not finite,
+        /// returning [`PoolError::MinGreaterThanMax`] or [`PoolError::NonFinite`] respectively.
+        pub fn set_min(&mut self, new_min: Balance) -> Result<(), PoolError> {
+            if !new_min.0.is_finite() {
+                Err(PoolError::NonFinite)
+            } else if new_min > self.max {
+                Err(PoolError::MinGreaterThanMax)
+            } else {
+                self.min = new_min;
+                self.set_current(self.current);
+                Ok(())
+            }
+        }
+    }
+
+    /// A quantity of balance, used to modify a [`BalancedPool`].
+    ///
+    /// Unlike most pool quantities, the current balance of a pool may be negative.
+    #[derive(
+        Debug,
+        Clone,
+        Copy,
+        PartialEq,
+        PartialOrd,
+        Default,
+        Add,
+        Sub,
+        AddAssign,
+        SubAssign,
+        Reflect,
+        FromReflect,
+    )]
+    pub struct Balance(pub f32);
+
+    impl Mul<f32> for Balance {
+        type Output = Balance;
+
+        fn mul(self, rhs: f32) -> Balance {
+            Balance(self.0 * rhs)
+        }
+    }
+
+    impl Mul<Balance> for f32 {
+        type Output = Balance;
+
+        fn mul(self, rhs: Balance) -> Balance {
+            Balance(self * rhs.0)
+        }
+    }
+
+    impl From<Balance> for f32 {
+        fn from(quantity: Balance) -> f32 {
+            quantity.0
+        }
+    }
+
+    impl Neg for Balance {
+        type Output = Balance;
+
+        fn neg(self) -> Balance {
+            Balance(-self.0)
+        }
+    }
+
+    impl Div<f32> for Balance {
+        type Output = Balance;
+
+        fn div(self, rhs: f32) -> Balance {
+            Balance(self.0 / rhs)
+        }
+    }
+
+    impl Div<Balance> for Balance {
+        type Output = f32;
+
+        fn div(self, rhs: Balance) -> f32 {
+            self.0 / rhs.0
+        }
+    }
+
+    impl Pool for BalancedPool {
+        type Quantity = Balance;
+        const ZERO: Balance = Balance(0.);
+
+        fn new(
+            current: Self::Quantity,
+            max: Self::Quantity,
+            regen_per_second: Self::Quantity,
+        ) -> Self {
+            BalancedPool {
+                current,
+                min: Self::ZERO,
+                max,
+                regen_per_second,
+            }
+        }
+
+        fn current(&self) -> Self::Quantity {
+            self.current
+        }
+
+        /// The current balance as a fraction of the range between the [`min`](BalancedPool::min) and the max,
+        /// between `0.0` and `1.0`.
+        ///
+        /// Returns `0.0` if the min and max are equal.
+        fn fraction(&self) -> f32 {
+            if self.max <= self.min {
+                return 0.0;
+            }
+
+            ((self.current - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+        }
+
+        /// Sets the current balance to a `fraction` of the range between the [`min`](BalancedPool::min) and the max.
+        ///
+        /// The fraction is clamped between `0.0` and `1.0`, and non-finite fractions are ignored.
+        fn set_fraction(&mut self, fraction: f32) -> Self::Quantity {
+            if !fraction.is_finite() {
+                return self.current;
+            }
+
+            self.set_current(self.min + (self.max - self.min) * fraction.clamp(0.0, 1.0))
+        }
+
+        /// Is the current balance at its [`min`](BalancedPool::min)?
+        fn is_empty(&self) -> bool {
+            self.current <= self.min
+        }
+
+        /// Can `amount` be spent without taking the balance below its [`min`](BalancedPool::min)?
+        fn available(&self, amount: Self::Quantity) -> Result<(), CannotUseAbility> {
+            if self.current - amount >= self.min {
+                Ok(())
+            } else {
+                Err(CannotUseAbility::PoolInsufficient {
+                    deficit: (self.min - (self.current - amount)).into(),
+                })
+            }
+        }
+
+        /// Sets the current balance, clamped between the [`min`](BalancedPool::min) and the max.
+        fn set_current(&mut self, new_quantity: Self::Quantity) -> Self::Quantity {
+            self.current = Balance(new_quantity.0.clamp(self.min.0, self.max.0));
+            self.current
+        }
+
+        fn max(&self) -> Self::Quantity {
+            self.max
+        }
+
+        /// Sets the max balance.
+        ///
+        /// In addition to the usual checks, returns [`PoolError::MinGreaterThanMax`] if `new_max` is less than the [`min`](BalancedPool::min).
+        fn set_max(&mut self, new_max: Self::Quantity) -> Result<(), PoolError> {
+            if !new_max.0.is_finite() {
+                Err(PoolError::NonFinite)
+            } else if new_max < Self::ZERO {
+                Err(PoolError::MaxLessThanZero)
+            } else if new_max < self.min {
+                Err(PoolError::MinGreaterThanMax)
             } else {
                 self.max = new_max;
                 self.set_current(self.current);
@@ -198,6 +1001,20 @@ pub mod mana {
             }
         }
 
+        /// Drains balance at `rate` per second for the elapsed `delta_time`, down to the [`min`](BalancedPool::min).
+        fn expend_per_second(
+            &mut self,
+            rate: Self::Quantity,
+            delta_time: Duration,
+        ) -> Self::Quantity {
+            let requested = rate * delta_time.as_secs_f32();
+            if requested <= Self::ZERO {
+                return Self::ZERO;
+            }
+
+            self.subtract(requested)
+        }
+
         fn regen_per_second(&self) -> Self::Quantity {
             self.regen_per_second
         }
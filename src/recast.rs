@@ -0,0 +1,380 @@
+//! Recasts allow an ability to be used a second time shortly after it was first cast.
+//!
+//! Placed traps that are recast to detonate, or dashes that can be recast to return to their origin
+//! are common examples.
+//! While the recast window of an ability is open, triggering it performs the recast instead of a fresh cast,
+//! and does not spend charges, cooldowns or resources.
+
+use bevy::ecs::prelude::{Component, Entity, Resource};
+use bevy::utils::Duration;
+use std::marker::PhantomData;
+
+use crate::Abilitylike;
+
+/// A component / resource that stores the [`Recast`] for each [`Abilitylike`] action of type `A`.
+///
+/// Actions without a [`Recast`] can never be recast.
+///
+/// ```rust
+/// use bevy::utils::Duration;
+/// use leafwing_abilities::prelude::*;
+/// use leafwing_abilities::recast::RecastExpiry;
+/// use leafwing_input_manager::Actionlike;
+///
+/// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+/// enum Action {
+///     PlaceTrap,
+/// }
+///
+/// let mut recasts = RecastState::new([(
+///     Action::PlaceTrap,
+///     Recast::new(Duration::from_secs(3), RecastExpiry::Resolve),
+/// )]);
+///
+/// // The first cast opens the recast window
+/// recasts.open(Action::PlaceTrap);
+/// assert!(recasts.in_window(Action::PlaceTrap));
+///
+/// // Casting again within the window performs the recast, closing the window
+/// assert!(recasts.recast(Action::PlaceTrap));
+/// assert!(!recasts.in_window(Action::PlaceTrap));
+/// ```
+#[derive(Resource, Component, Clone, PartialEq, Eq, Debug)]
+pub struct RecastState<A: Abilitylike> {
+    /// The underlying [`Recast`], stored in [`Actionlike::variants`] order.
+    recast_vec: Vec<Option<Recast>>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Abilitylike> Default for RecastState<A> {
+    fn default() -> Self {
+        RecastState {
+            recast_vec: A::variants().map(|_| None).collect(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Abilitylike> RecastState<A> {
+    /// Creates a new [`RecastState`] from an iterator of `(action, recast)` pairs
+    ///
+    /// If a [`Recast`] is not provided for an action, that action can never be recast.
+    ///
+    /// To create an empty [`RecastState`] struct, use the [`Default::default`] method instead.
+    #[must_use]
+    pub fn new(action_recast_pairs: impl IntoIterator<Item = (A, Recast)>) -> Self {
+        let mut recast_state = RecastState::default();
+        for (action, recast) in action_recast_pairs.into_iter() {
+            recast_state.set(action, recast);
+        }
+        recast_state
+    }
+
+    /// Is the recast window of `action` currently open?
+    ///
+    /// Returns `false` if the underlying [`Recast`] is [`None`].
+    #[inline]
+    #[must_use]
+    pub fn in_window(&self, action: A) -> bool {
+        if let Some(recast) = self.get(action) {
+            recast.is_open()
+        } else {
+            false
+        }
+    }
+
+    /// Opens the recast window of `action`, if it can be recast.
+    ///
+    /// This should be called when the ability is first cast.
+    #[inline]
+    pub fn open(&mut self, action: A) {
+        if let Some(recast) = self.get_mut(action) {
+            recast.open();
+        }
+    }
+
+    /// Recasts `action` if its recast window is open, closing the window.
+    ///
+    /// Returns `true` if the recast was performed.
+    /// If the window was not open, `false` is returned and this call has no effect.
+    #[inline]
+    pub fn recast(&mut self, action: A) -> bool {
+        if let Some(recast) = self.get_mut(action) {
+            recast.close()
+        } else {
+            false
+        }
+    }
+
+    /// Advances each underlying [`Recast`] window according to the elapsed `delta_time`.
+    ///
+    /// Calls `on_expiry` once for each action whose recast window closed as a result.
+    pub fn tick(&mut self, delta_time: Duration, mut on_expiry: impl FnMut(A, RecastExpiry)) {
+        for action in A::variants() {
+            if let Some(recast) = self.get_mut(action.clone()) {
+                if let Some(expiry) = recast.tick(delta_time) {
+                    on_expiry(action, expiry);
+                }
+            }
+        }
+    }
+
+    /// Returns a reference to the underlying [`Recast`] for `action`, if set.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, action: A) -> &Option<Recast> {
+        &self.recast_vec[action.index()]
+    }
+
+    /// Returns a mutable reference to the underlying [`Recast`] for `action`, if set.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, action: A) -> &mut Option<Recast> {
+        &mut self.recast_vec[action.index()]
+    }
+
+    /// Sets the underlying [`Recast`] for `action` to the provided value.
+    ///
+    /// Unless you're building a new [`RecastState`] struct, you likely want to use [`Self::get_mut`].
+    #[inline]
+    pub fn set(&mut self, action: A, recast: Recast) -> &mut Self {
+        *self.get_mut(action) = Some(recast);
+        self
+    }
+
+    /// Collects a `&mut Self` into a `Self`.
+    ///
+    /// Used to conclude the builder pattern. Actually just calls `self.clone()`.
+    #[inline]
+    #[must_use]
+    pub fn build(&mut self) -> Self {
+        self.clone()
+    }
+
+    /// Returns an iterator of references to the underlying non-[`None`] [`Recast`]s
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Recast> {
+        self.recast_vec.iter().flatten()
+    }
+
+    /// Returns an iterator of mutable references to the underlying non-[`None`] [`Recast`]s
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Recast> {
+        self.recast_vec.iter_mut().flatten()
+    }
+}
+
+/// A time-limited window after an ability is cast, during which it can be cast a second time.
+///
+/// ```rust
+/// use bevy::utils::Duration;
+/// use leafwing_abilities::recast::{Recast, RecastExpiry};
+///
+/// let mut recast = Recast::new(Duration::from_secs(2), RecastExpiry::Cancel);
+/// assert!(!recast.is_open());
+///
+/// recast.open();
+/// assert_eq!(recast.remaining(), Some(Duration::from_secs(2)));
+///
+/// assert_eq!(recast.tick(Duration::from_secs(1)), None);
+/// assert_eq!(recast.tick(Duration::from_secs(1)), Some(RecastExpiry::Cancel));
+/// assert!(!recast.is_open());
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Recast {
+    window: Duration,
+    /// The time remaining in the current recast window, if it is open.
+    remaining: Option<Duration>,
+    /// What should happen when the recast window expires without being used?
+    pub expiry: RecastExpiry,
+}
+
+/// What happens when the window of a [`Recast`] expires without the ability being recast?
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecastExpiry {
+    /// The recast is performed automatically.
+    ///
+    /// For example, a placed trap detonates on its own.
+    Resolve,
+    /// The recast is lost.
+    Cancel,
+}
+
+impl Recast {
+    /// Creates a new [`Recast`], whose window stays open for `window` after the ability is first cast.
+    ///
+    /// The window starts closed.
+    #[inline]
+    #[must_use]
+    pub fn new(window: Duration, expiry: RecastExpiry) -> Recast {
+        Recast {
+            window,
+            remaining: None,
+            expiry,
+        }
+    }
+
+    /// The length of the recast window.
+    #[inline]
+    #[must_use]
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// The time remaining until the recast window closes, or [`None`] if the window is not open.
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> Option<Duration> {
+        self.remaining
+    }
+
+    /// Is the recast window currently open?
+    #[inline]
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.remaining.is_some()
+    }
+
+    /// Opens the recast window, resetting the time remaining to the full window.
+    #[inline]
+    pub fn open(&mut self) {
+        self.remaining = Some(self.window);
+    }
+
+    /// Closes the recast window.
+    ///
+    /// Returns `true` if the window was open.
+    #[inline]
+    pub fn close(&mut self) -> bool {
+        self.remaining.take().is_some()
+    }
+
+    /// Advances the recast window by `delta_time`.
+    ///
+    /// If this causes the window to expire, it is closed and its [`RecastExpiry`] is returned.
+    pub fn tick(&mut self, delta_time: Duration) -> Option<RecastExpiry> {
+        let remaining = self.remaining?.saturating_sub(delta_time);
+
+        if remaining == Duration::ZERO {
+            self.remaining = None;
+            Some(self.expiry)
+        } else {
+            self.remaining = Some(remaining);
+            None
+        }
+    }
+}
+
+/// An event sent when the recast window of an ability of type `A` expires without being used.
+///
+/// Sent by the [`tick_recasts`](crate::systems::tick_recasts) system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecastExpired<A: Abilitylike> {
+    /// The entity whose recast window expired, or [`None`] if the [`RecastState`] is stored as a resource.
+    pub entity: Option<Entity>,
+    /// The ability whose recast window expired.
+    pub action: A,
+    /// Whether the recast should be resolved automatically or cancelled.
+    pub expiry: RecastExpiry,
+}
+
+/// Was an ability cast for the first time, recast during its recast window, or used to cancel its channel?
+///
+/// Returned by [`AbilityStateItem::trigger_or_recast`](crate::AbilityStateItem::trigger_or_recast).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastKind {
+    /// The ability was cast normally, opening its recast window if it has one.
+    Initial,
+    /// The ability was recast during its recast window.
+    Recast,
+    /// The ability was triggered while it was being channeled, cancelling its [`Channel`](crate::channel::Channel) instead of casting it again.
+    ChannelCancelled,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_abilities;
+    use leafwing_input_manager::Actionlike;
+
+    #[derive(Actionlike, Abilitylike, Clone, Copy, Debug, PartialEq)]
+    enum Action {
+        Trap,
+        Dash,
+    }
+
+    fn recasts() -> RecastState<Action> {
+        RecastState::new([(
+            Action::Trap,
+            Recast::new(Duration::from_secs(2), RecastExpiry::Resolve),
+        )])
+    }
+
+    #[test]
+    fn first_cast_opens_window() {
+        let mut recasts = recasts();
+        assert!(!recasts.in_window(Action::Trap));
+
+        recasts.open(Action::Trap);
+        assert!(recasts.in_window(Action::Trap));
+
+        // Actions without a recast are unaffected
+        recasts.open(Action::Dash);
+        assert!(!recasts.in_window(Action::Dash));
+    }
+
+    #[test]
+    fn recast_within_window() {
+        let mut recasts = recasts();
+        assert!(!recasts.recast(Action::Trap));
+
+        recasts.open(Action::Trap);
+        recasts.tick(Duration::from_secs(1), |_, _| {
+            panic!("Window should not expire")
+        });
+        assert!(recasts.recast(Action::Trap));
+        assert!(!recasts.in_window(Action::Trap));
+        assert!(!recasts.recast(Action::Trap));
+    }
+
+    #[test]
+    fn window_expires() {
+        let mut recasts = recasts();
+        recasts.open(Action::Trap);
+
+        let mut expired = Vec::new();
+        recasts.tick(Duration::from_secs(3), |action, expiry| {
+            expired.push((action, expiry))
+        });
+
+        assert_eq!(expired, vec![(Action::Trap, RecastExpiry::Resolve)]);
+        assert!(!recasts.in_window(Action::Trap));
+        assert!(!recasts.recast(Action::Trap));
+    }
+
+    #[test]
+    fn tick_world_sends_expiry_events() {
+        use crate::plugin::AbilityPlugin;
+        use crate::NullPool;
+        use bevy::ecs::event::Events;
+        use bevy::ecs::world::World;
+
+        let mut world = World::new();
+        let mut state = recasts();
+        state.open(Action::Trap);
+        let entity = world.spawn(state).id();
+
+        AbilityPlugin::<Action>::tick_world::<NullPool>(&mut world, Duration::from_secs(3));
+
+        let events = world.resource::<Events<RecastExpired<Action>>>();
+        let sent: Vec<_> = events.get_reader().iter(events).cloned().collect();
+        assert_eq!(
+            sent,
+            vec![RecastExpired {
+                entity: Some(entity),
+                action: Action::Trap,
+                expiry: RecastExpiry::Resolve,
+            }]
+        );
+    }
+}
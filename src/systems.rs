@@ -1,55 +1,569 @@
 //! The systems that power each [`InputManagerPlugin`](crate::plugin::InputManagerPlugin).
 
-use crate::pool::Pool;
-use crate::{charges::ChargeState, cooldown::CooldownState, Abilitylike};
+use crate::cast_time::{CastFinished, CastTimeState};
+use crate::channel::{ChannelCompleted, ChannelState};
+use crate::diagnostics::AbilityCounters;
+use crate::pool::{
+    AbilityCosts, CooldownResetOnPoolFull, Pool, PoolDepleted, PoolFull, PoolModifiers, RegenGate,
+    RegenMultiplier,
+};
+use crate::recast::{CastKind, RecastExpired, RecastState};
+use crate::triggers::{AbilityTriggered, LastUsed, TriggerSpawners, TriggeredAbilities};
+use crate::{
+    charges::ChargeState,
+    cooldown::{
+        CooldownReady, CooldownReduction, CooldownState, CooldownsPaused, DefaultCooldowns,
+        ExhaustionState,
+    },
+    Abilitylike,
+};
 
+use crate::plugin::{AbilityTimeSource, TimeSource};
 use bevy::ecs::prelude::*;
-use bevy::time::Time;
+use bevy::ecs::system::SystemParam;
+use bevy::time::{fixed_timestep::FixedTime, Time};
+use bevy::utils::{Duration, HashMap};
 use leafwing_input_manager::plugin::ToggleActions;
+use std::sync::Mutex;
+
+/// The time that abilities of type `A` should be advanced by when they are ticked.
+///
+/// This is read from the clock selected by the [`AbilityTimeSource<A>`] resource,
+/// which is configured by [`AbilityPlugin::with_time_source`](crate::plugin::AbilityPlugin::with_time_source).
+#[derive(SystemParam)]
+pub struct AbilityDeltaTime<'w, A: Abilitylike> {
+    time: Res<'w, Time>,
+    fixed_time: Option<Res<'w, FixedTime>>,
+    time_source: Option<Res<'w, AbilityTimeSource<A>>>,
+}
+
+impl<'w, A: Abilitylike> AbilityDeltaTime<'w, A> {
+    /// The elapsed time to advance abilities by.
+    ///
+    /// If the [`FixedTime`] resource is missing, [`TimeSource::Fixed`] falls back to the [`Time::delta`].
+    /// [`TimeSource::Manual`] also reads the [`Time::delta`], which is set by [`AbilityPlugin::tick_world`](crate::plugin::AbilityPlugin::tick_world).
+    #[must_use]
+    pub fn delta(&self) -> Duration {
+        let source = self
+            .time_source
+            .as_ref()
+            .map_or(TimeSource::Virtual, |time_source| time_source.source());
+
+        match (source, &self.fixed_time) {
+            (TimeSource::Real, _) => self.time.raw_delta(),
+            (TimeSource::Fixed, Some(fixed_time)) => fixed_time.period,
+            _ => self.time.delta(),
+        }
+    }
+}
 
 /// Advances all [`CooldownState`] components and resources for ability type `A`.
+///
+/// If a [`CooldownReduction`] is stored alongside the [`CooldownState`], those cooldowns recover faster.
+/// If an [`ExhaustionState`] is stored alongside the [`CooldownState`], the cooldowns of exhausted abilities recover slower,
+/// and the exhaustion decays.
+///
+/// Sends a [`CooldownReady`] event for each action whose [`Cooldown`](crate::cooldown::Cooldown) becomes ready.
+///
+/// Entities with a [`CooldownsPaused`] component are skipped entirely:
+/// neither their cooldowns nor their charges advance.
+/// If a closed [`RegenGate`] is stored alongside the [`CooldownState`], charges that are [recovered over time](crate::charges::Charges::recharges_over_time)
+/// are held in place: see [`CooldownState::tick_gated`] and [`ChargeState::tick_gated`].
+///
+/// Components are ticked in parallel. The events for the resource are sent first,
+/// followed by the events for each entity, sorted by [`Entity`].
+/// If the [`AbilityCounters`] resource exists, the number of ability states ticked is added to it.
+#[allow(clippy::too_many_arguments)]
 pub fn tick_cooldowns<A: Abilitylike>(
     mut query: Query<
-        (Option<&mut CooldownState<A>>, Option<&mut ChargeState<A>>),
-        Or<(With<CooldownState<A>>, With<ChargeState<A>>)>,
+        (
+            Entity,
+            Option<&mut CooldownState<A>>,
+            Option<&mut ChargeState<A>>,
+            Option<&CooldownReduction>,
+            Option<&mut ExhaustionState<A>>,
+            Option<&RegenGate>,
+        ),
+        (
+            Or<(With<CooldownState<A>>, With<ChargeState<A>>)>,
+            Without<CooldownsPaused>,
+        ),
     >,
     cooldowns_res: Option<ResMut<CooldownState<A>>>,
     charges_res: Option<ResMut<ChargeState<A>>>,
-    time: Res<Time>,
+    cooldown_reduction_res: Option<Res<CooldownReduction>>,
+    exhaustion_res: Option<ResMut<ExhaustionState<A>>>,
+    regen_gate_res: Option<Res<RegenGate>>,
+    delta_time: AbilityDeltaTime<A>,
+    mut cooldown_ready: EventWriter<CooldownReady<A>>,
+    counters: Option<ResMut<AbilityCounters<A>>>,
 ) {
-    let delta_time = time.delta();
+    let delta_time = delta_time.delta();
+
+    if let Some(mut counters) = counters {
+        let resources_ticked = usize::from(cooldowns_res.is_some() || charges_res.is_some());
+        counters.abilities_ticked += query.iter().len() + resources_ticked;
+    }
+
+    // Temporary bonuses to max charges are unaffected by cooldown reduction
+    let mut charges_res = charges_res.map(|res| res.into_inner());
+    let regen_gate_res = regen_gate_res.as_deref();
+    if let Some(charges) = charges_res.as_deref_mut() {
+        charges.tick_gated(delta_time, regen_gate_res);
+    }
 
     // Only tick the Cooldowns resource if it exists
     if let Some(mut cooldowns) = cooldowns_res {
-        let charges = charges_res.map(|res| res.into_inner());
+        let charges = charges_res;
+        let scaled_delta_time = match cooldown_reduction_res {
+            Some(reduction) => reduction.scale(delta_time),
+            None => delta_time,
+        };
+        let on_cooldown = actions_on_cooldown(&cooldowns);
 
-        cooldowns.tick(delta_time, charges);
+        match exhaustion_res {
+            Some(mut exhaustion) => {
+                exhaustion.tick_cooldowns(
+                    &mut cooldowns,
+                    scaled_delta_time,
+                    charges,
+                    regen_gate_res,
+                );
+                exhaustion.decay(delta_time);
+            }
+            None => cooldowns.tick_gated(scaled_delta_time, charges, regen_gate_res),
+        }
+
+        for action in newly_ready(&cooldowns, on_cooldown) {
+            cooldown_ready.send(CooldownReady {
+                entity: None,
+                action,
+            });
+        }
     }
 
     // Only tick the Cooldowns components if they exist
-    for (cooldowns, charges) in query.iter_mut() {
-        if let Some(mut cooldowns) = cooldowns {
-            let charges = charges.map(|data| data.into_inner());
+    // Each entity is independent, so they are ticked in parallel, collecting their events as they go
+    let ready_events = Mutex::new(Vec::new());
+    query.par_iter_mut().for_each_mut(
+        |(entity, cooldowns, charges, maybe_reduction, maybe_exhaustion, maybe_gate)| {
+            let mut charges = charges.map(|data| data.into_inner());
+            if let Some(charges) = charges.as_deref_mut() {
+                charges.tick_gated(delta_time, maybe_gate);
+            }
+
+            if let Some(mut cooldowns) = cooldowns {
+                let scaled_delta_time = match maybe_reduction {
+                    Some(reduction) => reduction.scale(delta_time),
+                    None => delta_time,
+                };
+                let on_cooldown = actions_on_cooldown(&cooldowns);
+
+                match maybe_exhaustion {
+                    Some(mut exhaustion) => {
+                        exhaustion.tick_cooldowns(
+                            &mut cooldowns,
+                            scaled_delta_time,
+                            charges,
+                            maybe_gate,
+                        );
+                        exhaustion.decay(delta_time);
+                    }
+                    None => cooldowns.tick_gated(scaled_delta_time, charges, maybe_gate),
+                }
+
+                let ready = newly_ready(&cooldowns, on_cooldown);
+                if !ready.is_empty() {
+                    ready_events
+                        .lock()
+                        .unwrap()
+                        .extend(ready.into_iter().map(|action| CooldownReady {
+                            entity: Some(entity),
+                            action,
+                        }));
+                }
+            }
+        },
+    );
+
+    cooldown_ready.send_batch(sorted_by_entity(ready_events, |event| event.entity));
+}
+
+/// Unwraps the events collected during parallel iteration, sorted by the entity that they are for.
+///
+/// Parallel iteration visits entities in an arbitrary order: sorting keeps the order of events deterministic.
+/// The sort is stable, so the events of each entity stay in the order that they were collected.
+fn sorted_by_entity<E>(events: Mutex<Vec<E>>, entity: impl Fn(&E) -> Option<Entity>) -> Vec<E> {
+    let mut events = events.into_inner().unwrap();
+    events.sort_by_key(entity);
+    events
+}
+
+/// Collects the actions whose own [`Cooldown`](crate::cooldown::Cooldown) is not ready.
+fn actions_on_cooldown<A: Abilitylike>(cooldowns: &CooldownState<A>) -> Vec<A> {
+    A::variants()
+        .filter(|action| {
+            matches!(cooldowns.get(action.clone()), Some(cooldown) if cooldown.ready().is_err())
+        })
+        .collect()
+}
+
+/// Collects the actions that were `on_cooldown` whose cooldown is now ready.
+fn newly_ready<A: Abilitylike>(cooldowns: &CooldownState<A>, on_cooldown: Vec<A>) -> Vec<A> {
+    on_cooldown
+        .into_iter()
+        .filter(|action| {
+            matches!(cooldowns.get(action.clone()), Some(cooldown) if cooldown.ready().is_ok())
+        })
+        .collect()
+}
+
+/// Copies the [`DefaultCooldowns`] onto each newly added [`CooldownState`] component that has no cooldowns set.
+///
+/// Components that were spawned with explicit cooldowns are left untouched.
+pub fn apply_default_cooldowns<A: Abilitylike>(
+    mut query: Query<&mut CooldownState<A>, Added<CooldownState<A>>>,
+    default_cooldowns: Option<Res<DefaultCooldowns<A>>>,
+) {
+    if let Some(default_cooldowns) = default_cooldowns {
+        for mut cooldowns in query.iter_mut() {
+            if cooldowns.iter().next().is_none() && cooldowns.global_cooldown.is_none() {
+                *cooldowns = default_cooldowns.0.clone();
+            }
+        }
+    }
+}
+
+/// Advances all [`LastUsed`] components and resources for ability type `A`.
+pub fn tick_last_used<A: Abilitylike>(
+    mut query: Query<&mut LastUsed<A>>,
+    last_used_res: Option<ResMut<LastUsed<A>>>,
+    delta_time: AbilityDeltaTime<A>,
+) {
+    let delta_time = delta_time.delta();
+
+    if let Some(mut last_used) = last_used_res {
+        last_used.tick(delta_time);
+    }
+
+    query
+        .par_iter_mut()
+        .for_each_mut(|mut last_used| last_used.tick(delta_time));
+}
+
+/// Advances all [`RecastState`] components and resources for ability type `A`.
+///
+/// Sends a [`RecastExpired`] event for each recast window that expires.
+pub fn tick_recasts<A: Abilitylike>(
+    mut query: Query<(Entity, &mut RecastState<A>)>,
+    recasts_res: Option<ResMut<RecastState<A>>>,
+    delta_time: AbilityDeltaTime<A>,
+    mut recast_expired: EventWriter<RecastExpired<A>>,
+) {
+    let delta_time = delta_time.delta();
+
+    if let Some(mut recasts) = recasts_res {
+        recasts.tick(delta_time, |action, expiry| {
+            recast_expired.send(RecastExpired {
+                entity: None,
+                action,
+                expiry,
+            })
+        });
+    }
+
+    // Each entity is independent, so they are ticked in parallel, collecting their events as they go
+    let expired_events = Mutex::new(Vec::new());
+    query.par_iter_mut().for_each_mut(|(entity, mut recasts)| {
+        let mut expired = Vec::new();
+        recasts.tick(delta_time, |action, expiry| {
+            expired.push(RecastExpired {
+                entity: Some(entity),
+                action,
+                expiry,
+            })
+        });
+
+        if !expired.is_empty() {
+            expired_events.lock().unwrap().append(&mut expired);
+        }
+    });
+
+    recast_expired.send_batch(sorted_by_entity(expired_events, |event| event.entity));
+}
+
+/// Advances all [`ChannelState`] components and resources for ability type `A`.
+///
+/// Sends a [`ChannelCompleted`] event for each channel that completes.
+pub fn tick_channels<A: Abilitylike>(
+    mut query: Query<(Entity, &mut ChannelState<A>)>,
+    channels_res: Option<ResMut<ChannelState<A>>>,
+    delta_time: AbilityDeltaTime<A>,
+    mut channel_completed: EventWriter<ChannelCompleted<A>>,
+) {
+    let delta_time = delta_time.delta();
+
+    if let Some(mut channels) = channels_res {
+        channels.tick(delta_time, |action| {
+            channel_completed.send(ChannelCompleted {
+                entity: None,
+                action,
+            })
+        });
+    }
+
+    // Each entity is independent, so they are ticked in parallel, collecting their events as they go
+    let completed_events = Mutex::new(Vec::new());
+    query.par_iter_mut().for_each_mut(|(entity, mut channels)| {
+        let mut completed = Vec::new();
+        channels.tick(delta_time, |action| {
+            completed.push(ChannelCompleted {
+                entity: Some(entity),
+                action,
+            })
+        });
+
+        if !completed.is_empty() {
+            completed_events.lock().unwrap().append(&mut completed);
+        }
+    });
 
-            cooldowns.tick(delta_time, charges);
+    channel_completed.send_batch(sorted_by_entity(completed_events, |event| event.entity));
+}
+
+/// Advances all [`CastTimeState`] components for ability type `A`, paying deferred costs from the [`Pool`] type `P`.
+///
+/// Sends a [`CastFinished`] event for each cast that finishes, as reported by [`CastTimeState::tick_and_pay`].
+/// Casts whose deferred cost cannot be paid finish with [`CannotUseAbility::PoolInsufficient`](crate::CannotUseAbility::PoolInsufficient).
+///
+/// This system is not added by the [`AbilityPlugin`](crate::plugin::AbilityPlugin), as it depends on the type of your pool:
+/// add it to your schedule after [`regenerate_resource_pool`], so that regeneration during the final frame of a cast counts.
+pub fn tick_cast_times<A: Abilitylike, P: Pool + Component>(
+    mut query: Query<(
+        Entity,
+        &mut CastTimeState<A>,
+        Option<&mut P>,
+        Option<&AbilityCosts<A, P>>,
+    )>,
+    delta_time: AbilityDeltaTime<A>,
+    mut cast_finished: EventWriter<CastFinished<A>>,
+) {
+    let delta_time = delta_time.delta();
+
+    for (entity, mut cast_times, maybe_pool, maybe_costs) in query.iter_mut() {
+        cast_times.tick_and_pay(
+            delta_time,
+            maybe_pool.map(|pool| pool.into_inner()),
+            maybe_costs,
+            |action, result| {
+                cast_finished.send(CastFinished {
+                    entity: Some(entity),
+                    action,
+                    result,
+                })
+            },
+        );
+    }
+}
+
+/// Runs the [`TriggerSpawners`] for each ability recorded in a [`TriggeredAbilities`] component.
+///
+/// Recasts do not run any spawners.
+/// The log itself is cleared later in the frame by [`clear_triggered_abilities`].
+pub fn run_trigger_spawners<A: Abilitylike>(
+    mut commands: Commands,
+    query: Query<(Entity, &TriggeredAbilities<A>)>,
+    spawners: Option<ResMut<TriggerSpawners<A>>>,
+) {
+    // Nothing to run if no spawners are registered
+    let mut spawners = match spawners {
+        Some(spawners) => spawners,
+        None => return,
+    };
+
+    for (entity, triggered) in query.iter() {
+        for (action, cast_kind) in triggered.iter() {
+            if *cast_kind == CastKind::Initial {
+                spawners.run(action.clone(), &mut commands, entity);
+            }
+        }
+    }
+}
+
+/// Sends an [`AbilityTriggered`] event for each ability recorded in a [`TriggeredAbilities`] component.
+///
+/// Runs during [`CoreSet::Last`](bevy::app::CoreSet::Last), before the log is cleared by [`clear_triggered_abilities`].
+pub fn send_ability_triggered<A: Abilitylike>(
+    query: Query<(Entity, &TriggeredAbilities<A>)>,
+    time: Option<Res<Time>>,
+    mut ability_triggered: EventWriter<AbilityTriggered<A>>,
+) {
+    let timestamp = time.map_or(Duration::ZERO, |time| time.elapsed());
+
+    for (entity, triggered) in query.iter() {
+        ability_triggered.send_batch(triggered.iter().map(|(action, cast_kind)| {
+            AbilityTriggered {
+                entity,
+                action: action.clone(),
+                cast_kind: *cast_kind,
+                timestamp,
+            }
+        }));
+    }
+}
+
+/// Clears the log of each [`TriggeredAbilities`] component, so that it only ever contains the abilities triggered this frame.
+///
+/// Runs in [`AbilitySystem::ClearFrameState`](crate::plugin::AbilitySystem::ClearFrameState).
+pub fn clear_triggered_abilities<A: Abilitylike>(mut query: Query<&mut TriggeredAbilities<A>>) {
+    for mut triggered in query.iter_mut() {
+        // Avoid triggering change detection when nothing was triggered
+        if !triggered.is_empty() {
+            triggered.clear();
         }
     }
 }
 
 /// Regenerates the resource of the [`Pool`] type `P` based on the elapsed [`Time`].
+///
+/// If a [`RegenMultiplier`] is stored alongside the pool, the amount regenerated is scaled by it.
+/// If a closed [`RegenGate`] is stored alongside the pool, it does not regenerate at all.
+/// Pools that do not regenerate are skipped (avoiding change detection),
+/// unless they are tracking the [time since they were damaged](Pool::time_since_damaged),
+/// or are above their max and may need to decay back down, such as after overhealing.
+///
+/// Pool components are regenerated in parallel, with batch sizes chosen by Bevy based on the number of matching entities.
 pub fn regenerate_resource_pool<P: Pool + Component + Resource>(
-    mut query: Query<&mut P>,
+    mut query: Query<(&mut P, Option<&RegenMultiplier>, Option<&RegenGate>)>,
     pool_res: Option<ResMut<P>>,
+    regen_multiplier_res: Option<Res<RegenMultiplier>>,
+    regen_gate_res: Option<Res<RegenGate>>,
     time: Res<Time>,
 ) {
     let delta_time = time.delta();
 
-    for mut pool in query.iter_mut() {
-        pool.regenerate(delta_time);
-    }
+    query
+        .par_iter_mut()
+        .for_each_mut(|(mut pool, maybe_multiplier, maybe_gate)| {
+            if needs_regeneration(&*pool, maybe_gate) {
+                let multiplier = maybe_multiplier.copied().unwrap_or_default();
+                pool.regenerate_scaled(delta_time, multiplier.multiplier());
+            }
+        });
 
     if let Some(mut pool) = pool_res {
-        pool.regenerate(delta_time);
+        if needs_regeneration(&*pool, regen_gate_res.as_deref()) {
+            let multiplier = regen_multiplier_res.as_deref().copied().unwrap_or_default();
+            pool.regenerate_scaled(delta_time, multiplier.multiplier());
+        }
+    }
+}
+
+/// Applies each changed [`PoolModifiers`] to the [`Pool`] type `P` that it is stored alongside.
+///
+/// Calls [`PoolModifiers::apply`].
+pub fn apply_pool_modifiers<P: Pool + Component + Resource>(
+    mut query: Query<(&mut P, &PoolModifiers<P>), Changed<PoolModifiers<P>>>,
+    pool_res: Option<ResMut<P>>,
+    modifiers_res: Option<Res<PoolModifiers<P>>>,
+) {
+    for (mut pool, modifiers) in query.iter_mut() {
+        modifiers.apply(&mut pool);
+    }
+
+    if let (Some(mut pool), Some(modifiers)) = (pool_res, modifiers_res) {
+        if modifiers.is_changed() {
+            modifiers.apply(&mut pool);
+        }
+    }
+}
+
+/// Should [`regenerate_resource_pool`] update this pool?
+fn needs_regeneration<P: Pool>(pool: &P, regen_gate: Option<&RegenGate>) -> bool {
+    if matches!(regen_gate, Some(gate) if !gate.is_open()) {
+        return false;
+    }
+
+    pool.regen_per_second() != P::ZERO
+        || pool.current() > pool.max()
+        || pool.time_since_damaged().is_some()
+}
+
+/// Refreshes cooldowns whenever the [`Pool`] type `P` they are tied to by a [`CooldownResetOnPoolFull`] becomes full.
+pub fn reset_cooldowns_on_pool_full<A: Abilitylike, P: Pool + Component>(
+    mut query: Query<(
+        &P,
+        &mut CooldownState<A>,
+        &mut CooldownResetOnPoolFull<A, P>,
+    )>,
+) {
+    for (pool, mut cooldowns, mut reset) in query.iter_mut() {
+        if reset.just_filled(pool) {
+            if let Some(cooldown) = cooldowns.get_mut(reset.action.clone()) {
+                cooldown.refresh();
+            }
+        }
+    }
+}
+
+/// Whether a [`Pool`] was empty and / or full when it was last checked by [`send_pool_events`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolBoundaries {
+    empty: bool,
+    full: bool,
+}
+
+impl PoolBoundaries {
+    fn new<P: Pool>(pool: &P) -> Self {
+        PoolBoundaries {
+            empty: pool.is_empty(),
+            full: pool.is_full(),
+        }
+    }
+}
+
+/// Sends a [`PoolDepleted`] or [`PoolFull`] event whenever a [`Pool`] of type `P` becomes empty or full.
+///
+/// Each event is sent once per transition, rather than every frame that the pool stays at that boundary.
+/// Pools are not considered to have transitioned when they are first seen by this system.
+pub fn send_pool_events<P: Pool + Component + Resource>(
+    query: Query<(Entity, &P), Changed<P>>,
+    pool_res: Option<Res<P>>,
+    mut removed: RemovedComponents<P>,
+    mut previous: Local<HashMap<Entity, PoolBoundaries>>,
+    mut previous_res: Local<Option<PoolBoundaries>>,
+    mut depleted: EventWriter<PoolDepleted<P>>,
+    mut full: EventWriter<PoolFull<P>>,
+) {
+    let mut send_events = |entity, old: Option<PoolBoundaries>, new: PoolBoundaries| {
+        if let Some(old) = old {
+            if new.empty && !old.empty {
+                depleted.send(PoolDepleted::new(entity));
+            }
+            if new.full && !old.full {
+                full.send(PoolFull::new(entity));
+            }
+        }
+    };
+
+    for entity in removed.iter() {
+        previous.remove(&entity);
+    }
+
+    for (entity, pool) in query.iter() {
+        let boundaries = PoolBoundaries::new(pool);
+        let old = previous.insert(entity, boundaries);
+        send_events(Some(entity), old, boundaries);
+    }
+
+    match pool_res {
+        Some(pool) if pool.is_changed() => {
+            let boundaries = PoolBoundaries::new(&*pool);
+            let old = previous_res.replace(boundaries);
+            send_events(None, old, boundaries);
+        }
+        Some(_) => (),
+        None => *previous_res = None,
     }
 }
 
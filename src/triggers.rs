@@ -0,0 +1,208 @@
+//! Tools for reacting to abilities when they are successfully triggered.
+//!
+//! Record successful triggers by adding a [`TriggeredAbilities`] component to the caster:
+//! each trigger performed through an [`AbilityState`](crate::AbilityState) is then logged there.
+//! The [`AbilityPlugin`](crate::plugin::AbilityPlugin) reads this log to run the spawn logic registered in [`TriggerSpawners`],
+//! which is a convenient place to spawn projectiles or apply physics impulses.
+//!
+//! The plugin also sends an [`AbilityTriggered`] event for each logged trigger, for analytics and other systems that want a single stream of ability uses.
+//!
+//! To track how long ago each ability was used, add a [`LastUsed`] component to the caster.
+
+use bevy::ecs::prelude::{Commands, Component, Entity, Resource};
+use bevy::utils::Duration;
+use std::marker::PhantomData;
+
+use crate::recast::CastKind;
+use crate::Abilitylike;
+
+/// A component that records each ability of type `A` that was successfully triggered by this entity.
+///
+/// Triggers are recorded by the methods of [`AbilityState`](crate::AbilityState),
+/// and the log is cleared at the end of each frame, in [`AbilitySystem::ClearFrameState`](crate::plugin::AbilitySystem::ClearFrameState).
+/// Systems that read this log should run before that set, or read the [`AbilityTriggered`] events sent from it instead.
+#[derive(Component, Clone, Debug, PartialEq, Eq)]
+pub struct TriggeredAbilities<A: Abilitylike> {
+    triggered: Vec<(A, CastKind)>,
+}
+
+impl<A: Abilitylike> Default for TriggeredAbilities<A> {
+    fn default() -> Self {
+        TriggeredAbilities {
+            triggered: Vec::new(),
+        }
+    }
+}
+
+impl<A: Abilitylike> TriggeredAbilities<A> {
+    /// Records that `action` was successfully triggered.
+    #[inline]
+    pub fn record(&mut self, action: A, cast_kind: CastKind) {
+        self.triggered.push((action, cast_kind));
+    }
+
+    /// Returns an iterator over the abilities triggered since the log was last cleared, in the order that they were triggered.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &(A, CastKind)> {
+        self.triggered.iter()
+    }
+
+    /// Removes and returns all of the recorded triggers, in the order that they were triggered.
+    #[inline]
+    pub fn drain(&mut self) -> impl Iterator<Item = (A, CastKind)> + '_ {
+        self.triggered.drain(..)
+    }
+
+    /// Removes all of the recorded triggers.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.triggered.clear();
+    }
+
+    /// Were no abilities triggered since the log was last cleared?
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.triggered.is_empty()
+    }
+}
+
+/// An event sent for each ability of type `A` that was successfully triggered by an entity with a [`TriggeredAbilities`] component.
+///
+/// Sent by the [`send_ability_triggered`](crate::systems::send_ability_triggered) system during [`CoreSet::Last`](bevy::app::CoreSet::Last),
+/// in the order that the abilities of each entity were triggered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbilityTriggered<A: Abilitylike> {
+    /// The entity that triggered the ability.
+    pub entity: Entity,
+    /// The ability that was triggered.
+    pub action: A,
+    /// Whether the ability was cast for the first time, or recast.
+    pub cast_kind: CastKind,
+    /// The [`Time::elapsed`](bevy::time::Time::elapsed) on the frame that the ability was triggered,
+    /// or [`Duration::ZERO`] if there is no [`Time`](bevy::time::Time) resource.
+    pub timestamp: Duration,
+}
+
+/// Tracks the time since each ability of type `A` was last used, for logic that is independent of cooldowns.
+///
+/// For example, a combo could be dropped if no attack was made in the last three seconds.
+///
+/// Uses are recorded by the methods of [`AbilityState`](crate::AbilityState), including recasts,
+/// and the elapsed time is advanced by the [`tick_last_used`](crate::systems::tick_last_used) system.
+#[derive(Resource, Component, Clone, Debug, PartialEq, Eq)]
+pub struct LastUsed<A: Abilitylike> {
+    /// The time since each action was last used, stored in [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) order.
+    ///
+    /// If [`None`], the action has never been used.
+    time_since_vec: Vec<Option<Duration>>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Abilitylike> Default for LastUsed<A> {
+    fn default() -> Self {
+        LastUsed {
+            time_since_vec: A::variants().map(|_| None).collect(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Abilitylike> LastUsed<A> {
+    /// Records that `action` was just used, resetting the time since its last use to zero.
+    #[inline]
+    pub fn record(&mut self, action: A) {
+        self.time_since_vec[action.index()] = Some(Duration::ZERO);
+    }
+
+    /// The time since `action` was last used.
+    ///
+    /// Returns [`None`] if the action has never been used.
+    #[inline]
+    #[must_use]
+    pub fn time_since_last_use(&self, action: A) -> Option<Duration> {
+        self.time_since_vec[action.index()]
+    }
+
+    /// Advances the time since each action was last used according to the elapsed `delta_time`.
+    ///
+    /// Actions that have never been used are unaffected.
+    pub fn tick(&mut self, delta_time: Duration) {
+        for time_since in self.time_since_vec.iter_mut().flatten() {
+            *time_since = time_since.saturating_add(delta_time);
+        }
+    }
+}
+
+/// The boxed spawn logic stored in [`TriggerSpawners`].
+pub type TriggerSpawner = Box<dyn FnMut(&mut Commands, Entity) + Send + Sync>;
+
+/// A resource storing the spawn logic to run when each ability of type `A` is triggered.
+///
+/// Each spawner is called with the entity that triggered the ability,
+/// for every entity with a [`TriggeredAbilities`] component.
+/// Spawners are only run for the initial cast of an ability, not for its [recasts](crate::recast).
+///
+/// This resource is added by the [`AbilityPlugin`](crate::plugin::AbilityPlugin).
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_abilities::prelude::*;
+/// use leafwing_abilities::triggers::TriggerSpawners;
+/// use leafwing_input_manager::Actionlike;
+///
+/// #[derive(Actionlike, Abilitylike, Clone, Copy)]
+/// enum Action {
+///     Fireball,
+/// }
+///
+/// #[derive(Component)]
+/// struct Projectile {
+///     caster: Entity,
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugin(AbilityPlugin::<Action>::default());
+///
+/// app.world
+///     .resource_mut::<TriggerSpawners<Action>>()
+///     .on_trigger_spawn(Action::Fireball, |commands, caster| {
+///         commands.spawn(Projectile { caster });
+///     });
+/// ```
+#[derive(Resource)]
+pub struct TriggerSpawners<A: Abilitylike> {
+    /// The spawners for each action, stored in [`Actionlike::variants`](leafwing_input_manager::Actionlike::variants) order.
+    spawner_vec: Vec<Vec<TriggerSpawner>>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Abilitylike> Default for TriggerSpawners<A> {
+    fn default() -> Self {
+        TriggerSpawners {
+            spawner_vec: A::variants().map(|_| Vec::new()).collect(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Abilitylike> TriggerSpawners<A> {
+    /// Registers `spawner` to be run each time `action` is triggered, with the entity that triggered it.
+    ///
+    /// Several spawners can be registered for the same action: they are run in the order that they were registered.
+    pub fn on_trigger_spawn(
+        &mut self,
+        action: A,
+        spawner: impl FnMut(&mut Commands, Entity) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.spawner_vec[action.index()].push(Box::new(spawner));
+        self
+    }
+
+    /// Runs each spawner registered for `action`, with the `caster` entity that triggered it.
+    pub fn run(&mut self, action: A, commands: &mut Commands, caster: Entity) {
+        for spawner in self.spawner_vec[action.index()].iter_mut() {
+            spawner(commands, caster);
+        }
+    }
+}
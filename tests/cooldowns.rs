@@ -1,15 +1,11 @@
-// BLOCKED: these tests should set the time manually.
-// Requires https://github.com/bevyengine/bevy/issues/6146 to do so.
-
 use bevy::input::InputPlugin;
 use bevy::prelude::*;
 use bevy::utils::Duration;
 use leafwing_abilities::prelude::*;
+use leafwing_abilities::NullPool;
 use leafwing_input_manager::prelude::*;
 
-use std::thread::sleep;
-
-#[derive(Actionlike, Abilitylike, Debug, Clone, Copy)]
+#[derive(Actionlike, Abilitylike, Debug, Clone, Copy, PartialEq)]
 enum Action {
     NoCooldown,
     Short,
@@ -26,14 +22,7 @@ impl Action {
     }
 
     fn cooldowns() -> CooldownState<Action> {
-        let mut cd = CooldownState::default();
-        for action in Action::variants() {
-            if let Some(cooldown) = action.cooldown() {
-                cd.set(action, cooldown);
-            }
-        }
-
-        cd
+        CooldownState::new_with(|action: Action| action.cooldown())
     }
 }
 
@@ -44,6 +33,10 @@ fn spawn(mut commands: Commands) {
     });
 }
 
+fn tick(world: &mut World, delta_time: Duration) {
+    AbilityPlugin::<Action>::tick_world::<NullPool>(world, delta_time);
+}
+
 #[test]
 fn cooldowns_on_entity() {
     use Action::*;
@@ -70,173 +63,807 @@ fn cooldowns_on_entity() {
 
     // No waiting
     let mut query_state = app.world.query::<&CooldownState<Action>>();
-    let cooldowns: &CooldownState<Action> = query_state.single(&mut app.world);
+    let cooldowns: &CooldownState<Action> = query_state.single(&app.world);
     assert!(cooldowns.ready(NoCooldown).is_ok());
-    assert_eq!(cooldowns.ready(Short), Err(CannotUseAbility::OnCooldown));
-    assert_eq!(cooldowns.ready(Long), Err(CannotUseAbility::OnCooldown));
+    assert!(matches!(
+        cooldowns.ready(Short),
+        Err(CannotUseAbility::OnCooldown { .. })
+    ));
+    assert!(matches!(
+        cooldowns.ready(Long),
+        Err(CannotUseAbility::OnCooldown { .. })
+    ));
 
-    sleep(Duration::from_secs_f32(0.2));
-    app.update();
+    tick(&mut app.world, Duration::from_millis(200));
 
     // Short wait
-    let mut query_state = app.world.query::<&CooldownState<Action>>();
-    let cooldowns: &CooldownState<Action> = query_state.single(&mut app.world);
+    let cooldowns: &CooldownState<Action> = query_state.single(&app.world);
     assert!(cooldowns.ready(NoCooldown).is_ok());
     assert!(cooldowns.ready(Short).is_ok());
-    assert_eq!(cooldowns.ready(Long), Err(CannotUseAbility::OnCooldown));
+    assert!(matches!(
+        cooldowns.ready(Long),
+        Err(CannotUseAbility::OnCooldown { .. })
+    ));
 }
 
 #[test]
 fn cooldowns_in_resource() {
     use Action::*;
 
-    let mut app = App::new();
-    app.add_plugin(AbilityPlugin::<Action>::default())
-        .add_plugins(MinimalPlugins)
-        .add_plugin(InputPlugin)
-        .insert_resource(Action::cooldowns());
+    let mut world = World::new();
+    world.insert_resource(Action::cooldowns());
 
     // Cooldown start ready
-    let mut cooldowns: Mut<CooldownState<Action>> = app.world.resource_mut();
+    let mut cooldowns: Mut<CooldownState<Action>> = world.resource_mut();
     for action in Action::variants() {
         assert!(cooldowns.ready(action).is_ok());
         let _ = cooldowns.trigger(action);
     }
 
-    app.update();
+    tick(&mut world, Duration::ZERO);
 
     // No waiting
-    let cooldowns: &CooldownState<Action> = app.world.resource();
+    let cooldowns: &CooldownState<Action> = world.resource();
     assert!(cooldowns.ready(NoCooldown).is_ok());
-    assert_eq!(cooldowns.ready(Short), Err(CannotUseAbility::OnCooldown));
-    assert_eq!(cooldowns.ready(Long), Err(CannotUseAbility::OnCooldown));
+    assert!(matches!(
+        cooldowns.ready(Short),
+        Err(CannotUseAbility::OnCooldown { .. })
+    ));
+    assert!(matches!(
+        cooldowns.ready(Long),
+        Err(CannotUseAbility::OnCooldown { .. })
+    ));
 
-    sleep(Duration::from_secs_f32(0.2));
-    app.update();
+    tick(&mut world, Duration::from_millis(200));
 
     // Short wait
-    let cooldowns: &CooldownState<Action> = app.world.resource();
+    let cooldowns: &CooldownState<Action> = world.resource();
     assert!(cooldowns.ready(NoCooldown).is_ok());
     assert!(cooldowns.ready(Short).is_ok());
-    assert_eq!(cooldowns.ready(Long), Err(CannotUseAbility::OnCooldown));
+    assert!(matches!(
+        cooldowns.ready(Long),
+        Err(CannotUseAbility::OnCooldown { .. })
+    ));
 }
 
 #[test]
 fn global_cooldowns_tick() {
-    let mut app = App::new();
-    app.add_plugin(AbilityPlugin::<Action>::default())
-        .add_plugins(MinimalPlugins)
-        .add_plugin(InputPlugin)
-        .insert_resource(Action::cooldowns());
+    let mut world = World::new();
+    world.insert_resource(Action::cooldowns());
 
-    let mut cooldowns: Mut<CooldownState<Action>> = app.world.resource_mut();
+    let mut cooldowns: Mut<CooldownState<Action>> = world.resource_mut();
     let initial_gcd = Some(Cooldown::new(Duration::from_micros(15)));
-    cooldowns.global_cooldown = initial_gcd.clone();
+    cooldowns.global_cooldown = initial_gcd;
     // Trigger the GCD
     let _ = cooldowns.trigger(Action::Long);
 
-    app.update();
+    tick(&mut world, Duration::from_micros(5));
 
-    let cooldowns: &CooldownState<Action> = app.world.resource();
+    let cooldowns: &CooldownState<Action> = world.resource();
     assert!(initial_gcd != cooldowns.global_cooldown);
 }
 
 #[test]
 fn global_cooldown_blocks_cooldownless_actions() {
-    let mut app = App::new();
-    app.add_plugin(AbilityPlugin::<Action>::default())
-        .add_plugins(MinimalPlugins)
-        .add_plugin(InputPlugin)
-        .insert_resource(Action::cooldowns());
-
-    // First delta time provided of each app is wonky
-    app.update();
+    let mut world = World::new();
+    world.insert_resource(Action::cooldowns());
 
-    let mut cooldowns: Mut<CooldownState<Action>> = app.world.resource_mut();
+    let mut cooldowns: Mut<CooldownState<Action>> = world.resource_mut();
     cooldowns.global_cooldown = Some(Cooldown::new(Duration::from_micros(15)));
 
     assert!(cooldowns.ready(Action::NoCooldown).is_ok());
 
     let _ = cooldowns.trigger(Action::NoCooldown);
-    assert_eq!(
+    assert!(matches!(
         cooldowns.ready(Action::NoCooldown),
-        Err(CannotUseAbility::OnCooldown)
-    );
+        Err(CannotUseAbility::OnGlobalCooldown { .. })
+    ));
 
-    sleep(Duration::from_micros(30));
-    app.update();
+    tick(&mut world, Duration::from_micros(30));
 
-    let cooldowns: &CooldownState<Action> = app.world.resource();
+    let cooldowns: &CooldownState<Action> = world.resource();
     assert!(cooldowns.ready(Action::NoCooldown).is_ok());
 }
 
 #[test]
 fn global_cooldown_affects_other_actions() {
-    let mut app = App::new();
-    app.add_plugin(AbilityPlugin::<Action>::default())
-        .add_plugins(MinimalPlugins)
-        .add_plugin(InputPlugin)
-        .insert_resource(Action::cooldowns());
+    let mut world = World::new();
+    world.insert_resource(Action::cooldowns());
 
-    // First delta time provided of each app is wonky
-    app.update();
-
-    let mut cooldowns: Mut<CooldownState<Action>> = app.world.resource_mut();
+    let mut cooldowns: Mut<CooldownState<Action>> = world.resource_mut();
     cooldowns.global_cooldown = Some(Cooldown::new(Duration::from_micros(15)));
     let _ = cooldowns.trigger(Action::Long);
-    assert_eq!(
+    assert!(matches!(
+        cooldowns.ready(Action::Short),
+        Err(CannotUseAbility::OnGlobalCooldown { .. })
+    ));
+    assert!(matches!(
+        cooldowns.ready(Action::Long),
+        Err(CannotUseAbility::OnCooldown { .. })
+    ));
+
+    tick(&mut world, Duration::from_micros(30));
+
+    let cooldowns: &CooldownState<Action> = world.resource();
+    assert!(cooldowns.ready(Action::Short).is_ok());
+    assert!(matches!(
+        cooldowns.ready(Action::Long),
+        Err(CannotUseAbility::OnCooldown { .. })
+    ));
+}
+
+#[test]
+fn global_cooldown_overrides_short_cooldowns() {
+    let mut world = World::new();
+    world.insert_resource(Action::cooldowns());
+
+    let mut cooldowns: Mut<CooldownState<Action>> = world.resource_mut();
+    cooldowns.global_cooldown = Some(Cooldown::from_secs(0.5));
+    let _ = cooldowns.trigger(Action::Short);
+    assert!(matches!(
+        cooldowns.ready(Action::Short),
+        Err(CannotUseAbility::OnCooldown { .. })
+    ));
+
+    // Let per-action cooldown elapse, leaving only the global cooldown
+    tick(&mut world, Duration::from_millis(200));
+
+    let cooldowns: &CooldownState<Action> = world.resource();
+    assert!(matches!(
         cooldowns.ready(Action::Short),
-        Err(CannotUseAbility::OnCooldown)
+        Err(CannotUseAbility::OnGlobalCooldown { .. })
+    ));
+
+    // Wait for full GCD to expire
+    tick(&mut world, Duration::from_millis(400));
+
+    let cooldowns: &CooldownState<Action> = world.resource();
+    assert!(cooldowns.ready(Action::Short).is_ok());
+}
+
+#[test]
+fn cooldown_reduction_halves_cooldown_time() {
+    use leafwing_abilities::cooldown::CooldownReduction;
+
+    let mut world = World::new();
+
+    let mut cooldowns = CooldownState::new([(Action::Long, Cooldown::from_secs(1.))]);
+    let _ = cooldowns.trigger(Action::Long);
+    let fast = world
+        .spawn((cooldowns.clone(), CooldownReduction::new(0.5)))
+        .id();
+    let normal = world.spawn(cooldowns).id();
+
+    tick(&mut world, Duration::from_millis(500));
+
+    let fast_cooldowns = world.get::<CooldownState<Action>>(fast).unwrap();
+    assert!(fast_cooldowns.ready(Action::Long).is_ok());
+
+    let normal_cooldowns = world.get::<CooldownState<Action>>(normal).unwrap();
+    assert_eq!(
+        normal_cooldowns.ready(Action::Long),
+        Err(CannotUseAbility::OnCooldown {
+            remaining: Duration::from_millis(500)
+        })
     );
+}
+
+#[test]
+fn tick_world_preserves_existing_time() {
+    let mut world = World::new();
+    world.insert_resource(Time::default());
+    let startup = world.resource::<Time>().startup();
+
+    tick(&mut world, Duration::from_secs(1));
+
+    let time = world.resource::<Time>();
+    assert_eq!(time.startup(), startup);
+    assert_eq!(time.delta(), Duration::ZERO);
+}
+
+#[test]
+fn tick_world_regenerates_pools() {
+    use leafwing_abilities::pool::Pool;
+    use leafwing_abilities::premade_pools::mana::{Mana, ManaPool};
+
+    let mut world = World::new();
+    let entity = world
+        .spawn((
+            AbilitiesBundle::<Action>::default(),
+            ManaPool::new_empty(Mana(10.), Mana(2.)),
+        ))
+        .id();
+
+    AbilityPlugin::<Action>::tick_world::<ManaPool>(&mut world, Duration::from_secs(2));
+    assert_eq!(world.get::<ManaPool>(entity).unwrap().current(), Mana(4.));
+}
+
+#[test]
+fn deferred_costs_are_paid_when_the_cast_finishes() {
+    use leafwing_abilities::cast_time::{CastFinished, CastTime, CastTimeState, CostTiming};
+    use leafwing_abilities::premade_pools::mana::{Mana, ManaPool};
+
+    let mut world = World::new();
+    let mut spawn_caster = |regen| {
+        world
+            .spawn((
+                AbilitiesBundle::<Action>::default(),
+                ActionState::<Action>::default(),
+                ManaPool::new_empty(Mana(10.), regen),
+                AbilityCosts::<Action, ManaPool>::new([(Action::NoCooldown, Mana(8.))]),
+                CastTimeState::new([(
+                    Action::NoCooldown,
+                    CastTime::new(Duration::from_secs(2))
+                        .with_cost_timing(CostTiming::OnCompletion),
+                )]),
+            ))
+            .id()
+    };
+    let regenerating = spawn_caster(Mana(5.));
+    let drained = spawn_caster(Mana(0.));
+
+    // Neither caster can afford the ability yet, but the cost is only checked once the cast finishes
+    let mut query_state = world.query::<AbilityState<Action, ManaPool>>();
+    for mut ability_state in query_state.iter_mut(&mut world) {
+        assert!(ability_state.trigger(Action::NoCooldown).is_ok());
+    }
+
+    AbilityPlugin::<Action>::tick_world::<ManaPool>(&mut world, Duration::from_secs(1));
     assert_eq!(
-        cooldowns.ready(Action::Long),
-        Err(CannotUseAbility::OnCooldown)
+        world.get::<ManaPool>(regenerating).unwrap().current(),
+        Mana(5.)
     );
 
-    sleep(Duration::from_micros(30));
-    app.update();
+    AbilityPlugin::<Action>::tick_world::<ManaPool>(&mut world, Duration::from_secs(1));
+    assert_eq!(
+        world.get::<ManaPool>(regenerating).unwrap().current(),
+        Mana(2.)
+    );
+    assert_eq!(world.get::<ManaPool>(drained).unwrap().current(), Mana(0.));
 
-    let cooldowns: &CooldownState<Action> = app.world.resource();
-    assert!(cooldowns.ready(Action::Short).is_ok());
+    let events = world.resource::<Events<CastFinished<Action>>>();
+    let mut finished: Vec<_> = events.get_reader().iter(events).cloned().collect();
+    finished.sort_by_key(|event| event.entity);
     assert_eq!(
-        cooldowns.ready(Action::Long),
-        Err(CannotUseAbility::OnCooldown)
+        finished,
+        vec![
+            CastFinished {
+                entity: Some(regenerating),
+                action: Action::NoCooldown,
+                result: Ok(()),
+            },
+            CastFinished {
+                entity: Some(drained),
+                action: Action::NoCooldown,
+                result: Err(CannotUseAbility::PoolInsufficient { deficit: 8. }),
+            },
+        ]
     );
 }
 
 #[test]
-fn global_cooldown_overrides_short_cooldowns() {
+fn default_cooldowns_apply_to_entities_without_cooldowns() {
     let mut app = App::new();
-    app.add_plugin(AbilityPlugin::<Action>::default())
+    app.add_plugin(AbilityPlugin::<Action>::default().with_default_cooldowns(Action::cooldowns()))
         .add_plugins(MinimalPlugins)
-        .add_plugin(InputPlugin)
-        .insert_resource(Action::cooldowns());
+        .add_plugin(InputPlugin);
+
+    let defaulted = app.world.spawn(AbilitiesBundle::<Action>::default()).id();
+    let explicit = app
+        .world
+        .spawn(AbilitiesBundle {
+            cooldowns: CooldownState::new([(Action::NoCooldown, Cooldown::from_secs(5.))]),
+            ..default()
+        })
+        .id();
 
-    // First delta time provided of each app is wonky
     app.update();
 
-    let mut cooldowns: Mut<CooldownState<Action>> = app.world.resource_mut();
-    cooldowns.global_cooldown = Some(Cooldown::from_secs(0.5));
-    let _ = cooldowns.trigger(Action::Short);
+    let cooldowns = app.world.get::<CooldownState<Action>>(defaulted).unwrap();
+    assert_eq!(cooldowns.get(Action::NoCooldown), &None);
+    assert_eq!(cooldowns.get(Action::Short), &Action::Short.cooldown());
+    assert_eq!(cooldowns.get(Action::Long), &Action::Long.cooldown());
+
+    let cooldowns = app.world.get::<CooldownState<Action>>(explicit).unwrap();
     assert_eq!(
-        cooldowns.ready(Action::Short),
-        Err(CannotUseAbility::OnCooldown)
+        cooldowns.get(Action::NoCooldown),
+        &Some(Cooldown::from_secs(5.))
     );
+    assert_eq!(cooldowns.get(Action::Long), &None);
+}
+
+#[test]
+fn temporary_max_charges_are_ticked() {
+    let mut world = World::new();
+    let mut charges = ChargeState::new([(Action::Short, Charges::simple(1))]);
+    charges.add_temporary_max(Action::Short, 1, Duration::from_secs(2));
+    let entity = world.spawn(charges).id();
+
+    let charges = world.get::<ChargeState<Action>>(entity).unwrap();
+    assert_eq!(charges.get(Action::Short).as_ref().unwrap().charges(), 2);
+
+    tick(&mut world, Duration::from_secs(1));
+    let charges = world.get::<ChargeState<Action>>(entity).unwrap();
+    assert_eq!(charges.get(Action::Short).as_ref().unwrap().charges(), 2);
+
+    tick(&mut world, Duration::from_secs(1));
+    let charges = world.get::<ChargeState<Action>>(entity).unwrap();
+    assert_eq!(charges.get(Action::Short).as_ref().unwrap().charges(), 1);
+    assert_eq!(
+        charges.get(Action::Short).as_ref().unwrap().max_charges(),
+        1
+    );
+}
+
+#[test]
+fn exhaustion_slows_cooldowns_until_rested() {
+    use leafwing_abilities::cooldown::ExhaustionState;
+
+    let mut world = World::new();
+    world.spawn((
+        AbilitiesBundle {
+            cooldowns: CooldownState::new([(Action::Long, Cooldown::from_secs(1.))]),
+            ..default()
+        },
+        ActionState::<Action>::default(),
+        ExhaustionState::<Action>::new(0.5, 0.5),
+    ));
+    let mut query = world.query::<AbilityState<Action>>();
+
+    // Spam the ability, refreshing its cooldown between uses
+    for _ in 0..3 {
+        let mut ability_state = query.single_mut(&mut world);
+        ability_state.trigger(Action::Long).unwrap();
+        ability_state.cooldowns.reset_all();
+    }
+    query.single_mut(&mut world).trigger(Action::Long).unwrap();
+
+    // An exhaustion of 2.0 makes the cooldown recover at a third of its normal speed
+    tick(&mut world, Duration::from_secs(2));
+    assert!(query.single(&world).ready(Action::Long).is_err());
+
+    // After resting, exhaustion has fully decayed
+    tick(&mut world, Duration::from_secs(10));
+    let exhaustion = world.query::<&ExhaustionState<Action>>().single(&world);
+    assert_eq!(exhaustion.exhaustion(Action::Long), 0.0);
+
+    // A single use only slows the cooldown slightly, so the same wait is now enough
+    query.single_mut(&mut world).trigger(Action::Long).unwrap();
+    tick(&mut world, Duration::from_secs(2));
+    assert!(query.single(&world).ready(Action::Long).is_ok());
+}
+
+#[test]
+fn abilities_trigger_global_cooldown_unless_ignored() {
+    let mut cooldowns = Action::cooldowns();
+    cooldowns
+        .set_global_cooldown(Cooldown::from_secs(0.5))
+        .set_ignores_global_cooldown(Action::NoCooldown, true);
+
+    let mut world = World::new();
+    world.spawn((
+        AbilitiesBundle {
+            cooldowns,
+            ..default()
+        },
+        ActionState::<Action>::default(),
+    ));
+    let mut query = world.query::<AbilityState<Action>>();
+
+    let mut ability_state = query.single_mut(&mut world);
+    ability_state.trigger(Action::Short).unwrap();
+    assert!(matches!(
+        ability_state.ready(Action::Long),
+        Err(CannotUseAbility::OnGlobalCooldown { .. })
+    ));
+    assert!(matches!(
+        ability_state.trigger(Action::Long),
+        Err(CannotUseAbility::OnGlobalCooldown { .. })
+    ));
+    assert!(ability_state
+        .cooldowns
+        .get(Action::Long)
+        .unwrap()
+        .ready()
+        .is_ok());
+
+    // Utility abilities can be used during the global cooldown, and do not restart it
+    ability_state.trigger(Action::NoCooldown).unwrap();
+    tick(&mut world, Duration::from_millis(500));
+
+    let mut ability_state = query.single_mut(&mut world);
+    assert!(ability_state.ready(Action::Long).is_ok());
+    ability_state.trigger(Action::NoCooldown).unwrap();
+    assert!(ability_state.ready(Action::Long).is_ok());
+}
+
+#[test]
+fn cooldown_state_reports_remaining() {
+    let mut cooldowns = Action::cooldowns();
+    cooldowns.trigger(Action::Long).unwrap();
+
+    assert_eq!(cooldowns.remaining(Action::NoCooldown), None);
+    assert_eq!(cooldowns.remaining(Action::Short), Some(Duration::ZERO));
+    assert_eq!(
+        cooldowns.remaining(Action::Long),
+        Some(Duration::from_secs(1))
+    );
+}
+
+#[test]
+fn global_rate_applies_to_all_cooldowns() {
+    let mut world = World::new();
+    let mut cooldowns = Action::cooldowns();
+    cooldowns.set_global_cooldown(Cooldown::from_secs(1.));
+    cooldowns.set_global_rate(0.5);
+    cooldowns.trigger(Action::Long).unwrap();
+    world.insert_resource(cooldowns);
+
+    tick(&mut world, Duration::from_secs(1));
+
+    let cooldowns: &CooldownState<Action> = world.resource();
+    assert_eq!(
+        cooldowns.remaining(Action::Long),
+        Some(Duration::from_millis(500))
+    );
+    assert_eq!(
+        cooldowns.global_cooldown.unwrap().remaining(),
+        Duration::from_millis(500)
+    );
+}
+
+#[test]
+fn cooldown_ready_events_are_sent_when_cooldowns_finish() {
+    use leafwing_abilities::cooldown::CooldownReady;
+
+    let mut world = World::new();
+    let mut cooldowns = Action::cooldowns();
+    cooldowns.trigger(Action::Short).unwrap();
+    cooldowns.trigger(Action::Long).unwrap();
+    let entity = world.spawn(cooldowns).id();
+    world.init_resource::<Events<CooldownReady<Action>>>();
+
+    let mut reader = world
+        .resource::<Events<CooldownReady<Action>>>()
+        .get_reader();
+
+    tick(&mut world, Duration::from_millis(60));
+    assert_eq!(
+        reader
+            .iter(world.resource::<Events<CooldownReady<Action>>>())
+            .count(),
+        0
+    );
+
+    tick(&mut world, Duration::from_millis(60));
+    let events: Vec<_> = reader
+        .iter(world.resource::<Events<CooldownReady<Action>>>())
+        .cloned()
+        .collect();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].entity, Some(entity));
+    assert!(matches!(events[0].action, Action::Short));
+
+    // Cooldowns that were already ready do not send further events
+    tick(&mut world, Duration::from_millis(100));
+    assert_eq!(
+        reader
+            .iter(world.resource::<Events<CooldownReady<Action>>>())
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn paused_entities_are_not_ticked() {
+    let mut world = World::new();
+    let mut cooldowns = Action::cooldowns();
+    cooldowns.trigger(Action::Long).unwrap();
+    let paused = world.spawn((cooldowns.clone(), CooldownsPaused)).id();
+    let unpaused = world.spawn(cooldowns).id();
+
+    tick(&mut world, Duration::from_millis(500));
+
+    let remaining = |world: &World, entity| {
+        world
+            .get::<CooldownState<Action>>(entity)
+            .unwrap()
+            .remaining(Action::Long)
+    };
+    assert_eq!(remaining(&world, paused), Some(Duration::from_secs(1)));
+    assert_eq!(
+        remaining(&world, unpaused),
+        Some(Duration::from_millis(500))
+    );
+
+    world.entity_mut(paused).remove::<CooldownsPaused>();
+    tick(&mut world, Duration::from_millis(500));
+    assert_eq!(remaining(&world, paused), Some(Duration::from_millis(500)));
+}
+
+#[test]
+fn single_entities_can_be_ticked_manually() {
+    let mut world = World::new();
+    let mut cooldowns = Action::cooldowns();
+    cooldowns.trigger(Action::Long).unwrap();
+    let warped = world.spawn(cooldowns.clone()).id();
+    let other = world.spawn(cooldowns).id();
+
+    // A time-warp effect advances one entity mid-frame, on top of the automatic ticks
+    world
+        .get_mut::<CooldownState<Action>>(warped)
+        .unwrap()
+        .tick(Duration::from_millis(300), None);
+    tick(&mut world, Duration::from_millis(500));
+
+    let remaining = |world: &World, entity| {
+        world
+            .get::<CooldownState<Action>>(entity)
+            .unwrap()
+            .remaining(Action::Long)
+    };
+    assert_eq!(remaining(&world, warped), Some(Duration::from_millis(200)));
+    assert_eq!(remaining(&world, other), Some(Duration::from_millis(500)));
+}
+
+#[test]
+fn fixed_update_ticks_by_fixed_period() {
+    let mut app = App::new();
+    app.add_plugin(AbilityPlugin::<Action>::default().in_fixed_update())
+        .init_resource::<Time>()
+        .insert_resource(FixedTime::new(Duration::from_millis(100)));
+
+    let mut cooldowns = Action::cooldowns();
+    cooldowns.trigger(Action::Long).unwrap();
+    let entity = app.world.spawn(cooldowns).id();
+
+    for _ in 0..3 {
+        app.world.run_schedule(CoreSchedule::FixedUpdate);
+    }
+
+    let cooldowns = app.world.get::<CooldownState<Action>>(entity).unwrap();
+    assert_eq!(
+        cooldowns.remaining(Action::Long),
+        Some(Duration::from_millis(700))
+    );
+}
+
+#[test]
+fn closed_regen_gate_holds_charges() {
+    use leafwing_abilities::pool::RegenGate;
+
+    let mut world = World::new();
+    let mut cooldowns = Action::cooldowns();
+    let mut charges = ChargeState::new([(Action::Short, Charges::replenish_one(2))]);
+    charges.expend(Action::Short).unwrap();
+    cooldowns.trigger(Action::Short).unwrap();
+    cooldowns.trigger(Action::Long).unwrap();
+
+    let mut gate = RegenGate::default();
+    gate.close();
+    let entity = world.spawn((cooldowns, charges, gate)).id();
+
+    tick(&mut world, Duration::from_millis(500));
+
+    // Charges are not recovered, but other cooldowns still advance
+    let charges = world.get::<ChargeState<Action>>(entity).unwrap();
+    assert_eq!(charges.get(Action::Short).as_ref().unwrap().charges(), 1);
+    let cooldowns = world.get::<CooldownState<Action>>(entity).unwrap();
+    assert_eq!(
+        cooldowns.remaining(Action::Long),
+        Some(Duration::from_millis(500))
+    );
+
+    world.get_mut::<RegenGate>(entity).unwrap().open();
+    tick(&mut world, Duration::from_millis(120));
+
+    let charges = world.get::<ChargeState<Action>>(entity).unwrap();
+    assert_eq!(charges.get(Action::Short).as_ref().unwrap().charges(), 2);
+}
+
+/// Spends one of the `charges` of `action`, then ticks them for half a second behind a closed [`RegenGate`].
+fn tick_behind_closed_gate(action: Action, charges: Charges) -> (CooldownState<Action>, Charges) {
+    use leafwing_abilities::pool::RegenGate;
+
+    let mut world = World::new();
+    let mut cooldowns = Action::cooldowns();
+    let mut charges = ChargeState::new([(action, charges)]);
+    charges.expend(action).unwrap();
+    if cooldowns.get(action).is_some() {
+        cooldowns.trigger(action).unwrap();
+    }
+
+    let mut gate = RegenGate::default();
+    gate.close();
+    let entity = world.spawn((cooldowns, charges, gate)).id();
+
+    tick(&mut world, Duration::from_millis(500));
+
+    let cooldowns = world.get::<CooldownState<Action>>(entity).unwrap().clone();
+    let charges = world.get::<ChargeState<Action>>(entity).unwrap();
+    (cooldowns, charges.get(action).clone().unwrap())
+}
+
+#[test]
+fn closed_regen_gate_holds_constantly_refreshing_charges() {
+    let (cooldowns, charges) = tick_behind_closed_gate(Action::Short, Charges::replenish_one(2));
+
+    assert_eq!(charges.charges(), 1);
+    let cooldown = cooldowns.get(Action::Short).as_ref().unwrap();
+    assert_eq!(cooldown.elapsed(), Duration::ZERO);
+}
+
+#[test]
+fn closed_regen_gate_holds_charges_refreshed_when_empty() {
+    let (cooldowns, charges) = tick_behind_closed_gate(Action::Short, Charges::replenish_all(1));
+
+    assert_eq!(charges.charges(), 0);
+    let cooldown = cooldowns.get(Action::Short).as_ref().unwrap();
+    assert_eq!(cooldown.elapsed(), Duration::ZERO);
+}
+
+#[test]
+fn closed_regen_gate_holds_independent_timers_without_a_cooldown() {
+    let (_, charges) = tick_behind_closed_gate(
+        Action::NoCooldown,
+        Charges::independent(2, Duration::from_millis(100)),
+    );
+
+    assert_eq!(charges.charges(), 1);
+    assert_eq!(charges.next_charge_in(), Some(Duration::from_millis(100)));
+}
+
+#[test]
+fn closed_regen_gate_ignores_rate_limited_charges() {
+    use leafwing_abilities::charges::{CooldownStrategy, ReplenishStrategy};
+
+    let rate_limited = Charges::new(
+        2,
+        ReplenishStrategy::OneAtATime,
+        CooldownStrategy::RateLimit,
+    );
+    let (cooldowns, charges) = tick_behind_closed_gate(Action::Short, rate_limited);
+
+    // The cooldown only limits the rate of use, so it still recovers
+    assert_eq!(charges.charges(), 1);
+    assert!(cooldowns.ready(Action::Short).is_ok());
+}
+
+#[test]
+fn closed_regen_gate_ignores_charges_that_ignore_cooldowns() {
+    let (cooldowns, charges) = tick_behind_closed_gate(Action::Short, Charges::simple(2));
+
+    assert_eq!(charges.charges(), 1);
+    assert!(cooldowns.ready(Action::Short).is_ok());
+}
+
+#[test]
+fn real_time_source_ignores_paused_time() {
+    use leafwing_abilities::plugin::TimeSource;
+
+    let mut app = App::new();
+    app.add_plugin(AbilityPlugin::<Action>::default().with_time_source(TimeSource::Real));
+
+    let mut time = Time::default();
+    let start = time.startup();
+    time.update_with_instant(start);
+    time.pause();
+    time.update_with_instant(start + Duration::from_millis(300));
+    assert_eq!(time.delta(), Duration::ZERO);
+    app.insert_resource(time);
+
+    let mut cooldowns = Action::cooldowns();
+    cooldowns.trigger(Action::Long).unwrap();
+    let entity = app.world.spawn(cooldowns).id();
 
-    // Let per-action cooldown elapse
-    sleep(Duration::from_millis(200));
     app.update();
 
-    let cooldowns: &CooldownState<Action> = app.world.resource();
+    let cooldowns = app.world.get::<CooldownState<Action>>(entity).unwrap();
     assert_eq!(
-        cooldowns.ready(Action::Short),
-        Err(CannotUseAbility::OnCooldown)
+        cooldowns.remaining(Action::Long),
+        Some(Duration::from_millis(700))
     );
+}
+
+#[test]
+fn tick_world_uses_given_delta_under_fixed_time() {
+    use leafwing_abilities::plugin::{AbilityTimeSource, TimeSource};
+
+    let mut world = World::new();
+    world.insert_resource(AbilityTimeSource::<Action>::new(TimeSource::Fixed));
+    world.insert_resource(FixedTime::new(Duration::from_millis(100)));
+
+    let mut cooldowns = Action::cooldowns();
+    cooldowns.trigger(Action::Long).unwrap();
+    let entity = world.spawn(cooldowns).id();
+
+    tick(&mut world, Duration::from_millis(400));
+
+    let cooldowns = world.get::<CooldownState<Action>>(entity).unwrap();
+    assert_eq!(
+        cooldowns.remaining(Action::Long),
+        Some(Duration::from_millis(600))
+    );
+    assert_eq!(
+        world.resource::<FixedTime>().period,
+        Duration::from_millis(100)
+    );
+}
+
+#[test]
+fn manual_time_source_only_ticks_when_asked() {
+    use leafwing_abilities::plugin::TimeSource;
+
+    let mut app = App::new();
+    app.add_plugin(AbilityPlugin::<Action>::default().with_time_source(TimeSource::Manual));
+
+    let mut time = Time::default();
+    let start = time.startup();
+    time.update_with_instant(start);
+    time.update_with_instant(start + Duration::from_millis(300));
+    app.insert_resource(time);
+
+    let mut cooldowns = Action::cooldowns();
+    cooldowns.trigger(Action::Long).unwrap();
+    let entity = app.world.spawn(cooldowns).id();
 
-    // Wait for full GCD to expire
-    sleep(Duration::from_millis(400));
     app.update();
+    let remaining = |app: &App| {
+        app.world
+            .get::<CooldownState<Action>>(entity)
+            .unwrap()
+            .remaining(Action::Long)
+    };
+    assert_eq!(remaining(&app), Some(Duration::from_secs(1)));
+
+    // One turn passes
+    tick(&mut app.world, Duration::from_millis(250));
+    assert_eq!(remaining(&app), Some(Duration::from_millis(750)));
+}
 
-    let cooldowns: &CooldownState<Action> = app.world.resource();
-    assert!(cooldowns.ready(Action::Short).is_ok());
+#[test]
+fn many_entities_tick_independently() {
+    use leafwing_abilities::cooldown::CooldownReady;
+
+    let mut world = World::new();
+    world.init_resource::<Events<CooldownReady<Action>>>();
+
+    // Stagger the cooldowns, so that each entity has a different amount remaining
+    let entities: Vec<Entity> = (0..1000)
+        .map(|i| {
+            let mut cooldowns = Action::cooldowns();
+            cooldowns.trigger(Action::Long).unwrap();
+            cooldowns
+                .get_mut(Action::Long)
+                .as_mut()
+                .unwrap()
+                .tick(Duration::from_millis(i % 10 * 100), &mut None);
+            world.spawn(cooldowns).id()
+        })
+        .collect();
+
+    let mut reader = world
+        .resource::<Events<CooldownReady<Action>>>()
+        .get_reader();
+    tick(&mut world, Duration::from_millis(450));
+
+    for (i, entity) in entities.iter().enumerate() {
+        let cooldowns = world.get::<CooldownState<Action>>(*entity).unwrap();
+        let expected =
+            Duration::from_millis(550).saturating_sub(Duration::from_millis(i as u64 % 10 * 100));
+        assert_eq!(cooldowns.remaining(Action::Long), Some(expected));
+    }
+
+    // Events are sent in a deterministic order, regardless of how the work was split up
+    let events: Vec<Option<Entity>> = reader
+        .iter(world.resource::<Events<CooldownReady<Action>>>())
+        .map(|event| event.entity)
+        .collect();
+    let expected: Vec<Option<Entity>> = entities
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| i % 10 >= 6)
+        .map(|(_, entity)| Some(*entity))
+        .collect();
+    assert_eq!(events, expected);
 }
@@ -0,0 +1,76 @@
+use bevy::diagnostic::Diagnostics;
+use bevy::prelude::*;
+use leafwing_abilities::cooldown::CooldownsPaused;
+use leafwing_abilities::diagnostics::AbilityDiagnosticsPlugin;
+use leafwing_abilities::prelude::*;
+use leafwing_abilities::triggers::TriggeredAbilities;
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Abilitylike, Debug, Clone, Copy)]
+enum Action {
+    Fireball,
+    Frostbolt,
+}
+
+fn cast_once(mut query: Query<AbilityState<Action>>, mut has_cast: Local<bool>) {
+    if !*has_cast {
+        for mut ability_state in query.iter_mut() {
+            ability_state.trigger(Action::Fireball).unwrap();
+            ability_state.trigger(Action::Frostbolt).unwrap();
+        }
+        *has_cast = true;
+    }
+}
+
+#[test]
+fn diagnostics_are_recorded() {
+    type Plugin = AbilityDiagnosticsPlugin<Action>;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AbilityPlugin::<Action>::default())
+        .add_plugin(Plugin::default())
+        .add_system(cast_once);
+
+    for _ in 0..2 {
+        app.world.spawn((
+            AbilitiesBundle {
+                cooldowns: CooldownState::new([(Action::Fireball, Cooldown::from_secs(10.))]),
+                // Frostbolt only spends charges
+                charges: ChargeState::new([(Action::Frostbolt, Charges::simple(3))]),
+            },
+            ActionState::<Action>::default(),
+            TriggeredAbilities::<Action>::default(),
+        ));
+    }
+
+    let measurement = |app: &App, id| {
+        app.world
+            .resource::<Diagnostics>()
+            .get(id)
+            .unwrap()
+            .value()
+            .unwrap()
+    };
+
+    app.update();
+    assert_eq!(measurement(&app, Plugin::ABILITIES_TICKED), 2.);
+    assert_eq!(measurement(&app, Plugin::ABILITIES_TRIGGERED), 4.);
+    assert_eq!(measurement(&app, Plugin::RECASTS_EXPIRED), 0.);
+
+    // Paused cooldowns are neither ticked nor counted as triggered again
+    let entities: Vec<Entity> = app
+        .world
+        .query_filtered::<Entity, With<CooldownState<Action>>>()
+        .iter(&app.world)
+        .collect();
+    for entity in entities {
+        app.world.entity_mut(entity).insert(CooldownsPaused);
+    }
+
+    for _ in 0..3 {
+        app.update();
+        assert_eq!(measurement(&app, Plugin::ABILITIES_TICKED), 0.);
+        assert_eq!(measurement(&app, Plugin::ABILITIES_TRIGGERED), 0.);
+    }
+}
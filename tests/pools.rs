@@ -0,0 +1,494 @@
+#![cfg(feature = "premade_pools")]
+
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use leafwing_abilities::pool::PoolError;
+use leafwing_abilities::prelude::*;
+use leafwing_abilities::premade_pools::balance::{Balance, BalancedPool};
+use leafwing_abilities::premade_pools::life::{Life, LifePool};
+use leafwing_abilities::premade_pools::mana::{Mana, ManaPool};
+use leafwing_abilities::premade_pools::stamina::{Stamina, StaminaPool};
+use leafwing_abilities::systems::regenerate_resource_pool;
+use leafwing_input_manager::Actionlike;
+use std::any::TypeId;
+
+#[derive(Actionlike, Abilitylike, Clone, Copy)]
+enum Action {
+    Heal,
+}
+
+/// Runs the regeneration systems for both premade pools once, with a frame of exactly `delta_time`.
+fn regenerate(world: &mut World, delta_time: Duration) {
+    let mut time = Time::default();
+    let start = time.startup();
+    time.update_with_instant(start);
+    time.update_with_instant(start + delta_time);
+    world.insert_resource(time);
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems((
+        regenerate_resource_pool::<LifePool>,
+        regenerate_resource_pool::<ManaPool>,
+    ));
+    schedule.run(world);
+}
+
+#[test]
+fn regen_multiplier_scales_all_pools() {
+    let mut world = World::new();
+    let pools = (
+        LifePool::new_empty(Life(100.), Life(1.)),
+        ManaPool::new_empty(Mana(100.), Mana(3.)),
+    );
+    let normal = world.spawn(pools.clone()).id();
+    let buffed = world.spawn((pools, RegenMultiplier::new(2.0))).id();
+
+    regenerate(&mut world, Duration::from_secs(2));
+
+    assert_eq!(world.get::<LifePool>(normal).unwrap().current(), Life(2.));
+    assert_eq!(world.get::<ManaPool>(normal).unwrap().current(), Mana(6.));
+    assert_eq!(world.get::<LifePool>(buffed).unwrap().current(), Life(4.));
+    assert_eq!(world.get::<ManaPool>(buffed).unwrap().current(), Mana(12.));
+}
+
+#[test]
+fn regen_multiplier_resource_scales_pool_resources() {
+    let mut world = World::new();
+    world.insert_resource(ManaPool::new_empty(Mana(100.), Mana(3.)));
+    world.insert_resource(RegenMultiplier::new(0.5));
+
+    regenerate(&mut world, Duration::from_secs(2));
+
+    assert_eq!(world.resource::<ManaPool>().current(), Mana(3.));
+}
+
+#[test]
+fn ability_types_are_registered_for_reflection() {
+    let mut app = App::new();
+    app.add_plugin(AbilityPlugin::<Action>::default());
+
+    let registry = app.world.resource::<AppTypeRegistry>().read();
+    for type_id in [
+        TypeId::of::<CooldownState<Action>>(),
+        TypeId::of::<ChargeState<Action>>(),
+        TypeId::of::<LifePool>(),
+        TypeId::of::<ManaPool>(),
+        TypeId::of::<StaminaPool>(),
+        TypeId::of::<BalancedPool>(),
+    ] {
+        let registration = registry.get(type_id).unwrap();
+        assert!(registration.data::<ReflectComponent>().is_some());
+    }
+}
+
+#[test]
+fn pool_regen_can_be_edited_through_reflection() {
+    let mut pool = LifePool::new_empty(Life(100.), Life(1.));
+    *pool.get_field_mut::<Life>("regen_per_second").unwrap() = Life(5.);
+
+    assert_eq!(pool.regen_per_second(), Life(5.));
+}
+
+#[test]
+fn shields_absorb_damage_before_life() {
+    let mut pool = LifePool::new_full(Life(100.), Life(10.));
+    pool.set_max_shield(Life(30.)).unwrap();
+    assert_eq!(pool.add_shield(Life(50.)), Life(30.));
+    assert_eq!(pool.current_with_shield(), Life(130.));
+
+    pool.expend(Life(20.)).unwrap();
+    assert_eq!(pool.current(), Life(100.));
+    assert_eq!(pool.current_shield(), Life(10.));
+    assert_eq!(pool.time_since_damaged(), Some(Duration::ZERO));
+
+    pool.set_current(Life(75.));
+    assert_eq!(pool.current(), Life(85.));
+    assert_eq!(pool.current_shield(), Life(0.));
+
+    // Shields do not regenerate
+    pool.regenerate(Duration::from_secs(10));
+    assert_eq!(pool.current(), Life(100.));
+    assert_eq!(pool.current_shield(), Life(0.));
+}
+
+#[test]
+fn damage_and_heal_report_the_amount_applied() {
+    let mut pool = LifePool::new(Life(50.), Life(100.), Life(0.));
+    pool.set_max_shield(Life(10.)).unwrap();
+    pool.add_shield(Life(10.));
+
+    // Shields absorb damage, which is still counted as dealt
+    assert_eq!(pool.damage(Life(30.)), Life(30.));
+    assert_eq!(pool.current(), Life(30.));
+    assert_eq!(pool.current_shield(), Life(0.));
+
+    // Overkill
+    assert_eq!(pool.damage(Life(45.)), Life(30.));
+    assert_eq!(pool.current(), Life(0.));
+
+    // Overheal
+    assert_eq!(pool.heal(Life(60.)), Life(60.));
+    assert_eq!(pool.heal(Life(60.)), Life(40.));
+    assert!(pool.is_full());
+
+    let mut stamina = StaminaPool::new(Stamina(3), Stamina(5), Stamina(0));
+    assert_eq!(stamina.add(Stamina(4)), Stamina(2));
+    assert_eq!(stamina.subtract(Stamina(7)), Stamina(5));
+    assert!(stamina.is_empty());
+}
+
+#[test]
+fn overhealing_decays_towards_max() {
+    let mut pool = LifePool::new(Life(90.), Life(100.), Life(5.));
+    assert_eq!(pool.heal(Life(20.)), Life(10.));
+
+    pool.overflow = OverflowPolicy::Decay {
+        decay_per_second: Life(10.),
+    };
+    assert_eq!(pool.heal(Life(25.)), Life(25.));
+    assert_eq!(pool.current(), Life(125.));
+    assert!(pool.is_full());
+
+    // Excess life decays without regenerating, and never drops below the max
+    pool.regenerate(Duration::from_secs(2));
+    assert_eq!(pool.current(), Life(105.));
+    pool.regenerate(Duration::from_secs(2));
+    assert_eq!(pool.current(), Life(100.));
+
+    // Damage is taken from the excess first
+    pool.damage(Life(10.));
+    assert_eq!(pool.current(), Life(90.));
+    pool.regenerate(Duration::from_secs(10));
+    assert_eq!(pool.current(), Life(100.));
+}
+
+#[test]
+fn overhealing_decays_without_regeneration() {
+    let mut pool = LifePool::new_full(Life(100.), Life(0.));
+    pool.overflow = OverflowPolicy::Decay {
+        decay_per_second: Life(10.),
+    };
+    pool.heal(Life(20.));
+
+    let mut world = World::new();
+    let entity = world.spawn(pool).id();
+
+    regenerate(&mut world, Duration::from_secs(1));
+    assert_eq!(world.get::<LifePool>(entity).unwrap().current(), Life(110.));
+    regenerate(&mut world, Duration::from_secs(2));
+    assert_eq!(world.get::<LifePool>(entity).unwrap().current(), Life(100.));
+}
+
+#[test]
+fn overflowing_pools_stay_finite() {
+    let mut mana_pool = ManaPool::new(Mana(50.), Mana(100.), Mana(5.));
+    mana_pool.overflow = OverflowPolicy::Decay {
+        decay_per_second: Mana(10.),
+    };
+
+    assert_eq!(mana_pool.set_current(Mana(f32::NAN)), Mana(50.));
+    assert_eq!(mana_pool.set_current(Mana(f32::INFINITY)), Mana(f32::MAX));
+    assert_eq!(mana_pool.set_current(Mana(f32::NEG_INFINITY)), Mana(0.));
+
+    let mut life_pool = LifePool::new(Life(50.), Life(100.), Life(5.));
+    life_pool.overflow = OverflowPolicy::Decay {
+        decay_per_second: Life(10.),
+    };
+    assert_eq!(life_pool.heal(Life(f32::NAN)), Life(0.));
+    assert_eq!(life_pool.current(), Life(50.));
+    life_pool.set_current(Life(f32::INFINITY));
+    assert!(life_pool.current().0.is_finite());
+}
+
+#[test]
+fn regeneration_waits_for_regen_delay() {
+    let mut pool = ManaPool::new_full(Mana(100.), Mana(10.));
+    pool.regen_delay = Duration::from_secs(2);
+    assert_eq!(pool.time_until_regen(), Duration::ZERO);
+
+    pool.expend(Mana(50.)).unwrap();
+    assert_eq!(pool.time_until_regen(), Duration::from_secs(2));
+
+    pool.regenerate(Duration::from_secs(1));
+    assert_eq!(pool.current(), Mana(50.));
+    assert_eq!(pool.time_until_regen(), Duration::from_secs(1));
+
+    // Only the time after the delay has passed is spent regenerating
+    pool.regenerate(Duration::from_secs(2));
+    assert_eq!(pool.current(), Mana(60.));
+    assert_eq!(pool.time_until_regen(), Duration::ZERO);
+}
+
+#[test]
+fn pool_events_are_sent_once_per_transition() {
+    use leafwing_abilities::pool::{PoolDepleted, PoolFull};
+
+    let mut app = App::new();
+    app.add_plugin(PoolPlugin::<LifePool>::default())
+        .init_resource::<Time>();
+    let entity = app
+        .world
+        .spawn(LifePool::new_full(Life(10.), Life(0.)))
+        .id();
+
+    let drain_events = |app: &mut App| {
+        let depleted: Vec<_> = app
+            .world
+            .resource_mut::<Events<PoolDepleted<LifePool>>>()
+            .drain()
+            .map(|event| event.entity)
+            .collect();
+        let full: Vec<_> = app
+            .world
+            .resource_mut::<Events<PoolFull<LifePool>>>()
+            .drain()
+            .map(|event| event.entity)
+            .collect();
+        (depleted, full)
+    };
+
+    // Newly added pools have not transitioned
+    app.update();
+    assert_eq!(drain_events(&mut app), (vec![], vec![]));
+
+    app.world
+        .get_mut::<LifePool>(entity)
+        .unwrap()
+        .set_current(Life(0.));
+    app.update();
+    assert_eq!(drain_events(&mut app), (vec![Some(entity)], vec![]));
+
+    // Staying empty does not send further events
+    app.world
+        .get_mut::<LifePool>(entity)
+        .unwrap()
+        .set_current(Life(0.));
+    app.update();
+    assert_eq!(drain_events(&mut app), (vec![], vec![]));
+
+    app.world
+        .get_mut::<LifePool>(entity)
+        .unwrap()
+        .replenish(Life(10.));
+    app.update();
+    assert_eq!(drain_events(&mut app), (vec![], vec![Some(entity)]));
+}
+
+#[test]
+fn pool_plugin_regenerates_pools() {
+    let mut app = App::new();
+    app.add_plugin(PoolPlugin::<ManaPool>::default());
+
+    let mut time = Time::default();
+    let start = time.startup();
+    time.update_with_instant(start);
+    time.update_with_instant(start + Duration::from_secs(1));
+    app.insert_resource(time);
+
+    let regenerating = app
+        .world
+        .spawn(ManaPool::new_empty(Mana(10.), Mana(2.)))
+        .id();
+    let static_pool = app
+        .world
+        .spawn(ManaPool::new_empty(Mana(10.), Mana(0.)))
+        .id();
+
+    app.update();
+    assert_eq!(
+        app.world.get::<ManaPool>(regenerating).unwrap().current(),
+        Mana(2.)
+    );
+    assert_eq!(
+        app.world.get::<ManaPool>(static_pool).unwrap().current(),
+        Mana(0.)
+    );
+}
+
+#[test]
+fn stamina_regenerates_whole_points() {
+    let mut pool = StaminaPool::new_full(Stamina(5), Stamina(2));
+    pool.expend(Stamina(1)).unwrap();
+    pool.expend(Stamina(4)).unwrap();
+    assert!(pool.is_empty());
+    assert!(pool.expend(Stamina(1)).is_err());
+
+    // Partial progress is carried over between ticks
+    pool.regenerate(Duration::from_millis(250));
+    assert_eq!(pool.current(), Stamina(0));
+    pool.regenerate(Duration::from_millis(250));
+    assert_eq!(pool.current(), Stamina(1));
+    pool.regenerate(Duration::from_millis(1750));
+    assert_eq!(pool.current(), Stamina(4));
+    assert_eq!(pool.regen_progress(), 0.5);
+
+    pool.regenerate(Duration::from_secs(10));
+    assert_eq!(pool.current(), Stamina(5));
+    assert_eq!(pool.regen_progress(), 0.0);
+}
+
+#[test]
+fn stamina_drains_fractional_points_between_frames() {
+    let mut pool = StaminaPool::new_full(Stamina(10), Stamina(0));
+    let frame = Duration::from_secs_f64(1. / 60.);
+
+    // Each frame only drains a tenth of a point
+    let mut spent = Stamina(0);
+    for _ in 0..35 {
+        spent += pool.expend_per_second(Stamina(6), frame);
+    }
+    assert_eq!(spent, Stamina(3));
+    assert_eq!(pool.current(), Stamina(7));
+
+    for _ in 0..30 {
+        pool.expend_per_second(Stamina(6), frame);
+    }
+    assert_eq!(pool.current(), Stamina(4));
+
+    // Draining stops once the pool runs dry
+    assert_eq!(
+        pool.expend_per_second(Stamina(6), Duration::from_secs(2)),
+        Stamina(4)
+    );
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn balanced_pools_can_go_negative() {
+    let mut poise = BalancedPool::new(Balance(20.), Balance(100.), Balance(0.));
+    assert_eq!(
+        poise.set_min(Balance(150.)),
+        Err(PoolError::MinGreaterThanMax)
+    );
+    poise.set_min(Balance(-100.)).unwrap();
+    assert_eq!(poise.min(), Balance(-100.));
+
+    // Costs can be paid down to the minimum, but not beyond it
+    poise.expend(Balance(70.)).unwrap();
+    assert_eq!(poise.current(), Balance(-50.));
+    assert!(poise.expend(Balance(60.)).is_err());
+    assert_eq!(poise.subtract(Balance(60.)), Balance(50.));
+    assert!(poise.is_empty());
+
+    // The fraction covers the whole range
+    poise.set_current(Balance(0.));
+    assert_eq!(poise.fraction(), 0.5);
+
+    // Raising the minimum raises the current value along with it
+    poise.set_min(Balance(10.)).unwrap();
+    assert_eq!(poise.current(), Balance(10.));
+    assert_eq!(
+        poise.set_max(Balance(5.)),
+        Err(PoolError::MinGreaterThanMax)
+    );
+}
+
+#[test]
+fn pool_modifiers_update_max() {
+    let mut app = App::new();
+    app.add_plugin(PoolPlugin::<LifePool>::default())
+        .init_resource::<Time>();
+    let entity = app
+        .world
+        .spawn((
+            LifePool::new(Life(50.), Life(100.), Life(0.)),
+            PoolModifiers::<LifePool>::new(Life(100.)),
+        ))
+        .id();
+
+    // A +50 max life buff scales the current life up
+    app.world
+        .get_mut::<PoolModifiers<LifePool>>(entity)
+        .unwrap()
+        .add_bonus(Life(50.));
+    app.update();
+    let life_pool = app.world.get::<LifePool>(entity).unwrap();
+    assert_eq!(life_pool.max(), Life(150.));
+    assert_eq!(life_pool.current(), Life(75.));
+
+    // Once healed to full, expiring the buff does not leave the pool over its max
+    app.world
+        .get_mut::<LifePool>(entity)
+        .unwrap()
+        .set_current(Life(150.));
+    app.world
+        .get_mut::<PoolModifiers<LifePool>>(entity)
+        .unwrap()
+        .remove_bonus(Life(50.));
+    app.update();
+    let life_pool = app.world.get::<LifePool>(entity).unwrap();
+    assert_eq!(life_pool.max(), Life(100.));
+    assert_eq!(life_pool.current(), Life(100.));
+}
+
+#[test]
+fn negative_regen_drains_to_zero() {
+    let mut life_pool = LifePool::new(Life(10.), Life(10.), Life(-4.));
+    life_pool.regen_delay = Duration::from_secs(5);
+    life_pool.set_max_shield(Life(5.)).unwrap();
+    life_pool.add_shield(Life(5.));
+
+    // Draining is continuous, ignoring the regen delay and the shield
+    life_pool.regenerate(Duration::from_secs(1));
+    assert_eq!(life_pool.current(), Life(6.));
+    life_pool.regenerate(Duration::from_secs(1));
+    assert_eq!(life_pool.current(), Life(2.));
+    assert_eq!(life_pool.current_shield(), Life(5.));
+
+    life_pool.regenerate(Duration::from_secs(1));
+    assert_eq!(life_pool.current(), LifePool::ZERO);
+
+    let mut mana_pool = ManaPool::new(Mana(3.), Mana(10.), Mana(-2.));
+    mana_pool.regenerate(Duration::from_secs(2));
+    assert_eq!(mana_pool.current(), ManaPool::ZERO);
+}
+
+#[test]
+fn manual_regeneration_only_regenerates_when_asked() {
+    let mut app = App::new();
+    app.add_plugin(PoolPlugin::<ManaPool>::default().with_manual_regeneration());
+
+    let mut time = Time::default();
+    let start = time.startup();
+    time.update_with_instant(start);
+    time.update_with_instant(start + Duration::from_secs(1));
+    app.insert_resource(time);
+
+    let entity = app
+        .world
+        .spawn(ManaPool::new_empty(Mana(10.), Mana(2.)))
+        .id();
+
+    app.update();
+    assert_eq!(
+        app.world.get::<ManaPool>(entity).unwrap().current(),
+        Mana(0.)
+    );
+
+    // One turn passes
+    PoolPlugin::<ManaPool>::regenerate_world(&mut app.world, Duration::from_secs(2));
+    assert_eq!(
+        app.world.get::<ManaPool>(entity).unwrap().current(),
+        Mana(4.)
+    );
+}
+
+#[test]
+fn many_pools_regenerate_independently() {
+    let mut world = World::new();
+    let entities: Vec<Entity> = (0..1000)
+        .map(|i| {
+            let regen = Mana((i % 5) as f32);
+            world.spawn(ManaPool::new_empty(Mana(100.), regen)).id()
+        })
+        .collect();
+
+    PoolPlugin::<ManaPool>::regenerate_world(&mut world, Duration::from_secs(2));
+
+    for (i, entity) in entities.iter().enumerate() {
+        assert_eq!(
+            world.get::<ManaPool>(*entity).unwrap().current(),
+            Mana((i % 5) as f32 * 2.)
+        );
+    }
+}
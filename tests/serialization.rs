@@ -0,0 +1,89 @@
+use bevy::utils::Duration;
+use leafwing_abilities::prelude::*;
+use leafwing_input_manager::Actionlike;
+
+#[derive(Actionlike, Abilitylike, Debug, Clone, Copy, PartialEq)]
+enum Action {
+    Dash,
+    Fireball,
+}
+
+#[test]
+fn cooldowns_round_trip_mid_cooldown() {
+    let mut cooldowns = CooldownState::new([
+        (Action::Dash, Cooldown::from_secs(2.)),
+        (Action::Fireball, Cooldown::from_secs(5.)),
+    ]);
+    cooldowns.trigger(Action::Fireball).unwrap();
+    cooldowns.tick(Duration::from_secs(1), None);
+
+    let serialized = ron::to_string(&cooldowns).unwrap();
+    let deserialized: CooldownState<Action> = ron::from_str(&serialized).unwrap();
+
+    assert_eq!(deserialized, cooldowns);
+    assert!(deserialized.ready(Action::Dash).is_ok());
+    assert!(deserialized.ready(Action::Fireball).is_err());
+    assert_eq!(
+        deserialized.remaining(Action::Fireball),
+        Some(Duration::from_secs(4))
+    );
+}
+
+#[test]
+fn charges_round_trip() {
+    let mut charges = ChargeState::new([(Action::Dash, Charges::replenish_one(2))]);
+    charges.expend(Action::Dash).unwrap();
+
+    let serialized = ron::to_string(&charges).unwrap();
+    let deserialized: ChargeState<Action> = ron::from_str(&serialized).unwrap();
+
+    assert_eq!(deserialized, charges);
+    assert_eq!(
+        deserialized.get(Action::Dash).as_ref().unwrap().charges(),
+        1
+    );
+    assert_eq!(deserialized.get(Action::Fireball), &None);
+}
+
+#[test]
+fn non_finite_rates_are_ignored_when_deserializing() {
+    let mut cooldown = Cooldown::from_secs(2.);
+    cooldown.set_rate(0.5);
+
+    let serialized = ron::to_string(&cooldown).unwrap();
+    assert!(serialized.contains("rate:0.5"));
+    let corrupted = serialized.replace("rate:0.5", "rate:NaN");
+    let deserialized: Cooldown = ron::from_str(&corrupted).unwrap();
+
+    assert_eq!(deserialized.rate(), 1.0);
+}
+
+#[test]
+fn cooldowns_saved_without_every_group_can_be_loaded() {
+    let cooldowns = CooldownState::new([(Action::Dash, Cooldown::from_secs(2.))]);
+    let serialized = ron::to_string(&cooldowns).unwrap();
+    assert!(serialized.contains(",group_cooldowns:[],group_vec:[None,None]"));
+
+    // Saved before cooldown groups existed
+    let without_groups = serialized.replace(",group_cooldowns:[],group_vec:[None,None]", "");
+    let deserialized: CooldownState<Action> = ron::from_str(&without_groups).unwrap();
+    assert_eq!(deserialized, cooldowns);
+
+    // Saved before `Action::Fireball` was added
+    let fewer_actions = serialized.replace("group_vec:[None,None]", "group_vec:[None]");
+    let deserialized: CooldownState<Action> = ron::from_str(&fewer_actions).unwrap();
+    assert_eq!(deserialized, cooldowns);
+    assert_eq!(deserialized.group(Action::Fireball), None);
+}
+
+#[test]
+fn stacked_cooldowns_without_stacks_cannot_be_loaded() {
+    use leafwing_abilities::cooldown::StackedCooldown;
+
+    let cooldown = StackedCooldown::new(Duration::from_secs(3), 2);
+    let serialized = ron::to_string(&cooldown).unwrap();
+    let deserialized: StackedCooldown = ron::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, cooldown);
+
+    assert!(ron::from_str::<StackedCooldown>("(stacks:[])").is_err());
+}
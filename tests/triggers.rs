@@ -0,0 +1,212 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use leafwing_abilities::prelude::*;
+use leafwing_abilities::recast::CastKind;
+use leafwing_abilities::triggers::{
+    AbilityTriggered, LastUsed, TriggerSpawners, TriggeredAbilities,
+};
+use leafwing_abilities::NullPool;
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Abilitylike, Debug, Clone, Copy)]
+enum Action {
+    Fireball,
+    Block,
+}
+
+#[derive(Component)]
+struct Projectile {
+    caster: Entity,
+}
+
+fn cast_fireball(mut query: Query<AbilityState<Action>>) {
+    for mut ability_state in query.iter_mut() {
+        let _ = ability_state.trigger(Action::Fireball);
+    }
+}
+
+#[test]
+fn triggering_runs_spawners_with_caster() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(AbilityPlugin::<Action>::default())
+        .add_system(cast_fireball);
+
+    app.world
+        .resource_mut::<TriggerSpawners<Action>>()
+        .on_trigger_spawn(Action::Fireball, |commands, caster| {
+            commands.spawn(Projectile { caster });
+        })
+        .on_trigger_spawn(Action::Block, |_, _| panic!("Block was never triggered"));
+
+    let caster = app
+        .world
+        .spawn((
+            AbilitiesBundle {
+                cooldowns: CooldownState::new([(Action::Fireball, Cooldown::from_secs(10.))]),
+                ..default()
+            },
+            ActionState::<Action>::default(),
+            TriggeredAbilities::<Action>::default(),
+        ))
+        .id();
+
+    app.update();
+
+    let mut query = app.world.query::<&Projectile>();
+    let projectiles: Vec<&Projectile> = query.iter(&app.world).collect();
+    assert_eq!(projectiles.len(), 1);
+    assert_eq!(projectiles[0].caster, caster);
+
+    // The fireball is now on cooldown, so nothing else is spawned
+    app.update();
+    assert_eq!(query.iter(&app.world).count(), 1);
+    assert!(app
+        .world
+        .get::<TriggeredAbilities<Action>>(caster)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn time_since_last_use_resets_on_trigger() {
+    let mut world = World::new();
+    let caster = world
+        .spawn((
+            AbilitiesBundle::<Action>::default(),
+            ActionState::<Action>::default(),
+            LastUsed::<Action>::default(),
+        ))
+        .id();
+    let mut query = world.query::<AbilityState<Action>>();
+
+    let time_since_last_use = |world: &World, action| {
+        world
+            .get::<LastUsed<Action>>(caster)
+            .unwrap()
+            .time_since_last_use(action)
+    };
+
+    // Idle frames do not count for abilities that were never used
+    AbilityPlugin::<Action>::tick_world::<NullPool>(&mut world, Duration::from_secs(1));
+    assert_eq!(time_since_last_use(&world, Action::Fireball), None);
+
+    query
+        .single_mut(&mut world)
+        .trigger(Action::Fireball)
+        .unwrap();
+    assert_eq!(
+        query.single(&world).time_since_last_use(Action::Fireball),
+        Some(Duration::ZERO)
+    );
+
+    AbilityPlugin::<Action>::tick_world::<NullPool>(&mut world, Duration::from_secs(1));
+    AbilityPlugin::<Action>::tick_world::<NullPool>(&mut world, Duration::from_secs(2));
+    assert_eq!(
+        time_since_last_use(&world, Action::Fireball),
+        Some(Duration::from_secs(3))
+    );
+    assert_eq!(time_since_last_use(&world, Action::Block), None);
+
+    query
+        .single_mut(&mut world)
+        .trigger(Action::Fireball)
+        .unwrap();
+    assert_eq!(
+        time_since_last_use(&world, Action::Fireball),
+        Some(Duration::ZERO)
+    );
+}
+
+#[derive(Resource, Default)]
+struct ObservedTriggers(Vec<usize>);
+
+fn observe_triggers(
+    query: Query<&TriggeredAbilities<Action>>,
+    mut observed: ResMut<ObservedTriggers>,
+) {
+    let n_triggered = query.iter().map(|triggered| triggered.iter().count()).sum();
+    observed.0.push(n_triggered);
+}
+
+#[test]
+fn triggered_abilities_are_cleared_at_end_of_frame() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(AbilityPlugin::<Action>::default())
+        .init_resource::<ObservedTriggers>()
+        .add_system(cast_fireball)
+        .add_system(observe_triggers.in_base_set(CoreSet::PostUpdate));
+
+    let caster = app
+        .world
+        .spawn((
+            AbilitiesBundle {
+                cooldowns: CooldownState::new([(Action::Fireball, Cooldown::from_secs(10.))]),
+                ..default()
+            },
+            ActionState::<Action>::default(),
+            TriggeredAbilities::<Action>::default(),
+        ))
+        .id();
+
+    app.update();
+    assert!(app
+        .world
+        .get::<TriggeredAbilities<Action>>(caster)
+        .unwrap()
+        .is_empty());
+
+    // The fireball is on cooldown, so nothing is triggered on the second frame
+    app.update();
+    assert_eq!(app.world.resource::<ObservedTriggers>().0, vec![1, 0]);
+}
+
+#[test]
+fn triggers_are_sent_as_events() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(AbilityPlugin::<Action>::default())
+        .add_system(cast_fireball);
+
+    let caster = app
+        .world
+        .spawn((
+            AbilitiesBundle {
+                cooldowns: CooldownState::new([(Action::Fireball, Cooldown::from_secs(10.))]),
+                ..default()
+            },
+            ActionState::<Action>::default(),
+            TriggeredAbilities::<Action>::default(),
+        ))
+        .id();
+
+    let mut reader = app
+        .world
+        .resource::<Events<AbilityTriggered<Action>>>()
+        .get_reader();
+
+    app.update();
+    let events: Vec<_> = reader
+        .iter(app.world.resource::<Events<AbilityTriggered<Action>>>())
+        .cloned()
+        .collect();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].entity, caster);
+    assert!(matches!(events[0].action, Action::Fireball));
+    assert_eq!(events[0].cast_kind, CastKind::Initial);
+    assert_eq!(events[0].timestamp, app.world.resource::<Time>().elapsed());
+
+    // The fireball is on cooldown, so nothing is triggered on the second frame
+    app.update();
+    assert_eq!(
+        reader
+            .iter(app.world.resource::<Events<AbilityTriggered<Action>>>())
+            .count(),
+        0
+    );
+}